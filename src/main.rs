@@ -1,31 +1,85 @@
+use clap::Parser;
 use ratatui::DefaultTerminal;
 use ratatui::widgets::TableState;
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
+use todo_cli::tasks::adapters::cli::cli_command::{Cli, TodoCommand};
+use todo_cli::tasks::adapters::cli::handler::handle_command;
+use todo_cli::tasks::adapters::cli::recovery::offer_backup_if_corrupted;
+use todo_cli::tasks::adapters::clock::system_clock::SystemClock;
 use todo_cli::tasks::adapters::persistence::json_file_task_repository::JsonFileTaskRepository;
 use todo_cli::tasks::adapters::tui::app::App;
 use todo_cli::tasks::adapters::tui::event::handle_events;
 use todo_cli::tasks::adapters::tui::ui::draw;
+use todo_cli::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use todo_cli::tasks::ports::outputs::clock::Clock;
+use todo_cli::tasks::ports::outputs::journal_repository::JournalRepository;
+use todo_cli::tasks::ports::outputs::project_repository::ProjectRepository;
 use todo_cli::tasks::ports::outputs::task_repository::TaskRepository;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let repo: JsonFileTaskRepository = JsonFileTaskRepository::new()?;
-    run(repo)
+fn main() -> ExitCode {
+    let cli: Cli = Cli::parse();
+    let result: Result<(), Box<dyn std::error::Error>> = JsonFileTaskRepository::new()
+        .map_err(Into::into)
+        .and_then(|repo| check_data_file(&repo, cli.command.as_ref()).map(|()| repo))
+        .and_then(|repo| match cli.command {
+            None | Some(TodoCommand::Tui) => run(repo, SystemClock),
+            Some(command) => {
+                handle_command(repo, SystemClock, command, cli.output).map_err(Into::into)
+            }
+        });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
 }
 
-fn run<R: TaskRepository + Clone>(repo: R) -> Result<(), Box<dyn std::error::Error>> {
-    let mut app: App<R> = App::new(repo)?;
+/// Backup commands are how a corrupted data file gets repaired, so they skip
+/// the check.
+fn check_data_file(
+    repo: &JsonFileTaskRepository,
+    command: Option<&TodoCommand>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if matches!(command, Some(TodoCommand::Backup { .. })) {
+        return Ok(());
+    }
+    let interactive: bool = io::stdin().is_terminal() && io::stderr().is_terminal();
+    offer_backup_if_corrupted(
+        repo,
+        interactive,
+        &mut io::stdin().lock(),
+        &mut io::stderr(),
+    )?;
+    Ok(())
+}
+
+fn run<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    repo: R,
+    clock: C,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut app: App<R, C> = App::new(repo, clock)?;
     let mut terminal: DefaultTerminal = ratatui::init();
     let mut table_state: TableState = TableState::default();
     let result = loop_app(&mut terminal, &mut app, &mut table_state);
     ratatui::restore(); // SIEMPRE se ejecuta, haya error o no
     result
 }
-fn loop_app<R: TaskRepository + Clone>(
+fn loop_app<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
     terminal: &mut DefaultTerminal,
-    app: &mut App<R>,
+    app: &mut App<R, C>,
     table_state: &mut TableState,
 ) -> Result<(), Box<dyn std::error::Error>> {
     while !app.should_quit {
-        table_state.select(Some(app.selected));
+        table_state.select(Some(app.cursor()));
         terminal.draw(|frame| draw(frame, app, table_state))?;
         handle_events(app)?;
     }
@@ -1,15 +1,36 @@
-use crate::tasks::application::use_cases::list_tasks::{FilterTask, ListTasksCommand};
-use clap::{Parser, Subcommand, ValueEnum};
+use crate::tasks::application::use_cases::add_task::AddTaskCommand;
+use crate::tasks::application::use_cases::delete_project::DeleteProjectPolicy;
+use crate::tasks::application::use_cases::edit_task::EditFields;
+use crate::tasks::application::use_cases::list_tasks::{
+    DueFilter, FilterTask, ListTasksCommand, SortKey, TaskSort,
+};
+use crate::tasks::application::use_cases::resolve_task_id::TaskRef;
+use crate::tasks::domain::due_date::DueDate;
+use crate::tasks::domain::priority::Priority;
+use crate::tasks::domain::project::INBOX;
+use crate::tasks::domain::recurrence::Recurrence;
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
 use uuid::Uuid;
 
+/// How task references are read, shown below the top-level help.
+const TASK_REF_HELP: &str = "Wherever a task id is taken, a unique id prefix or a list number works too. \
+List numbers count through every task sorted oldest first, as a plain `todo list` shows them, \
+not through a filtered or sorted list (trash commands count the trash, most recently deleted \
+first, and `unarchive` the archive). A number that no task has is matched as an id prefix.";
+
 #[derive(Debug, Parser)]
-#[command(name = "todo", version, about = "Manage tasks from the terminal")]
+#[command(
+    name = "todo",
+    version,
+    about = "Manage tasks from the terminal",
+    after_help = TASK_REF_HELP
+)]
 pub struct Cli {
     #[arg(long, value_enum, global = true, default_value_t = OutputFormat::Table)]
     pub output: OutputFormat,
 
     #[command(subcommand)]
-    pub command: TodoCommand,
+    pub command: Option<TodoCommand>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -20,21 +41,316 @@ pub enum OutputFormat {
 
 #[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
 pub enum TodoCommand {
-    Add {
-        title: String,
-    },
-    List {
-        #[arg(long, value_enum, default_value_t = StatusArg::All)]
-        status: StatusArg,
-    },
+    /// Add a task
+    Add(AddArgs),
+    /// List tasks, oldest first unless sorted otherwise
+    List(ListArgs),
+    /// Mark tasks as done
     Done {
-        id: Uuid,
+        #[command(flatten)]
+        selection: TaskSelection,
+        /// Also complete open subtasks instead of refusing
+        #[arg(long)]
+        cascade: bool,
+        /// Only report what would change
+        #[arg(long)]
+        dry_run: bool,
     },
+    /// Move tasks back to todo
     Todo {
-        id: Uuid,
+        #[command(flatten)]
+        selection: TaskSelection,
+        /// Only report what would change
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Mark a task as in progress
+    Start { id: TaskRef },
+    /// Mark a task as blocked, optionally saying why
+    Block {
+        id: TaskRef,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Mark a task as cancelled
+    Cancel { id: TaskRef },
+    /// Move tasks, and their subtasks, to the trash
+    Delete {
+        #[command(flatten)]
+        selection: TaskSelection,
+        /// Only report what would change
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Change the fields of tasks
+    Edit(EditArgs),
+    /// Edit a task's notes in `$VISUAL` / `$EDITOR`
+    Note { id: TaskRef },
+    /// Add, list, rename or delete projects
+    Project {
+        #[command(subcommand)]
+        command: ProjectCommand,
+    },
+    /// Manage which tasks block which
+    Dep {
+        #[command(subcommand)]
+        command: DepCommand,
     },
+    /// Find tasks whose title or notes match every term, best matches first
+    Search {
+        #[arg(required = true, num_args = 1..)]
+        terms: Vec<String>,
+    },
+    /// Move done or cancelled tasks to the archive
+    #[command(group(ArgGroup::new("selection").required(true).multiple(true)))]
+    Archive {
+        #[arg(group = "selection")]
+        ids: Vec<TaskRef>,
+        /// Archive every done or cancelled task untouched for this long (`<N>d`)
+        #[arg(long, value_parser = parse_days, group = "selection")]
+        older_than: Option<u32>,
+    },
+    /// Move an archived task back to the task list
+    Unarchive { id: TaskRef },
+    /// List, restore or purge deleted tasks
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommand,
+    },
+    /// List, take or restore backups of the data file
+    Backup {
+        #[command(subcommand)]
+        command: BackupCommand,
+    },
+    /// Revert the latest change to tasks
+    Undo,
+    /// Re-apply the latest undone change
+    Redo,
+    /// Open the interactive interface (the default)
+    Tui,
+}
+
+impl TodoCommand {
+    /// Name under which the command's changes are journaled.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TodoCommand::Add(_) => "add",
+            TodoCommand::List(_) => "list",
+            TodoCommand::Done { .. } => "done",
+            TodoCommand::Todo { .. } => "todo",
+            TodoCommand::Start { .. } => "start",
+            TodoCommand::Block { .. } => "block",
+            TodoCommand::Cancel { .. } => "cancel",
+            TodoCommand::Delete { .. } => "delete",
+            TodoCommand::Edit(_) => "edit",
+            TodoCommand::Note { .. } => "note",
+            TodoCommand::Project { .. } => "project",
+            TodoCommand::Dep { .. } => "dep",
+            TodoCommand::Search { .. } => "search",
+            TodoCommand::Archive { .. } => "archive",
+            TodoCommand::Unarchive { .. } => "unarchive",
+            TodoCommand::Trash { .. } => "trash",
+            TodoCommand::Backup { .. } => "backup",
+            TodoCommand::Undo => "undo",
+            TodoCommand::Redo => "redo",
+            TodoCommand::Tui => "tui",
+        }
+    }
+}
+
+/// The tasks a command acts on: any number of ids, the tasks matching a
+/// filter expression, or both.
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+#[command(about = None, long_about = None)]
+#[group(required = true, multiple = true)]
+pub struct TaskSelection {
+    /// Task ids, unique id prefixes or list numbers (see `todo --help`)
+    pub ids: Vec<TaskRef>,
+    /// Also every task matching this filter, e.g. `tag:sprint-12`
+    #[arg(long = "where", value_name = "EXPR")]
+    pub filter: Option<String>,
+}
+
+impl TaskSelection {
+    pub fn id(id: TaskRef) -> Self {
+        Self {
+            ids: vec![id],
+            filter: None,
+        }
+    }
+
+    /// The task when exactly one is named and there is no filter.
+    pub fn single(&self) -> Option<&TaskRef> {
+        match (self.ids.as_slice(), &self.filter) {
+            ([id], None) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub struct ListArgs {
+    #[arg(long, value_enum, default_value_t = StatusArg::All)]
+    pub status: StatusArg,
+    #[arg(long, value_enum)]
+    pub priority: Option<PriorityArg>,
+    /// `overdue`, `today` or `<N>d` for tasks due within N days
+    #[arg(long, value_parser = parse_due_filter)]
+    pub due: Option<DueFilter>,
+    /// Only tasks carrying this tag (repeat to require several)
+    #[arg(long = "tag")]
+    pub tags: Vec<String>,
+    /// Only tasks carrying at least one of these tags
+    #[arg(long = "any-tag")]
+    pub any_tags: Vec<String>,
+    /// Only tasks of this project (`inbox` for tasks without project)
+    #[arg(long)]
+    pub project: Option<String>,
+    /// Only open tasks whose blockers are all closed
+    #[arg(long)]
+    pub ready: bool,
+    /// List archived tasks instead (read-only)
+    #[arg(long)]
+    pub archived: bool,
+    /// Filter expression, e.g. `status:todo and (tag:backend or priority>=high)`
+    #[arg(long = "where", value_name = "EXPR")]
+    pub filter: Option<String>,
+    /// Order of the listed tasks
+    #[arg(long, value_enum, default_value_t = SortArg::Created)]
+    pub sort: SortArg,
+    /// Sort in descending order
+    #[arg(long)]
+    pub reverse: bool,
+    /// Show at most this many tasks
+    #[arg(long)]
+    pub limit: Option<usize>,
+    /// Skip this many tasks first
+    #[arg(long, default_value_t = 0)]
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub struct AddArgs {
+    /// Title words; words starting with `+` become tags (`todo add Fix bug +backend`)
+    #[arg(required = true, num_args = 1..)]
+    pub title: Vec<String>,
+    #[arg(long, value_enum)]
+    pub priority: Option<PriorityArg>,
+    #[arg(long, value_parser = DueDate::parse)]
+    pub due: Option<DueDate>,
+    /// `daily`, `weekly`, `monthly` or an RRULE such as `FREQ=WEEKLY;BYDAY=MO,TH`
+    #[arg(long, value_parser = Recurrence::parse)]
+    pub repeat: Option<Recurrence>,
+    /// Longer free text kept out of the title
+    #[arg(long)]
+    pub description: Option<String>,
+    /// Read the description (Markdown) from stdin
+    #[arg(long, conflicts_with = "description")]
+    pub note: bool,
+    /// Create the task as a subtask of this task
+    #[arg(long)]
+    pub parent: Option<TaskRef>,
+    #[arg(long)]
+    pub project: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+#[command(group(ArgGroup::new("fields").required(true).multiple(true)))]
+pub struct EditArgs {
+    #[command(flatten)]
+    pub selection: TaskSelection,
+    /// Only report what would change
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long, group = "fields")]
+    pub title: Option<String>,
+    #[arg(long, value_enum, group = "fields")]
+    pub priority: Option<PriorityArg>,
+    #[arg(long, value_parser = DueDate::parse, group = "fields")]
+    pub due: Option<DueDate>,
+    #[arg(long, group = "fields", conflicts_with = "due")]
+    pub clear_due: bool,
+    #[arg(long, value_parser = Recurrence::parse, group = "fields")]
+    pub repeat: Option<Recurrence>,
+    /// Stop the task from recurring
+    #[arg(long, group = "fields", conflicts_with = "repeat")]
+    pub no_repeat: bool,
+    #[arg(long = "tag", group = "fields")]
+    pub add_tags: Vec<String>,
+    #[arg(long = "untag", group = "fields")]
+    pub remove_tags: Vec<String>,
+    #[arg(long, group = "fields")]
+    pub description: Option<String>,
+    #[arg(long, group = "fields", conflicts_with = "description")]
+    pub clear_description: bool,
+    /// Read the description (Markdown) from stdin
+    #[arg(long, group = "fields", conflicts_with_all = ["description", "clear_description"])]
+    pub note: bool,
+    #[arg(long, group = "fields")]
+    pub parent: Option<TaskRef>,
+    /// Turn a subtask back into a top-level task
+    #[arg(long, group = "fields", conflicts_with = "parent")]
+    pub no_parent: bool,
+    #[arg(long, group = "fields")]
+    pub project: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum ProjectCommand {
+    /// Create a project
+    Add { name: String },
+    /// List projects with their open and total task counts
+    List,
+    /// Rename a project
+    Rename { name: String, new_name: String },
+    /// Delete a project; its tasks are either moved or deleted with it.
+    /// This cannot be undone with `todo undo`
+    #[command(group(ArgGroup::new("policy").required(true)))]
     Delete {
-        id: Uuid,
+        name: String,
+        /// Move the project's tasks to this project (`inbox` allowed)
+        #[arg(long, group = "policy")]
+        move_to: Option<String>,
+        /// Move the project's tasks to the trash too
+        #[arg(long, group = "policy")]
+        cascade: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum DepCommand {
+    /// Make `id` wait for `blocker`
+    Add { id: TaskRef, blocker: TaskRef },
+    /// Stop `id` waiting for `blocker`
+    Remove { id: TaskRef, blocker: TaskRef },
+    /// Show the tasks blocking `id`
+    List { id: TaskRef },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum TrashCommand {
+    /// Show trashed tasks, most recently deleted first
+    List,
+    /// Take a task, and the subtasks deleted with it, out of the trash
+    Restore { id: TaskRef },
+    /// Delete trashed tasks for good
+    Empty {
+        /// Only purge tasks deleted at least this long ago (`<N>d`)
+        #[arg(long, value_parser = parse_days)]
+        older_than: Option<u32>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum BackupCommand {
+    /// Show backups, newest first
+    List,
+    /// Back up the data file now
+    Create,
+    /// Replace the data file with a backup, backing up the current one first
+    Restore {
+        /// Backup name as shown by `todo backup list`
+        name: String,
     },
 }
 
@@ -42,7 +358,54 @@ pub enum TodoCommand {
 pub enum StatusArg {
     All,
     Todo,
+    InProgress,
+    Blocked,
     Done,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortArg {
+    Created,
+    Modified,
+    Title,
+    Status,
+    Priority,
+    Due,
+}
+
+impl From<SortArg> for SortKey {
+    fn from(value: SortArg) -> Self {
+        match value {
+            SortArg::Created => SortKey::Created,
+            SortArg::Modified => SortKey::Modified,
+            SortArg::Title => SortKey::Title,
+            SortArg::Status => SortKey::Status,
+            SortArg::Priority => SortKey::Priority,
+            SortArg::Due => SortKey::Due,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PriorityArg {
+    None,
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl From<PriorityArg> for Priority {
+    fn from(value: PriorityArg) -> Self {
+        match value {
+            PriorityArg::None => Priority::None,
+            PriorityArg::Low => Priority::Low,
+            PriorityArg::Medium => Priority::Medium,
+            PriorityArg::High => Priority::High,
+            PriorityArg::Urgent => Priority::Urgent,
+        }
+    }
 }
 
 impl From<StatusArg> for ListTasksCommand {
@@ -51,18 +414,163 @@ impl From<StatusArg> for ListTasksCommand {
     }
 }
 
+pub fn parse_due_filter(value: &str) -> Result<DueFilter, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "overdue" => Ok(DueFilter::Overdue),
+        "today" => Ok(DueFilter::Today),
+        other => other
+            .strip_suffix('d')
+            .and_then(|days| days.parse::<u32>().ok())
+            .map(DueFilter::Within)
+            .ok_or_else(|| {
+                format!("invalid due filter '{value}' (expected overdue, today or <N>d)")
+            }),
+    }
+}
+
+pub fn parse_days(value: &str) -> Result<u32, String> {
+    value
+        .trim()
+        .to_ascii_lowercase()
+        .strip_suffix('d')
+        .and_then(|days| days.parse::<u32>().ok())
+        .ok_or_else(|| format!("invalid age '{value}' (expected <N>d)"))
+}
+
+/// Splits `+tag` words out of the title words. Only a whole shell word is a
+/// tag, so quoted text such as `"learn c +c++"` stays in the title.
+pub fn split_inline_tags(words: &[String]) -> (String, Vec<String>) {
+    let (tags, title): (Vec<&str>, Vec<&str>) =
+        words.iter().map(String::as_str).partition(|word| {
+            word.len() > 1 && word.starts_with('+') && !word.contains(char::is_whitespace)
+        });
+    (
+        title.join(" "),
+        tags.into_iter().map(str::to_string).collect(),
+    )
+}
+
+impl AddArgs {
+    /// The command for these arguments, with `--parent` already resolved.
+    pub fn into_command(self, parent: Option<Uuid>) -> AddTaskCommand {
+        let (title, tags) = split_inline_tags(&self.title);
+        let mut command = AddTaskCommand::new(title).with_tags(tags);
+        if let Some(priority) = self.priority {
+            command = command.with_priority(priority.into());
+        }
+        if let Some(due) = self.due {
+            command = command.with_due(due);
+        }
+        if let Some(repeat) = self.repeat {
+            command = command.with_recurrence(repeat);
+        }
+        if let Some(description) = self.description {
+            command = command.with_description(description);
+        }
+        if let Some(parent) = parent {
+            command = command.with_parent(parent);
+        }
+        if let Some(project) = self.project {
+            command = command.with_project(project);
+        }
+        command
+    }
+}
+
+impl From<ListArgs> for ListTasksCommand {
+    fn from(args: ListArgs) -> Self {
+        let mut command = ListTasksCommand::from(args.status)
+            .with_all_tags(args.tags)
+            .with_any_tags(args.any_tags);
+        if let Some(project) = args.project {
+            command = command.with_project(project);
+        }
+        if let Some(priority) = args.priority {
+            command = command.with_priority(priority.into());
+        }
+        if let Some(due) = args.due {
+            command = command.with_due(due);
+        }
+        if args.ready {
+            command = command.with_ready();
+        }
+        if args.archived {
+            command = command.with_archived();
+        }
+        if let Some(filter) = args.filter {
+            command = command.with_where(filter);
+        }
+        let sort = TaskSort::new(args.sort.into());
+        command = command
+            .with_sort(if args.reverse { sort.reversed() } else { sort })
+            .with_offset(args.offset);
+        if let Some(limit) = args.limit {
+            command = command.with_limit(limit);
+        }
+        command
+    }
+}
+
+impl EditArgs {
+    /// The fields these arguments change, with `--parent` already resolved.
+    pub fn into_fields(self, parent: Option<Uuid>) -> EditFields {
+        let mut fields = EditFields::new()
+            .with_added_tags(self.add_tags)
+            .with_removed_tags(self.remove_tags);
+        if let Some(title) = self.title {
+            fields = fields.with_title(title);
+        }
+        if let Some(priority) = self.priority {
+            fields = fields.with_priority(priority.into());
+        }
+        if self.due.is_some() || self.clear_due {
+            fields = fields.with_due(self.due);
+        }
+        if self.repeat.is_some() || self.no_repeat {
+            fields = fields.with_recurrence(self.repeat);
+        }
+        if self.description.is_some() || self.clear_description {
+            fields = fields.with_description(self.description);
+        }
+        if parent.is_some() || self.no_parent {
+            fields = fields.with_parent(parent);
+        }
+        if let Some(project) = self.project {
+            fields = fields.with_project(project);
+        }
+        fields
+    }
+}
+
+pub fn delete_policy_from_args(move_to: Option<String>, cascade: bool) -> DeleteProjectPolicy {
+    match move_to {
+        Some(target) => DeleteProjectPolicy::MoveTo(target),
+        None if cascade => DeleteProjectPolicy::Cascade,
+        None => DeleteProjectPolicy::MoveTo(INBOX.to_string()),
+    }
+}
+
 pub fn status_command_to_filter_task(command: StatusArg) -> FilterTask {
     match command {
         StatusArg::All => FilterTask::All,
         StatusArg::Todo => FilterTask::Todo,
+        StatusArg::InProgress => FilterTask::InProgress,
+        StatusArg::Blocked => FilterTask::Blocked,
         StatusArg::Done => FilterTask::Done,
+        StatusArg::Cancelled => FilterTask::Cancelled,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Cli, OutputFormat, StatusArg, TodoCommand};
-    use clap::Parser;
+    use super::{
+        AddArgs, Cli, DepCommand, EditArgs, ListArgs, OutputFormat, PriorityArg, ProjectCommand,
+        SortArg, StatusArg, TaskSelection, TodoCommand, TrashCommand, split_inline_tags,
+    };
+    use crate::tasks::application::use_cases::list_tasks::DueFilter;
+    use crate::tasks::application::use_cases::resolve_task_id::TaskRef;
+    use crate::tasks::domain::due_date::DueDate;
+    use clap::{CommandFactory, Parser};
     use uuid::Uuid;
 
     #[test]
@@ -73,9 +581,16 @@ mod tests {
 
         assert_eq!(
             cli.command,
-            TodoCommand::Add {
-                title: "Buy milk".to_string(),
-            }
+            Some(TodoCommand::Add(AddArgs {
+                title: vec!["Buy milk".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }))
         );
     }
 
@@ -87,9 +602,21 @@ mod tests {
 
         assert_eq!(
             cli.command,
-            TodoCommand::List {
+            Some(TodoCommand::List(ListArgs {
                 status: StatusArg::All,
-            }
+                priority: None,
+                due: None,
+                tags: vec![],
+                any_tags: vec![],
+                project: None,
+                ready: false,
+                archived: false,
+                filter: None,
+                sort: SortArg::Created,
+                reverse: false,
+                limit: None,
+                offset: 0,
+            }))
         );
     }
 
@@ -102,9 +629,21 @@ mod tests {
 
         assert_eq!(
             cli.command,
-            TodoCommand::List {
+            Some(TodoCommand::List(ListArgs {
                 status: StatusArg::Done,
-            }
+                priority: None,
+                due: None,
+                tags: vec![],
+                any_tags: vec![],
+                project: None,
+                ready: false,
+                archived: false,
+                filter: None,
+                sort: SortArg::Created,
+                reverse: false,
+                limit: None,
+                offset: 0,
+            }))
         );
     }
 
@@ -116,7 +655,64 @@ mod tests {
 
         assert_eq!(cli.output, OutputFormat::Table);
 
-        assert_eq!(cli.command, TodoCommand::Done { id });
+        assert_eq!(
+            cli.command,
+            Some(TodoCommand::Done {
+                selection: TaskSelection::id(TaskRef::Id(id)),
+                dry_run: false,
+                cascade: false
+            })
+        );
+    }
+
+    #[test]
+    fn parses_several_ids_or_a_filter_for_bulk_commands() {
+        let ids = Cli::try_parse_from(["todo", "delete", "1", "2", "--dry-run"])
+            .expect("cli should parse ids");
+        let filter = Cli::try_parse_from(["todo", "todo", "--where", "tag:sprint-12"])
+            .expect("cli should parse a filter");
+
+        assert_eq!(
+            ids.command,
+            Some(TodoCommand::Delete {
+                selection: TaskSelection {
+                    ids: vec![TaskRef::Index(1), TaskRef::Index(2)],
+                    filter: None,
+                },
+                dry_run: true,
+            })
+        );
+        assert_eq!(
+            filter.command,
+            Some(TodoCommand::Todo {
+                selection: TaskSelection {
+                    ids: vec![],
+                    filter: Some("tag:sprint-12".to_string()),
+                },
+                dry_run: false,
+            })
+        );
+        assert!(Cli::try_parse_from(["todo", "done", "--dry-run"]).is_err());
+    }
+
+    #[test]
+    fn parses_id_prefixes_and_list_numbers() {
+        let prefix = Cli::try_parse_from(["todo", "start", "3F2a"]).expect("cli should parse");
+        let number = Cli::try_parse_from(["todo", "start", "2"]).expect("cli should parse");
+
+        assert_eq!(
+            prefix.command,
+            Some(TodoCommand::Start {
+                id: TaskRef::Prefix("3f2a".to_string())
+            })
+        );
+        assert_eq!(
+            number.command,
+            Some(TodoCommand::Start {
+                id: TaskRef::Index(2)
+            })
+        );
+        assert!(Cli::try_parse_from(["todo", "start", "not-an-id"]).is_err());
     }
 
     #[test]
@@ -127,16 +723,515 @@ mod tests {
         assert_eq!(cli.output, OutputFormat::Json);
         assert_eq!(
             cli.command,
-            TodoCommand::List {
+            Some(TodoCommand::List(ListArgs {
                 status: StatusArg::All,
-            }
+                priority: None,
+                due: None,
+                tags: vec![],
+                any_tags: vec![],
+                project: None,
+                ready: false,
+                archived: false,
+                filter: None,
+                sort: SortArg::Created,
+                reverse: false,
+                limit: None,
+                offset: 0,
+            }))
         );
     }
 
+    #[test]
+    fn parses_edit_command_with_title() {
+        let id = Uuid::new_v4();
+        let cli = Cli::try_parse_from(["todo", "edit", &id.to_string(), "--title", "Buy oat milk"])
+            .expect("cli should parse edit");
+
+        assert_eq!(
+            cli.command,
+            Some(TodoCommand::Edit(EditArgs {
+                selection: TaskSelection::id(TaskRef::Id(id)),
+                dry_run: false,
+                title: Some("Buy oat milk".to_string()),
+                priority: None,
+                due: None,
+                clear_due: false,
+                repeat: None,
+                no_repeat: false,
+                add_tags: vec![],
+                remove_tags: vec![],
+                description: None,
+                clear_description: false,
+                note: false,
+                parent: None,
+                no_parent: false,
+                project: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_add_command_with_priority() {
+        let cli = Cli::try_parse_from(["todo", "add", "Fix prod", "--priority", "urgent"])
+            .expect("cli should parse add with priority");
+
+        assert_eq!(
+            cli.command,
+            Some(TodoCommand::Add(AddArgs {
+                title: vec!["Fix prod".to_string()],
+                priority: Some(PriorityArg::Urgent),
+                due: None,
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_edit_command_with_only_priority() {
+        let id = Uuid::new_v4();
+        let cli = Cli::try_parse_from(["todo", "edit", &id.to_string(), "--priority", "low"])
+            .expect("cli should parse edit with priority");
+
+        assert_eq!(
+            cli.command,
+            Some(TodoCommand::Edit(EditArgs {
+                selection: TaskSelection::id(TaskRef::Id(id)),
+                dry_run: false,
+                title: None,
+                priority: Some(PriorityArg::Low),
+                due: None,
+                clear_due: false,
+                repeat: None,
+                no_repeat: false,
+                add_tags: vec![],
+                remove_tags: vec![],
+                description: None,
+                clear_description: false,
+                note: false,
+                parent: None,
+                no_parent: false,
+                project: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_priority() {
+        let parsed = Cli::try_parse_from(["todo", "add", "Fix prod", "--priority", "critical"]);
+
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn parses_add_command_with_due_date() {
+        let cli = Cli::try_parse_from(["todo", "add", "File taxes", "--due", "2026-04-30"])
+            .expect("cli should parse add with due date");
+
+        assert_eq!(
+            cli.command,
+            Some(TodoCommand::Add(AddArgs {
+                title: vec!["File taxes".to_string()],
+                priority: None,
+                due: Some(DueDate::parse("2026-04-30").expect("due date should parse")),
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_list_command_with_due_filters() {
+        for (arg, expected) in [
+            ("overdue", DueFilter::Overdue),
+            ("today", DueFilter::Today),
+            ("7d", DueFilter::Within(7)),
+        ] {
+            let cli = Cli::try_parse_from(["todo", "list", "--due", arg])
+                .expect("cli should parse list with due filter");
+
+            assert_eq!(
+                cli.command,
+                Some(TodoCommand::List(ListArgs {
+                    status: StatusArg::All,
+                    priority: None,
+                    due: Some(expected),
+                    tags: vec![],
+                    any_tags: vec![],
+                    project: None,
+                    ready: false,
+                    archived: false,
+                    filter: None,
+                    sort: SortArg::Created,
+                    reverse: false,
+                    limit: None,
+                    offset: 0,
+                }))
+            );
+        }
+    }
+
+    #[test]
+    fn parses_list_command_with_where_expression() {
+        let cli = Cli::try_parse_from([
+            "todo",
+            "list",
+            "--where",
+            "status:todo and title~\"deploy\"",
+        ])
+        .expect("cli should parse list with where");
+
+        let Some(TodoCommand::List(args)) = cli.command else {
+            panic!("expected list command");
+        };
+        assert_eq!(
+            args.filter.as_deref(),
+            Some("status:todo and title~\"deploy\"")
+        );
+    }
+
+    #[test]
+    fn parses_list_command_with_sort_and_pagination() {
+        let cli = Cli::try_parse_from([
+            "todo",
+            "list",
+            "--sort",
+            "due",
+            "--reverse",
+            "--limit",
+            "10",
+            "--offset",
+            "20",
+        ])
+        .expect("cli should parse list with sort");
+
+        let Some(TodoCommand::List(args)) = cli.command else {
+            panic!("expected list command");
+        };
+        assert_eq!(
+            (args.sort, args.reverse, args.limit, args.offset),
+            (SortArg::Due, true, Some(10), 20)
+        );
+        assert!(Cli::try_parse_from(["todo", "list", "--sort", "size"]).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_due_values() {
+        assert!(Cli::try_parse_from(["todo", "add", "x", "--due", "someday"]).is_err());
+        assert!(Cli::try_parse_from(["todo", "list", "--due", "soon"]).is_err());
+    }
+
+    #[test]
+    fn rejects_edit_command_with_due_and_clear_due() {
+        let id = Uuid::new_v4();
+        let parsed = Cli::try_parse_from([
+            "todo",
+            "edit",
+            &id.to_string(),
+            "--due",
+            "2026-04-30",
+            "--clear-due",
+        ]);
+
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn parses_add_command_with_inline_tags() {
+        let cli = Cli::try_parse_from(["todo", "add", "Fix bug", "+backend", "+urgent"])
+            .expect("cli should parse add with tags");
+
+        let Some(TodoCommand::Add(AddArgs { title, .. })) = cli.command else {
+            panic!("expected add command");
+        };
+        let (title, tags) = split_inline_tags(&title);
+        assert_eq!(title, "Fix bug");
+        assert_eq!(tags, ["+backend", "+urgent"]);
+    }
+
+    #[test]
+    fn a_quoted_title_containing_plus_words_keeps_them() {
+        let cli = Cli::try_parse_from(["todo", "add", "learn c +c++", "+study"])
+            .expect("cli should parse add with tags");
+
+        let Some(TodoCommand::Add(AddArgs { title, .. })) = cli.command else {
+            panic!("expected add command");
+        };
+        let (title, tags) = split_inline_tags(&title);
+        assert_eq!(title, "learn c +c++");
+        assert_eq!(tags, ["+study"]);
+    }
+
+    #[test]
+    fn split_inline_tags_joins_unquoted_words_and_keeps_lone_plus() {
+        let words: Vec<String> = ["Fix", "a + b", "+api", "bug"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+
+        let (title, tags) = split_inline_tags(&words);
+
+        assert_eq!(title, "Fix a + b bug");
+        assert_eq!(tags, ["+api"]);
+    }
+
+    #[test]
+    fn parses_list_command_with_tag_filters() {
+        let cli = Cli::try_parse_from([
+            "todo",
+            "list",
+            "--tag",
+            "backend",
+            "--tag",
+            "urgent",
+            "--any-tag",
+            "api",
+        ])
+        .expect("cli should parse list with tags");
+
+        let Some(TodoCommand::List(ListArgs { tags, any_tags, .. })) = cli.command else {
+            panic!("expected list command");
+        };
+        assert_eq!(tags, ["backend", "urgent"]);
+        assert_eq!(any_tags, ["api"]);
+    }
+
+    #[test]
+    fn parses_edit_command_with_only_tag_changes() {
+        let id = Uuid::new_v4();
+        let cli = Cli::try_parse_from(["todo", "edit", &id.to_string(), "--untag", "wip"])
+            .expect("cli should parse edit with untag");
+
+        let Some(TodoCommand::Edit(EditArgs { remove_tags, .. })) = cli.command else {
+            panic!("expected edit command");
+        };
+        assert_eq!(remove_tags, ["wip"]);
+    }
+
+    #[test]
+    fn parses_note_command_and_stdin_note_flag() {
+        let id = Uuid::new_v4();
+        let note =
+            Cli::try_parse_from(["todo", "note", &id.to_string()]).expect("cli should parse note");
+        let edit = Cli::try_parse_from(["todo", "edit", &id.to_string(), "--note"])
+            .expect("cli should parse edit with stdin note");
+        let both = Cli::try_parse_from(["todo", "add", "Plan", "--note", "--description", "x"]);
+
+        assert_eq!(
+            note.command,
+            Some(TodoCommand::Note {
+                id: TaskRef::Id(id)
+            })
+        );
+        assert!(matches!(
+            edit.command,
+            Some(TodoCommand::Edit(EditArgs { note: true, .. }))
+        ));
+        assert!(both.is_err());
+    }
+
+    #[test]
+    fn parses_project_subcommands() {
+        let add = Cli::try_parse_from(["todo", "project", "add", "work"])
+            .expect("cli should parse project add");
+        let rename = Cli::try_parse_from(["todo", "project", "rename", "work", "job"])
+            .expect("cli should parse project rename");
+        let delete = Cli::try_parse_from(["todo", "project", "delete", "job", "--cascade"])
+            .expect("cli should parse project delete");
+
+        assert_eq!(
+            add.command,
+            Some(TodoCommand::Project {
+                command: ProjectCommand::Add {
+                    name: "work".to_string()
+                }
+            })
+        );
+        assert_eq!(
+            rename.command,
+            Some(TodoCommand::Project {
+                command: ProjectCommand::Rename {
+                    name: "work".to_string(),
+                    new_name: "job".to_string(),
+                }
+            })
+        );
+        assert_eq!(
+            delete.command,
+            Some(TodoCommand::Project {
+                command: ProjectCommand::Delete {
+                    name: "job".to_string(),
+                    move_to: None,
+                    cascade: true,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn project_delete_requires_an_explicit_policy() {
+        let missing = Cli::try_parse_from(["todo", "project", "delete", "work"]);
+        let both = Cli::try_parse_from([
+            "todo",
+            "project",
+            "delete",
+            "work",
+            "--cascade",
+            "--move-to",
+            "inbox",
+        ]);
+
+        assert!(missing.is_err());
+        assert!(both.is_err());
+    }
+
+    #[test]
+    fn rejects_edit_command_without_fields() {
+        let id = Uuid::new_v4();
+        let parsed = Cli::try_parse_from(["todo", "edit", &id.to_string()]);
+
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn parses_no_subcommand_as_none() {
+        let cli = Cli::try_parse_from(["todo"]).expect("cli should parse without subcommand");
+
+        assert_eq!(cli.command, None);
+    }
+
+    #[test]
+    fn parses_tui_command() {
+        let cli = Cli::try_parse_from(["todo", "tui"]).expect("cli should parse tui");
+
+        assert_eq!(cli.command, Some(TodoCommand::Tui));
+    }
+
     #[test]
     fn rejects_invalid_uuid_for_done_command() {
         let parsed = Cli::try_parse_from(["todo", "done", "not-a-uuid"]);
 
         assert!(parsed.is_err());
     }
+
+    #[test]
+    fn parses_dependency_commands() {
+        let id = Uuid::new_v4();
+        let blocker = Uuid::new_v4();
+        let cli =
+            Cli::try_parse_from(["todo", "dep", "add", &id.to_string(), &blocker.to_string()])
+                .expect("cli should parse dep add");
+
+        assert_eq!(
+            cli.command,
+            Some(TodoCommand::Dep {
+                command: DepCommand::Add {
+                    id: TaskRef::Id(id),
+                    blocker: TaskRef::Id(blocker)
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn parses_trash_empty_with_an_age() {
+        let cli = Cli::try_parse_from(["todo", "trash", "empty", "--older-than", "30d"])
+            .expect("cli should parse trash empty");
+
+        assert_eq!(
+            cli.command,
+            Some(TodoCommand::Trash {
+                command: TrashCommand::Empty {
+                    older_than: Some(30)
+                }
+            })
+        );
+        assert!(Cli::try_parse_from(["todo", "trash", "empty", "--older-than", "30"]).is_err());
+    }
+
+    #[test]
+    fn archive_requires_ids_or_an_age() {
+        let id = Uuid::new_v4();
+        let cli = Cli::try_parse_from(["todo", "archive", &id.to_string()])
+            .expect("cli should parse archive ids");
+
+        assert_eq!(
+            cli.command,
+            Some(TodoCommand::Archive {
+                ids: vec![TaskRef::Id(id)],
+                older_than: None
+            })
+        );
+        assert!(Cli::try_parse_from(["todo", "archive"]).is_err());
+    }
+
+    #[test]
+    fn parses_ready_list_flag() {
+        let cli =
+            Cli::try_parse_from(["todo", "list", "--ready"]).expect("cli should parse list ready");
+
+        let Some(TodoCommand::List(args)) = cli.command else {
+            panic!("expected list command");
+        };
+        assert!(args.ready);
+    }
+
+    #[test]
+    fn parses_repeat_rules_and_rejects_unsupported_ones() {
+        let cli =
+            Cli::try_parse_from(["todo", "add", "Standup", "--repeat", "FREQ=WEEKLY;BYDAY=MO"])
+                .expect("cli should parse add with repeat");
+        let Some(TodoCommand::Add(args)) = cli.command else {
+            panic!("expected add command");
+        };
+        assert_eq!(
+            args.repeat.map(|rule| rule.to_string()),
+            Some("FREQ=WEEKLY;BYDAY=MO".to_string())
+        );
+
+        assert!(Cli::try_parse_from(["todo", "add", "x", "--repeat", "yearly"]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "todo",
+                "edit",
+                &Uuid::new_v4().to_string(),
+                "--repeat",
+                "daily",
+                "--no-repeat"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn every_subcommand_has_its_own_help_text() {
+        let cli = Cli::command();
+
+        for command in cli.get_subcommands() {
+            let about = command
+                .get_about()
+                .map(|about| about.to_string())
+                .unwrap_or_default();
+            assert!(!about.is_empty(), "{} has no help text", command.get_name());
+            assert!(
+                !about.starts_with("The tasks a command acts on"),
+                "{} is described by its task selection",
+                command.get_name()
+            );
+            for subcommand in command.get_subcommands() {
+                assert!(
+                    subcommand.get_about().is_some(),
+                    "{} {} has no help text",
+                    command.get_name(),
+                    subcommand.get_name()
+                );
+            }
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use crate::tasks::application::errors::ApplicationError;
+use std::io;
 use thiserror::Error;
 
 pub type CliResult<T> = Result<T, CliError>;
@@ -8,4 +9,21 @@ pub enum CliError {
     Application(#[from] ApplicationError),
     #[error(transparent)]
     Serializer(#[from] serde_json::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("editor '{editor}' exited with an error; the note was not changed")]
+    EditorFailed { editor: String },
+    #[error("{failed} of {total} task(s) could not be changed; nothing was written")]
+    BulkFailed { failed: usize, total: usize },
+    #[error(
+        "the task was changed while its note was being edited; the note was not saved:\n{note}"
+    )]
+    NoteConflict { note: String },
+    #[error(
+        "the data file is corrupted: {error}\nrun `todo backup restore {backup}` to restore the newest valid backup"
+    )]
+    CorruptedStore { error: String, backup: String },
+    /// `todo tui` is started by the binary, not by the command handler.
+    #[error("'todo {command}' cannot be run here")]
+    Unsupported { command: &'static str },
 }
@@ -0,0 +1,1201 @@
+use crate::tasks::adapters::cli::cli_command::{
+    BackupCommand, DepCommand, OutputFormat, ProjectCommand, TaskSelection, TodoCommand,
+    TrashCommand, delete_policy_from_args,
+};
+use crate::tasks::adapters::cli::errors::{CliError, CliResult};
+use crate::tasks::adapters::cli::note_editor::{edit_note, editor_from_env, read_note_from_stdin};
+use crate::tasks::adapters::cli::printer::{
+    print_backup, print_backups, print_bulk_report, print_delete, print_journal_entry,
+    print_project, print_project_deleted, print_projects, print_purged, print_search_hits,
+    print_task, print_tasks, print_trash,
+};
+use crate::tasks::application::errors::ApplicationError;
+use crate::tasks::application::journal::run_journaled;
+use crate::tasks::application::subtasks::SubtaskDonePolicy;
+use crate::tasks::application::use_cases::add_dependency::{
+    AddDependencyCommand, AddDependencyService, AddDependencyUseCase,
+};
+use crate::tasks::application::use_cases::add_project::{
+    AddProjectCommand, AddProjectService, AddProjectUseCase,
+};
+use crate::tasks::application::use_cases::add_task::{AddTaskService, AddTaskUseCase};
+use crate::tasks::application::use_cases::archive_tasks::{
+    ArchiveTasksCommand, ArchiveTasksService, ArchiveTasksUseCase,
+};
+use crate::tasks::application::use_cases::bulk_update::{
+    BulkAction, BulkReport, BulkUpdateCommand, BulkUpdateService, BulkUpdateUseCase,
+};
+use crate::tasks::application::use_cases::change_task_status::{
+    ChangeTaskStatusCommand, ChangeTaskStatusService, ChangeTaskStatusUseCase,
+};
+use crate::tasks::application::use_cases::create_backup::{
+    CreateBackupService, CreateBackupUseCase,
+};
+use crate::tasks::application::use_cases::delete_project::{
+    DeleteProjectCommand, DeleteProjectService, DeleteProjectUseCase,
+};
+use crate::tasks::application::use_cases::delete_task::{
+    DeleteTaskCommand, DeleteTaskService, DeleteTaskUseCase,
+};
+use crate::tasks::application::use_cases::edit_task::{
+    EditFields, EditTaskCommand, EditTaskService, EditTaskUseCase,
+};
+use crate::tasks::application::use_cases::empty_trash::{
+    EmptyTrashCommand, EmptyTrashService, EmptyTrashUseCase,
+};
+use crate::tasks::application::use_cases::get_task::{
+    GetTaskCommand, GetTaskService, GetTaskUseCase,
+};
+use crate::tasks::application::use_cases::list_backups::{ListBackupsService, ListBackupsUseCase};
+use crate::tasks::application::use_cases::list_dependencies::{
+    ListDependenciesCommand, ListDependenciesService, ListDependenciesUseCase,
+};
+use crate::tasks::application::use_cases::list_projects::{
+    ListProjectsService, ListProjectsUseCase,
+};
+use crate::tasks::application::use_cases::list_tasks::{ListTasksService, ListTasksUseCase};
+use crate::tasks::application::use_cases::list_trash::{ListTrashService, ListTrashUseCase};
+use crate::tasks::application::use_cases::mark_task_done::{
+    CompletedTask, MarkTaskDoneCommand, MarkTaskDoneService, MarkTaskDoneUseCase,
+};
+use crate::tasks::application::use_cases::mark_task_todo::{
+    MarkTaskTodoCommand, MarkTaskTodoService, MarkTaskTodoUseCase,
+};
+use crate::tasks::application::use_cases::redo::{RedoService, RedoUseCase};
+use crate::tasks::application::use_cases::remove_dependency::{
+    RemoveDependencyCommand, RemoveDependencyService, RemoveDependencyUseCase,
+};
+use crate::tasks::application::use_cases::rename_project::{
+    RenameProjectCommand, RenameProjectService, RenameProjectUseCase,
+};
+use crate::tasks::application::use_cases::resolve_task_id::{
+    IdScope, ResolveTaskIdCommand, ResolveTaskIdService, ResolveTaskIdUseCase, ShortIds, TaskRef,
+    short_ids,
+};
+use crate::tasks::application::use_cases::restore_backup::{
+    RestoreBackupCommand, RestoreBackupService, RestoreBackupUseCase,
+};
+use crate::tasks::application::use_cases::restore_task::{
+    RestoreTaskCommand, RestoreTaskService, RestoreTaskUseCase,
+};
+use crate::tasks::application::use_cases::search_tasks::{
+    SearchHit, SearchTasksCommand, SearchTasksService, SearchTasksUseCase,
+};
+use crate::tasks::application::use_cases::unarchive_task::{
+    UnarchiveTaskCommand, UnarchiveTaskService, UnarchiveTaskUseCase,
+};
+use crate::tasks::application::use_cases::undo::{UndoService, UndoUseCase};
+use crate::tasks::domain::project::Project;
+use crate::tasks::domain::search::SearchQuery;
+use crate::tasks::domain::task::{Task, TaskStatus};
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::backup_repository::BackupRepository;
+use crate::tasks::ports::outputs::clock::Clock;
+use crate::tasks::ports::outputs::errors::RepoError;
+use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use uuid::Uuid;
+
+pub fn handle_command<
+    R: TaskRepository
+        + ProjectRepository
+        + ArchiveRepository
+        + JournalRepository
+        + BackupRepository
+        + Clone,
+    C: Clock + Clone,
+>(
+    repo: R,
+    clock: C,
+    command: TodoCommand,
+    output: OutputFormat,
+) -> CliResult<()> {
+    match command {
+        TodoCommand::Project { command } => handle_project_command(repo, clock, command, output),
+        TodoCommand::Undo => {
+            let mut service: UndoService<R> = UndoService::new(repo);
+            print_journal_entry("undo", &service.execute()?, output)
+        }
+        TodoCommand::Redo => {
+            let mut service: RedoService<R> = RedoService::new(repo);
+            print_journal_entry("redo", &service.execute()?, output)
+        }
+        // Archiving moves tasks between files, which the journal cannot
+        // replay, so it is not journaled.
+        TodoCommand::Archive { ids, older_than } => {
+            let ids: Vec<Uuid> = ids
+                .into_iter()
+                .map(|id| resolve(&repo, id, IdScope::Tasks))
+                .collect::<CliResult<Vec<Uuid>>>()?;
+            let mut command = ArchiveTasksCommand::new().with_ids(ids);
+            if let Some(days) = older_than {
+                command = command.with_older_than(days);
+            }
+            let mut service: ArchiveTasksService<R, C> =
+                ArchiveTasksService::new(repo.clone(), clock);
+            let archived: Vec<Task> = service.execute(command)?;
+            print_tasks(&archived, &short_ids(&repo)?, output)
+        }
+        TodoCommand::Unarchive { id } => {
+            let id: Uuid = resolve(&repo, id, IdScope::Archive)?;
+            let mut service: UnarchiveTaskService<R> = UnarchiveTaskService::new(repo.clone());
+            let task: Task = service.execute(UnarchiveTaskCommand::new(id))?;
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        // Restoring replaces the whole data file, which the journal cannot
+        // replay either.
+        TodoCommand::Backup { command } => handle_backup_command(repo, command, output),
+        TodoCommand::Tui => Err(CliError::Unsupported { command: "tui" }),
+        command => {
+            // Whatever the command changes becomes one undoable journal entry.
+            let label: &str = command.label();
+            let now = clock.now();
+            run_journaled(repo, label, now, |journaling| {
+                handle_task_command(journaling, clock, command, output)
+            })
+        }
+    }
+}
+
+fn handle_task_command<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + Clone,
+    C: Clock + Clone,
+>(
+    repo: R,
+    clock: C,
+    command: TodoCommand,
+    output: OutputFormat,
+) -> CliResult<()> {
+    match command {
+        TodoCommand::Add(mut args) => {
+            if args.note {
+                args.description = Some(read_note_from_stdin()?);
+            }
+            let parent: Option<Uuid> = resolve_optional(&repo, args.parent.clone())?;
+            let mut service: AddTaskService<R> = AddTaskService::new(repo.clone());
+            let task: Task = service.execute(args.into_command(parent))?;
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        TodoCommand::List(args) => {
+            let service: ListTasksService<R, C> = ListTasksService::new(repo.clone(), clock);
+            let tasks: Vec<Task> = service.execute(args.into())?;
+            print_tasks(&tasks, &short_ids(&repo)?, output)
+        }
+        TodoCommand::Search { terms } => {
+            let query: SearchQuery =
+                SearchQuery::parse(&terms.join(" ")).map_err(ApplicationError::from)?;
+            let service: SearchTasksService<R> = SearchTasksService::new(repo.clone());
+            let hits: Vec<SearchHit> = service.execute(SearchTasksCommand::new(query.clone()))?;
+            print_search_hits(&query, &hits, &short_ids(&repo)?, output)
+        }
+        TodoCommand::Done {
+            selection,
+            cascade,
+            dry_run,
+        } => {
+            let policy = if cascade {
+                SubtaskDonePolicy::Cascade
+            } else {
+                SubtaskDonePolicy::Block
+            };
+            let Some(id) = selection.single().filter(|_| !dry_run).cloned() else {
+                let action = BulkAction::Done(policy);
+                return handle_bulk(repo, clock, action, selection, dry_run, output);
+            };
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let mut service: MarkTaskDoneService<R, C> =
+                MarkTaskDoneService::new(repo.clone(), clock);
+            let completed: CompletedTask =
+                service.execute(MarkTaskDoneCommand::new(id).with_subtask_policy(policy))?;
+            let ids: ShortIds = short_ids(&repo)?;
+            match completed.next_occurrence {
+                Some(next) => print_tasks(&[completed.task, next], &ids, output),
+                None => print_task(&completed.task, &ids, output),
+            }
+        }
+        TodoCommand::Todo { selection, dry_run } => {
+            let Some(id) = selection.single().filter(|_| !dry_run).cloned() else {
+                return handle_bulk(repo, clock, BulkAction::Todo, selection, dry_run, output);
+            };
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let mut service: MarkTaskTodoService<R> = MarkTaskTodoService::new(repo.clone());
+            let task: Task = service.execute(MarkTaskTodoCommand::new(id))?;
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        TodoCommand::Start { id } => {
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let mut service: ChangeTaskStatusService<R> =
+                ChangeTaskStatusService::new(repo.clone());
+            let task: Task =
+                service.execute(ChangeTaskStatusCommand::new(id, TaskStatus::InProgress))?;
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        TodoCommand::Block { id, reason } => {
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let mut command = ChangeTaskStatusCommand::new(id, TaskStatus::Blocked);
+            if let Some(reason) = reason {
+                command = command.with_reason(reason);
+            }
+            let mut service: ChangeTaskStatusService<R> =
+                ChangeTaskStatusService::new(repo.clone());
+            let task: Task = service.execute(command)?;
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        TodoCommand::Cancel { id } => {
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let mut service: ChangeTaskStatusService<R> =
+                ChangeTaskStatusService::new(repo.clone());
+            let task: Task =
+                service.execute(ChangeTaskStatusCommand::new(id, TaskStatus::Cancelled))?;
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        TodoCommand::Delete { selection, dry_run } => {
+            let Some(id) = selection.single().filter(|_| !dry_run).cloned() else {
+                return handle_bulk(repo, clock, BulkAction::Delete, selection, dry_run, output);
+            };
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let mut service: DeleteTaskService<R, C> = DeleteTaskService::new(repo, clock);
+            let deleted: bool = service.execute(DeleteTaskCommand::new(id))?;
+            print_delete(id.to_string(), deleted, output)
+        }
+        TodoCommand::Edit(mut args) => {
+            if args.note {
+                args.description = Some(read_note_from_stdin()?);
+            }
+            let parent: Option<Uuid> = resolve_optional(&repo, args.parent.clone())?;
+            let Some(id) = args.selection.single().filter(|_| !args.dry_run).cloned() else {
+                let (selection, dry_run) = (args.selection.clone(), args.dry_run);
+                // The bulk use case applies the fields to each task in turn.
+                let action = BulkAction::Edit(Box::new(args.into_fields(parent)));
+                return handle_bulk(repo, clock, action, selection, dry_run, output);
+            };
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let mut service: EditTaskService<R> = EditTaskService::new(repo.clone());
+            let command = EditTaskCommand::new(id, args.into_fields(parent));
+            let task: Task = service.execute(command)?;
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        TodoCommand::Note { id } => {
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let task: Task = GetTaskService::new(repo.clone()).execute(GetTaskCommand::new(id))?;
+            let current: &str = task.description().unwrap_or_default();
+            let note: String = edit_note(&editor_from_env(), current)?;
+            if note.trim() == current {
+                return print_task(&task, &short_ids(&repo)?, output);
+            }
+            // The editor may stay open for a while: refuse to overwrite a
+            // change made meanwhile, but hand the note back so it is not lost.
+            let fields: EditFields = EditFields::new().with_description(Some(note.clone()));
+            let command: EditTaskCommand =
+                EditTaskCommand::new(id, fields).with_expected_version(task.version());
+            let mut service: EditTaskService<R> = EditTaskService::new(repo.clone());
+            let task: Task = match service.execute(command) {
+                Err(ApplicationError::Repository(RepoError::Conflict { .. })) => {
+                    return Err(CliError::NoteConflict { note });
+                }
+                result => result?,
+            };
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        TodoCommand::Dep { command } => handle_dep_command(repo, command, output),
+        TodoCommand::Trash { command } => handle_trash_command(repo, clock, command, output),
+        // Run without journaling by `handle_command`.
+        command @ (TodoCommand::Project { .. }
+        | TodoCommand::Archive { .. }
+        | TodoCommand::Unarchive { .. }
+        | TodoCommand::Backup { .. }
+        | TodoCommand::Undo
+        | TodoCommand::Redo
+        | TodoCommand::Tui) => Err(CliError::Unsupported {
+            command: command.label(),
+        }),
+    }
+}
+
+/// Several tasks, a filter or a dry run: everything is checked first and
+/// written together, or not at all when any task fails.
+fn handle_bulk<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + Clone,
+    C: Clock + Clone,
+>(
+    repo: R,
+    clock: C,
+    action: BulkAction,
+    selection: TaskSelection,
+    dry_run: bool,
+    output: OutputFormat,
+) -> CliResult<()> {
+    let ids: Vec<Uuid> = selection
+        .ids
+        .into_iter()
+        .map(|id| resolve(&repo, id, IdScope::Tasks))
+        .collect::<CliResult<Vec<Uuid>>>()?;
+    let mut command = BulkUpdateCommand::new(action).with_ids(ids);
+    if let Some(filter) = selection.filter {
+        command = command.with_where(filter);
+    }
+    if dry_run {
+        command = command.with_dry_run();
+    }
+    let mut service: BulkUpdateService<R, C> = BulkUpdateService::new(repo.clone(), clock);
+    let report: BulkReport = service.execute(command)?;
+    print_bulk_report(&report, &short_ids(&repo)?, output)?;
+    match report.failed() {
+        0 => Ok(()),
+        failed => Err(CliError::BulkFailed {
+            failed,
+            total: report.outcomes.len(),
+        }),
+    }
+}
+
+fn handle_project_command<R: TaskRepository + ProjectRepository, C: Clock>(
+    repo: R,
+    clock: C,
+    command: ProjectCommand,
+    output: OutputFormat,
+) -> CliResult<()> {
+    match command {
+        ProjectCommand::Add { name } => {
+            let mut service: AddProjectService<R> = AddProjectService::new(repo);
+            let project: Project = service.execute(AddProjectCommand::new(name))?;
+            print_project(&project, output)
+        }
+        ProjectCommand::List => {
+            let service: ListProjectsService<R> = ListProjectsService::new(repo);
+            print_projects(&service.execute()?, output)
+        }
+        ProjectCommand::Rename { name, new_name } => {
+            let mut service: RenameProjectService<R> = RenameProjectService::new(repo);
+            let project: Project = service.execute(RenameProjectCommand::new(name, new_name))?;
+            print_project(&project, output)
+        }
+        ProjectCommand::Delete {
+            name,
+            move_to,
+            cascade,
+        } => {
+            let mut service: DeleteProjectService<R, C> = DeleteProjectService::new(repo, clock);
+            let policy = delete_policy_from_args(move_to, cascade);
+            let deleted = service.execute(DeleteProjectCommand::new(name, policy))?;
+            print_project_deleted(&deleted, output)
+        }
+    }
+}
+
+fn handle_trash_command<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + Clone,
+    C: Clock,
+>(
+    repo: R,
+    clock: C,
+    command: TrashCommand,
+    output: OutputFormat,
+) -> CliResult<()> {
+    match command {
+        TrashCommand::List => {
+            let service: ListTrashService<R> = ListTrashService::new(repo.clone());
+            print_trash(&service.execute()?, &short_ids(&repo)?, output)
+        }
+        TrashCommand::Restore { id } => {
+            let id: Uuid = resolve(&repo, id, IdScope::Trash)?;
+            let mut service: RestoreTaskService<R> = RestoreTaskService::new(repo.clone());
+            let task: Task = service.execute(RestoreTaskCommand::new(id))?;
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        TrashCommand::Empty { older_than } => {
+            let mut command = EmptyTrashCommand::new();
+            if let Some(days) = older_than {
+                command = command.with_older_than(days);
+            }
+            let mut service: EmptyTrashService<R, C> = EmptyTrashService::new(repo, clock);
+            let purged: Vec<Task> = service.execute(command)?;
+            print_purged(&purged, output)
+        }
+    }
+}
+
+fn handle_backup_command<R: BackupRepository>(
+    repo: R,
+    command: BackupCommand,
+    output: OutputFormat,
+) -> CliResult<()> {
+    match command {
+        BackupCommand::List => {
+            let service: ListBackupsService<R> = ListBackupsService::new(repo);
+            print_backups(&service.execute()?, output)
+        }
+        BackupCommand::Create => {
+            let mut service: CreateBackupService<R> = CreateBackupService::new(repo);
+            print_backup("created", &service.execute()?, output)
+        }
+        BackupCommand::Restore { name } => {
+            let mut service: RestoreBackupService<R> = RestoreBackupService::new(repo);
+            let backup = service.execute(RestoreBackupCommand::new(name))?;
+            print_backup("restored", &backup, output)
+        }
+    }
+}
+
+fn handle_dep_command<R: TaskRepository + ArchiveRepository + Clone>(
+    repo: R,
+    command: DepCommand,
+    output: OutputFormat,
+) -> CliResult<()> {
+    match command {
+        DepCommand::Add { id, blocker } => {
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let blocker: Uuid = resolve(&repo, blocker, IdScope::Tasks)?;
+            let mut service: AddDependencyService<R> = AddDependencyService::new(repo.clone());
+            let task: Task = service.execute(AddDependencyCommand::new(id, blocker))?;
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        DepCommand::Remove { id, blocker } => {
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let blocker: Uuid = resolve(&repo, blocker, IdScope::Tasks)?;
+            let mut service: RemoveDependencyService<R> =
+                RemoveDependencyService::new(repo.clone());
+            let task: Task = service.execute(RemoveDependencyCommand::new(id, blocker))?;
+            print_task(&task, &short_ids(&repo)?, output)
+        }
+        DepCommand::List { id } => {
+            let id: Uuid = resolve(&repo, id, IdScope::Tasks)?;
+            let service: ListDependenciesService<R> = ListDependenciesService::new(repo.clone());
+            let blockers: Vec<Task> = service.execute(ListDependenciesCommand::new(id))?;
+            print_tasks(&blockers, &short_ids(&repo)?, output)
+        }
+    }
+}
+
+/// Turns a task reference from the command line (full id, id prefix or list
+/// number) into the task id.
+fn resolve<R: TaskRepository + ArchiveRepository + Clone>(
+    repo: &R,
+    reference: TaskRef,
+    scope: IdScope,
+) -> CliResult<Uuid> {
+    let service: ResolveTaskIdService<R> = ResolveTaskIdService::new(repo.clone());
+    Ok(service.execute(ResolveTaskIdCommand::new(reference).with_scope(scope))?)
+}
+
+fn resolve_optional<R: TaskRepository + ArchiveRepository + Clone>(
+    repo: &R,
+    reference: Option<TaskRef>,
+) -> CliResult<Option<Uuid>> {
+    reference
+        .map(|reference| resolve(repo, reference, IdScope::Tasks))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::handle_command;
+    use crate::tasks::adapters::cli::cli_command::{
+        AddArgs, DepCommand, EditArgs, ListArgs, OutputFormat, PriorityArg, ProjectCommand,
+        SortArg, StatusArg, TaskSelection, TodoCommand,
+    };
+    use crate::tasks::adapters::cli::errors::CliError;
+    use crate::tasks::adapters::clock::system_clock::SystemClock;
+    use crate::tasks::adapters::persistence::json_file_task_repository::JsonFileTaskRepository;
+    use crate::tasks::application::use_cases::list_tasks::{TaskSort, sort_tasks};
+    use crate::tasks::application::use_cases::resolve_task_id::TaskRef;
+    use crate::tasks::domain::priority::Priority;
+    use crate::tasks::domain::task::{Task, TaskStatus};
+    use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use tempfile::tempdir;
+    use uuid::Uuid;
+
+    #[test]
+    fn add_command_persists_task() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Buy milk".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("add should succeed");
+
+        let all = repo.list(TaskQuery::All).expect("list should succeed");
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].title(), "Buy milk");
+    }
+
+    #[test]
+    fn done_command_marks_existing_task() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Pay rent".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("add should succeed");
+        let id = repo.list(TaskQuery::All).expect("list should succeed")[0].task_id();
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Done {
+                selection: TaskSelection::id(TaskRef::Id(id)),
+                dry_run: false,
+                cascade: false,
+            },
+            OutputFormat::Table,
+        )
+        .expect("done should succeed");
+
+        let found = repo.find_by_id(id).expect("find should succeed");
+        let found = found.expect("task should exist");
+        assert_eq!(found.status(), TaskStatus::Done);
+    }
+
+    #[test]
+    fn commands_accept_list_numbers_and_id_prefixes() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        repo.save(Task::new("first".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        repo.save(Task::new("second".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let mut listed: Vec<Task> = repo.list(TaskQuery::All).expect("list should succeed");
+        sort_tasks(&mut listed, TaskSort::default());
+        let (first, second) = (listed[0].task_id(), listed[1].task_id());
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Start {
+                id: TaskRef::Prefix(first.to_string()[..13].to_string()),
+            },
+            OutputFormat::Json,
+        )
+        .expect("start by prefix should succeed");
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Cancel {
+                id: TaskRef::Index(2),
+            },
+            OutputFormat::Json,
+        )
+        .expect("cancel by number should succeed");
+
+        let status = |id| {
+            repo.find_by_id(id)
+                .expect("find should succeed")
+                .expect("task should exist")
+                .status()
+        };
+        assert_eq!(status(first), TaskStatus::InProgress);
+        assert_eq!(status(second), TaskStatus::Cancelled);
+    }
+
+    #[test]
+    fn done_command_fails_for_unknown_task() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+
+        let result = handle_command(
+            repo,
+            SystemClock,
+            TodoCommand::Done {
+                selection: TaskSelection::id(TaskRef::Id(Uuid::new_v4())),
+                dry_run: false,
+                cascade: false,
+            },
+            OutputFormat::Table,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn edit_command_renames_existing_task() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Buy milk".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("add should succeed");
+        let id = repo.list(TaskQuery::All).expect("list should succeed")[0].task_id();
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Edit(EditArgs {
+                selection: TaskSelection::id(TaskRef::Id(id)),
+                dry_run: false,
+                title: Some("Buy oat milk".to_string()),
+                priority: None,
+                due: None,
+                clear_due: false,
+                repeat: None,
+                no_repeat: false,
+                add_tags: vec![],
+                remove_tags: vec![],
+                description: None,
+                clear_description: false,
+                note: false,
+                parent: None,
+                no_parent: false,
+                project: None,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("edit should succeed");
+
+        let found = repo.find_by_id(id).expect("find should succeed");
+        let found = found.expect("task should exist");
+        assert_eq!(found.title(), "Buy oat milk");
+    }
+
+    #[test]
+    fn add_command_sets_priority() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Fix prod".to_string()],
+                priority: Some(PriorityArg::High),
+                due: None,
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("add should succeed");
+
+        let high = repo
+            .list(TaskQuery::ByPriority(Priority::High))
+            .expect("list should succeed");
+        assert_eq!(high.len(), 1);
+    }
+
+    #[test]
+    fn add_command_extracts_inline_tags() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Fix bug".to_string(), "+Backend".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("add should succeed");
+
+        let all = repo.list(TaskQuery::All).expect("list should succeed");
+        assert_eq!(all[0].title(), "Fix bug");
+        let tags: Vec<&str> = all[0].tags().iter().map(|tag| tag.as_str()).collect();
+        assert_eq!(tags, ["backend"]);
+    }
+
+    #[test]
+    fn add_command_rejects_invalid_inline_tag() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+
+        let result = handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Fix bug".to_string(), "+back/end".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }),
+            OutputFormat::Json,
+        );
+
+        assert!(result.is_err());
+        assert!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn edit_command_rejects_blank_title() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Buy milk".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("add should succeed");
+        let id = repo.list(TaskQuery::All).expect("list should succeed")[0].task_id();
+
+        let result = handle_command(
+            repo,
+            SystemClock,
+            TodoCommand::Edit(EditArgs {
+                selection: TaskSelection::id(TaskRef::Id(id)),
+                dry_run: false,
+                title: Some("   ".to_string()),
+                priority: None,
+                due: None,
+                clear_due: false,
+                repeat: None,
+                no_repeat: false,
+                add_tags: vec![],
+                remove_tags: vec![],
+                description: None,
+                clear_description: false,
+                note: false,
+                parent: None,
+                no_parent: false,
+                project: None,
+            }),
+            OutputFormat::Json,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn list_command_succeeds_on_empty_store() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+
+        let result = handle_command(
+            repo,
+            SystemClock,
+            TodoCommand::List(ListArgs {
+                status: StatusArg::Todo,
+                priority: None,
+                due: None,
+                tags: vec![],
+                any_tags: vec![],
+                project: None,
+                ready: false,
+                archived: false,
+                filter: None,
+                sort: SortArg::Created,
+                reverse: false,
+                limit: None,
+                offset: 0,
+            }),
+            OutputFormat::Table,
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn add_command_files_task_into_existing_project() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Project {
+                command: ProjectCommand::Add {
+                    name: "Work".to_string(),
+                },
+            },
+            OutputFormat::Json,
+        )
+        .expect("project add should succeed");
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Write report".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                project: Some("work".to_string()),
+                description: None,
+                note: false,
+                parent: None,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("add should succeed");
+
+        let project = repo
+            .find_project_by_name("work")
+            .expect("find should succeed")
+            .expect("project should exist");
+        let in_project = repo
+            .list(TaskQuery::ByProject(Some(project.project_id())))
+            .expect("list should succeed");
+        assert_eq!(in_project.len(), 1);
+    }
+
+    #[test]
+    fn add_command_rejects_unknown_project() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+
+        let result = handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Write report".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                project: Some("nowhere".to_string()),
+                description: None,
+                note: false,
+                parent: None,
+            }),
+            OutputFormat::Json,
+        );
+
+        assert!(result.is_err());
+        assert!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn add_command_rejects_overlong_title_but_accepts_long_description() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let long_text = "word ".repeat(100);
+
+        let rejected = handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec![long_text.clone()],
+                priority: None,
+                due: None,
+                repeat: None,
+                description: None,
+                note: false,
+                parent: None,
+                project: None,
+            }),
+            OutputFormat::Json,
+        );
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Read".to_string(), "notes".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                description: Some(long_text.clone()),
+                note: false,
+                parent: None,
+                project: None,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("add should succeed");
+
+        assert!(rejected.is_err());
+        let all = repo.list(TaskQuery::All).expect("list should succeed");
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].description(), Some(long_text.trim()));
+    }
+
+    #[test]
+    fn block_command_rejects_completing_a_blocked_task() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Add(AddArgs {
+                title: vec!["Deploy".to_string()],
+                priority: None,
+                due: None,
+                repeat: None,
+                project: None,
+                description: None,
+                note: false,
+                parent: None,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("add should succeed");
+        let id = repo.list(TaskQuery::All).expect("list should succeed")[0].task_id();
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Block {
+                id: TaskRef::Id(id),
+                reason: Some("waiting on QA".to_string()),
+            },
+            OutputFormat::Json,
+        )
+        .expect("block should succeed");
+        let done = handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Done {
+                selection: TaskSelection::id(TaskRef::Id(id)),
+                dry_run: false,
+                cascade: false,
+            },
+            OutputFormat::Json,
+        );
+
+        assert!(done.is_err());
+        let found = repo
+            .find_by_id(id)
+            .expect("find should succeed")
+            .expect("task should exist");
+        assert_eq!(found.status(), TaskStatus::Blocked);
+        assert_eq!(found.blocked_reason(), Some("waiting on QA"));
+    }
+
+    #[test]
+    fn dependency_blocks_done_until_removed() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let blocker = Task::new("Design".to_string()).expect("task should be created");
+        let task = Task::new("Build".to_string()).expect("task should be created");
+        repo.save(blocker.clone()).expect("save should succeed");
+        repo.save(task.clone()).expect("save should succeed");
+        let (id, blocker) = (task.task_id(), blocker.task_id());
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Dep {
+                command: DepCommand::Add {
+                    id: TaskRef::Id(id),
+                    blocker: TaskRef::Id(blocker),
+                },
+            },
+            OutputFormat::Json,
+        )
+        .expect("dep add should succeed");
+        let blocked = handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Done {
+                selection: TaskSelection::id(TaskRef::Id(id)),
+                dry_run: false,
+                cascade: false,
+            },
+            OutputFormat::Json,
+        );
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Dep {
+                command: DepCommand::Remove {
+                    id: TaskRef::Id(id),
+                    blocker: TaskRef::Id(blocker),
+                },
+            },
+            OutputFormat::Json,
+        )
+        .expect("dep remove should succeed");
+        let done = handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Done {
+                selection: TaskSelection::id(TaskRef::Id(id)),
+                dry_run: false,
+                cascade: false,
+            },
+            OutputFormat::Json,
+        );
+
+        assert!(blocked.is_err());
+        assert!(done.is_ok());
+    }
+
+    #[test]
+    fn bulk_done_writes_nothing_when_a_task_fails_and_undoes_as_one_change() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let open = Task::new("Write docs".to_string()).expect("task should be created");
+        let done = Task::new("Ship".to_string())
+            .and_then(Task::mark_done)
+            .expect("task should be created");
+        repo.save(open.clone()).expect("save should succeed");
+        repo.save(done.clone()).expect("save should succeed");
+        let bulk_done = |ids: Vec<Uuid>| TodoCommand::Done {
+            selection: TaskSelection {
+                ids: ids.into_iter().map(TaskRef::Id).collect(),
+                filter: None,
+            },
+            cascade: false,
+            dry_run: false,
+        };
+        let status = |repo: &JsonFileTaskRepository, id: Uuid| {
+            repo.find_by_id(id)
+                .expect("find should succeed")
+                .expect("task should exist")
+                .status()
+        };
+
+        let failed = handle_command(
+            repo.clone(),
+            SystemClock,
+            bulk_done(vec![open.task_id(), done.task_id()]),
+            OutputFormat::Json,
+        );
+        assert!(failed.is_err());
+        assert_eq!(status(&repo, open.task_id()), TaskStatus::Todo);
+
+        let second = Task::new("Review".to_string()).expect("task should be created");
+        repo.save(second.clone()).expect("save should succeed");
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            bulk_done(vec![open.task_id(), second.task_id()]),
+            OutputFormat::Json,
+        )
+        .expect("bulk done should succeed");
+        assert_eq!(status(&repo, second.task_id()), TaskStatus::Done);
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Undo,
+            OutputFormat::Json,
+        )
+        .expect("undo should succeed");
+        assert_eq!(status(&repo, open.task_id()), TaskStatus::Todo);
+        assert_eq!(status(&repo, second.task_id()), TaskStatus::Todo);
+    }
+
+    #[test]
+    fn undo_restores_a_deleted_task_and_list_is_not_journaled() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let task = Task::new("Pay rent".to_string()).expect("task should be created");
+        repo.save(task.clone()).expect("save should succeed");
+
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Delete {
+                selection: TaskSelection::id(TaskRef::Id(task.task_id())),
+                dry_run: false,
+            },
+            OutputFormat::Json,
+        )
+        .expect("delete should succeed");
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::List(ListArgs {
+                status: StatusArg::All,
+                priority: None,
+                due: None,
+                tags: vec![],
+                any_tags: vec![],
+                project: None,
+                ready: false,
+                archived: false,
+                filter: None,
+                sort: SortArg::Created,
+                reverse: false,
+                limit: None,
+                offset: 0,
+            }),
+            OutputFormat::Json,
+        )
+        .expect("list should succeed");
+        handle_command(
+            repo.clone(),
+            SystemClock,
+            TodoCommand::Undo,
+            OutputFormat::Json,
+        )
+        .expect("undo should succeed");
+
+        assert!(
+            repo.find_by_id(task.task_id())
+                .expect("find should succeed")
+                .is_some()
+        );
+        let again = handle_command(repo, SystemClock, TodoCommand::Undo, OutputFormat::Json);
+        assert!(again.is_err());
+    }
+
+    #[test]
+    fn the_tui_command_is_an_error_rather_than_a_panic() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+
+        let result = handle_command(repo, SystemClock, TodoCommand::Tui, OutputFormat::Json);
+
+        assert!(matches!(
+            result,
+            Err(CliError::Unsupported { command: "tui" })
+        ));
+    }
+}
@@ -1,3 +1,6 @@
 pub mod cli_command;
 pub mod errors;
+pub mod handler;
+pub mod note_editor;
 pub mod printer;
+pub mod recovery;
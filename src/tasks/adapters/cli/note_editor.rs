@@ -0,0 +1,79 @@
+use crate::tasks::adapters::cli::errors::{CliError, CliResult};
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::process::Command;
+
+/// Used when neither `$VISUAL` nor `$EDITOR` is set.
+const DEFAULT_EDITOR: &str = "vi";
+
+pub fn editor_from_env() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+/// Opens `editor` (a program plus optional arguments, e.g. `code --wait`) on a
+/// temporary Markdown file holding `initial` and returns the saved text.
+pub fn edit_note(editor: &str, initial: &str) -> CliResult<String> {
+    let file = tempfile::Builder::new()
+        .prefix("todo-note-")
+        .suffix(".md")
+        .tempfile()?;
+    fs::write(file.path(), initial)?;
+
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or(DEFAULT_EDITOR);
+    let status = Command::new(program)
+        .args(words)
+        .arg(file.path())
+        .status()?;
+    if !status.success() {
+        return Err(CliError::EditorFailed {
+            editor: editor.to_string(),
+        });
+    }
+    Ok(fs::read_to_string(file.path())?)
+}
+
+pub fn read_note_from_stdin() -> CliResult<String> {
+    let mut note = String::new();
+    io::stdin().read_to_string(&mut note)?;
+    Ok(note)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::edit_note;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::tempdir;
+
+    fn fake_editor(dir: &std::path::Path, script: &str) -> String {
+        let path = dir.join("editor.sh");
+        fs::write(&path, format!("#!/bin/sh\n{script}\n")).expect("script should be written");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .expect("script should be executable");
+        path.display().to_string()
+    }
+
+    #[test]
+    fn edit_note_returns_text_saved_by_the_editor() {
+        let temp = tempdir().expect("temp dir should be created");
+        let editor = fake_editor(temp.path(), r#"printf '%s\n- added' "$(cat "$1")" > "$1""#);
+
+        let note = edit_note(&editor, "# Plan").expect("editor should succeed");
+
+        assert_eq!(note, "# Plan\n- added");
+    }
+
+    #[test]
+    fn edit_note_fails_when_the_editor_exits_with_error() {
+        let temp = tempdir().expect("temp dir should be created");
+        let editor = fake_editor(temp.path(), "exit 1");
+
+        assert!(edit_note(&editor, "").is_err());
+    }
+}
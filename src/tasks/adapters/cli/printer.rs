@@ -1,27 +1,44 @@
 use crate::tasks::adapters::cli::cli_command::OutputFormat;
 use crate::tasks::adapters::cli::errors::CliResult;
+use crate::tasks::application::use_cases::bulk_update::BulkReport;
+use crate::tasks::application::use_cases::delete_project::DeletedProject;
+use crate::tasks::application::use_cases::list_projects::ProjectSummary;
+use crate::tasks::application::use_cases::resolve_task_id::ShortIds;
+use crate::tasks::application::use_cases::search_tasks::SearchHit;
+use crate::tasks::domain::journal::JournalEntry;
+use crate::tasks::domain::priority::Priority;
+use crate::tasks::domain::project::{INBOX, Project};
+use crate::tasks::domain::search::SearchQuery;
 use crate::tasks::domain::task::{Task, TaskStatus};
+use crate::tasks::ports::outputs::backup_repository::Backup;
 use serde::Serialize;
+use std::io::IsTerminal;
+use std::ops::Range;
 
-pub fn print_task(task: &Task, output: OutputFormat) -> CliResult<()> {
+const HIGHLIGHT_START: &str = "\x1b[1;33m";
+const HIGHLIGHT_END: &str = "\x1b[0m";
+
+/// Tables show each task's number in the list and its shortest unique id
+/// prefix, both accepted wherever a task id is; JSON keeps the full id.
+pub fn print_task(task: &Task, ids: &ShortIds, output: OutputFormat) -> CliResult<()> {
     match output {
         OutputFormat::Json => {
             println!("{}", serde_json::to_string(task)?);
         }
         OutputFormat::Table => {
-            print_tasks_table(std::slice::from_ref(task));
+            print_tasks_table(std::slice::from_ref(task), ids);
         }
     }
     Ok(())
 }
 
-pub fn print_tasks(tasks: &[Task], output: OutputFormat) -> CliResult<()> {
+pub fn print_tasks(tasks: &[Task], ids: &ShortIds, output: OutputFormat) -> CliResult<()> {
     match output {
         OutputFormat::Json => {
             println!("{}", serde_json::to_string(tasks)?);
         }
         OutputFormat::Table => {
-            print_tasks_table(tasks);
+            print_tasks_table(tasks, ids);
         }
     }
     Ok(())
@@ -29,7 +46,7 @@ pub fn print_tasks(tasks: &[Task], output: OutputFormat) -> CliResult<()> {
 
 pub fn print_delete(id: String, deleted: bool, output: OutputFormat) -> CliResult<()> {
     let message = if deleted {
-        format!("deleted {id}")
+        format!("moved {id} to the trash")
     } else {
         format!("task {id} not found")
     };
@@ -54,60 +71,582 @@ pub fn print_delete(id: String, deleted: bool, output: OutputFormat) -> CliResul
     Ok(())
 }
 
-fn print_tasks_table(tasks: &[Task]) {
-    let id_header = "ID";
-    let status_header = "STATUS";
-    let title_header = "TITLE";
+/// One row per selected task saying whether the action succeeded for it,
+/// then what was written.
+pub fn print_bulk_report(
+    report: &BulkReport,
+    ids: &ShortIds,
+    output: OutputFormat,
+) -> CliResult<()> {
+    let changed = report.outcomes.len();
+    let message = if report.applied {
+        format!("changed {changed} task(s)")
+    } else if report.failed() > 0 {
+        format!(
+            "{} of {changed} task(s) failed; nothing written",
+            report.failed()
+        )
+    } else {
+        format!("dry run: {changed} task(s) would change; nothing written")
+    };
+    match output {
+        OutputFormat::Json => {
+            let payload = BulkOutput {
+                dry_run: report.dry_run,
+                applied: report.applied,
+                results: report
+                    .outcomes
+                    .iter()
+                    .map(|outcome| BulkResultOutput {
+                        id: outcome.task.task_id().to_string(),
+                        ok: outcome.result.is_ok(),
+                        error: outcome.result.as_ref().err().map(ToString::to_string),
+                        task: outcome.result.as_ref().unwrap_or(&outcome.task),
+                    })
+                    .collect(),
+                message,
+            };
+            println!("{}", serde_json::to_string(&payload)?);
+        }
+        OutputFormat::Table => {
+            let headers = ["#", "ID", "RESULT", "STATUS", "TITLE"];
+            let rows: Vec<Vec<String>> = report
+                .outcomes
+                .iter()
+                .map(|outcome| {
+                    let task = outcome.result.as_ref().unwrap_or(&outcome.task);
+                    vec![
+                        index_label(&outcome.task, ids),
+                        ids.prefix(task.task_id()),
+                        match &outcome.result {
+                            Ok(_) => "OK".to_string(),
+                            Err(error) => format!("FAILED: {error}"),
+                        },
+                        status_label(task),
+                        task.title().to_string(),
+                    ]
+                })
+                .collect();
+            print!("{}", format_table(&headers, &rows));
+            // Failures end in an error that says the same.
+            if report.failed() == 0 {
+                println!("{message}");
+            }
+        }
+    }
+    Ok(())
+}
 
-    let id_width = tasks
-        .iter()
-        .map(|task| task.task_id().to_string().len())
-        .max()
-        .unwrap_or(0)
-        .max(id_header.len());
-    let status_width = tasks
-        .iter()
-        .map(|task| status_label(task).len())
-        .max()
-        .unwrap_or(0)
-        .max(status_header.len());
-    let title_width = tasks
-        .iter()
-        .map(|task| task.title().len())
-        .max()
-        .unwrap_or(0)
-        .max(title_header.len());
-
-    println!(
-        "| {:<id_width$} | {:<status_width$} | {:<title_width$} |",
-        id_header, status_header, title_header
+pub fn print_project(project: &Project, output: OutputFormat) -> CliResult<()> {
+    match output {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string(project)?);
+        }
+        OutputFormat::Table => {
+            let rows = vec![vec![
+                project.project_id().to_string(),
+                project.name().to_string(),
+            ]];
+            print!("{}", format_table(&["ID", "NAME"], &rows));
+        }
+    }
+    Ok(())
+}
+
+pub fn print_projects(summaries: &[ProjectSummary], output: OutputFormat) -> CliResult<()> {
+    let outputs: Vec<ProjectOutput> = summaries.iter().map(ProjectOutput::from).collect();
+    match output {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string(&outputs)?);
+        }
+        OutputFormat::Table => {
+            let rows: Vec<Vec<String>> = outputs
+                .iter()
+                .map(|project| {
+                    vec![
+                        project.name.clone(),
+                        project.open_tasks.to_string(),
+                        project.total_tasks.to_string(),
+                    ]
+                })
+                .collect();
+            print!("{}", format_table(&["NAME", "OPEN", "TOTAL"], &rows));
+        }
+    }
+    Ok(())
+}
+
+pub fn print_project_deleted(deleted: &DeletedProject, output: OutputFormat) -> CliResult<()> {
+    let name = deleted.project.name().to_string();
+    let message = format!(
+        "deleted project {name} ({} tasks moved, {} tasks moved to the trash)",
+        deleted.moved_tasks, deleted.deleted_tasks
     );
-    println!(
-        "|-{:-<id_width$}-|-{:-<status_width$}-|-{:-<title_width$}-|",
-        "", "", ""
+    match output {
+        OutputFormat::Json => {
+            let payload = DeleteProjectOutput {
+                name,
+                moved_tasks: deleted.moved_tasks,
+                deleted_tasks: deleted.deleted_tasks,
+                message,
+            };
+            println!("{}", serde_json::to_string(&payload)?);
+        }
+        OutputFormat::Table => {
+            let rows = vec![vec!["DELETED".to_string(), message]];
+            print!("{}", format_table(&["RESULT", "MESSAGE"], &rows));
+        }
+    }
+    Ok(())
+}
+
+/// Ranked search results; in table mode the matched parts of each title are
+/// highlighted when printing to a terminal.
+pub fn print_search_hits(
+    query: &SearchQuery,
+    hits: &[SearchHit],
+    ids: &ShortIds,
+    output: OutputFormat,
+) -> CliResult<()> {
+    match output {
+        OutputFormat::Json => {
+            let payload: Vec<SearchHitOutput> = hits
+                .iter()
+                .map(|hit| SearchHitOutput {
+                    score: hit.score,
+                    task: &hit.task,
+                })
+                .collect();
+            println!("{}", serde_json::to_string(&payload)?);
+        }
+        OutputFormat::Table => {
+            let colored = std::io::stdout().is_terminal();
+            let headers = ["#", "ID", "SCORE", "STATUS", "TITLE"];
+            let rows: Vec<Vec<String>> = hits
+                .iter()
+                .map(|hit| {
+                    let title = hit.task.title();
+                    vec![
+                        index_label(&hit.task, ids),
+                        ids.prefix(hit.task.task_id()),
+                        hit.score.to_string(),
+                        status_label(&hit.task),
+                        if colored {
+                            highlight(title, &query.highlights(title))
+                        } else {
+                            title.to_string()
+                        },
+                    ]
+                })
+                .collect();
+            print!("{}", format_table(&headers, &rows));
+        }
+    }
+    Ok(())
+}
+
+/// Trashed tasks with the time they were deleted.
+pub fn print_trash(tasks: &[Task], ids: &ShortIds, output: OutputFormat) -> CliResult<()> {
+    match output {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string(tasks)?);
+        }
+        OutputFormat::Table => {
+            let headers = ["#", "ID", "DELETED", "STATUS", "TITLE"];
+            let rows: Vec<Vec<String>> = tasks
+                .iter()
+                .enumerate()
+                .map(|(position, task)| {
+                    vec![
+                        (position + 1).to_string(),
+                        ids.prefix(task.task_id()),
+                        task.deleted_at()
+                            .map(|deleted_at| deleted_at.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_default(),
+                        status_label(task),
+                        task.title().to_string(),
+                    ]
+                })
+                .collect();
+            print!("{}", format_table(&headers, &rows));
+        }
+    }
+    Ok(())
+}
+
+pub fn print_purged(purged: &[Task], output: OutputFormat) -> CliResult<()> {
+    let message = format!("purged {} task(s) from the trash", purged.len());
+    match output {
+        OutputFormat::Json => {
+            let payload = PurgeOutput {
+                ids: purged
+                    .iter()
+                    .map(|task| task.task_id().to_string())
+                    .collect(),
+                message,
+            };
+            println!("{}", serde_json::to_string(&payload)?);
+        }
+        OutputFormat::Table => {
+            let rows = vec![vec!["PURGED".to_string(), message]];
+            print!("{}", format_table(&["RESULT", "MESSAGE"], &rows));
+        }
+    }
+    Ok(())
+}
+
+/// Backups that cannot be read back show as `invalid` rather than a count.
+pub fn print_backups(backups: &[Backup], output: OutputFormat) -> CliResult<()> {
+    let outputs: Vec<BackupOutput> = backups.iter().map(BackupOutput::from).collect();
+    match output {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string(&outputs)?);
+        }
+        OutputFormat::Table => {
+            let rows: Vec<Vec<String>> = backups
+                .iter()
+                .map(|backup| {
+                    vec![
+                        backup.name.clone(),
+                        backup.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        backup
+                            .tasks
+                            .map_or_else(|| "invalid".to_string(), |tasks| tasks.to_string()),
+                    ]
+                })
+                .collect();
+            print!("{}", format_table(&["NAME", "CREATED", "TASKS"], &rows));
+        }
+    }
+    Ok(())
+}
+
+pub fn print_backup(action: &str, backup: &Backup, output: OutputFormat) -> CliResult<()> {
+    let tasks: String = backup.tasks.map_or_else(
+        || "unreadable".to_string(),
+        |tasks| format!("{tasks} task(s)"),
     );
+    let message = format!("{action} backup {} ({tasks})", backup.name);
+    match output {
+        OutputFormat::Json => {
+            let payload = BackupActionOutput {
+                action: action.to_string(),
+                backup: BackupOutput::from(backup),
+                message,
+            };
+            println!("{}", serde_json::to_string(&payload)?);
+        }
+        OutputFormat::Table => {
+            let result = action.to_uppercase();
+            print!(
+                "{}",
+                format_table(&["RESULT", "MESSAGE"], &[vec![result, message]])
+            );
+        }
+    }
+    Ok(())
+}
 
-    for task in tasks {
-        let id = task.task_id().to_string();
-        println!(
-            "| {:<id_width$} | {:<status_width$} | {:<title_width$} |",
-            id,
-            status_label(task),
-            task.title()
-        );
+pub fn print_journal_entry(
+    action: &str,
+    entry: &JournalEntry,
+    output: OutputFormat,
+) -> CliResult<()> {
+    let message = format!("{action}: {}", entry.summary());
+    match output {
+        OutputFormat::Json => {
+            let payload = JournalOutput {
+                action: action.to_string(),
+                operation: entry.label().to_string(),
+                recorded_at: entry.recorded_at().to_rfc3339(),
+                tasks: entry.changes().len(),
+                message,
+            };
+            println!("{}", serde_json::to_string(&payload)?);
+        }
+        OutputFormat::Table => {
+            let result = action.to_uppercase();
+            print!(
+                "{}",
+                format_table(&["RESULT", "MESSAGE"], &[vec![result, message]])
+            );
+        }
+    }
+    Ok(())
+}
+
+fn print_tasks_table(tasks: &[Task], ids: &ShortIds) {
+    let headers = ["#", "ID", "STATUS", "PRIORITY", "DUE", "TITLE", "TAGS"];
+    let rows: Vec<Vec<String>> = tasks
+        .iter()
+        .map(|task| {
+            vec![
+                index_label(task, ids),
+                ids.prefix(task.task_id()),
+                status_label(task),
+                priority_label(task).to_string(),
+                due_label(task),
+                task.title().to_string(),
+                tags_label(task),
+            ]
+        })
+        .collect();
+    print!("{}", format_table(&headers, &rows));
+}
+
+fn format_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            rows.iter()
+                .map(|row| visible_width(&row[column]))
+                .max()
+                .unwrap_or(0)
+                .max(header.chars().count())
+        })
+        .collect();
+
+    let format_row = |cells: Vec<&str>| -> String {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| {
+                let padding = " ".repeat(width - visible_width(cell));
+                format!("{cell}{padding}")
+            })
+            .collect();
+        format!("| {} |\n", cells.join(" | "))
+    };
+
+    let mut table = format_row(headers.to_vec());
+    let separators: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    table.push_str(&format!("|-{}-|\n", separators.join("-|-")));
+    for row in rows {
+        table.push_str(&format_row(row.iter().map(String::as_str).collect()));
+    }
+    table
+}
+
+/// Characters a cell takes on screen, leaving out ANSI colour codes.
+fn visible_width(cell: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in cell.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+/// Wraps the given character ranges of `text` in highlight colour codes.
+fn highlight(text: &str, ranges: &[Range<usize>]) -> String {
+    let mut highlighted = String::new();
+    for (index, c) in text.chars().enumerate() {
+        if ranges.iter().any(|range| range.start == index) {
+            highlighted.push_str(HIGHLIGHT_START);
+        }
+        highlighted.push(c);
+        if ranges.iter().any(|range| range.end == index + 1) {
+            highlighted.push_str(HIGHLIGHT_END);
+        }
     }
+    highlighted
+}
+
+/// Blank for tasks outside the task list, e.g. archived ones.
+fn index_label(task: &Task, ids: &ShortIds) -> String {
+    ids.index(task.task_id())
+        .map(|index| index.to_string())
+        .unwrap_or_default()
 }
 
-fn status_label(task: &Task) -> &'static str {
-    match task.status() {
+fn status_label(task: &Task) -> String {
+    let label = match task.status() {
         TaskStatus::Todo => "TODO",
+        TaskStatus::InProgress => "IN_PROGRESS",
+        TaskStatus::Blocked => "BLOCKED",
         TaskStatus::Done => "DONE",
+        TaskStatus::Cancelled => "CANCELLED",
+    };
+    match task.blocked_reason() {
+        Some(reason) => format!("{label} ({reason})"),
+        None => label.to_string(),
+    }
+}
+
+fn priority_label(task: &Task) -> &'static str {
+    match task.priority() {
+        Priority::None => "-",
+        Priority::Low => "LOW",
+        Priority::Medium => "MEDIUM",
+        Priority::High => "HIGH",
+        Priority::Urgent => "URGENT",
+    }
+}
+
+/// The due date, marked with `↻` when the task recurs.
+fn due_label(task: &Task) -> String {
+    let due = task
+        .due()
+        .map(|due| due.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    match task.recurrence() {
+        Some(_) => format!("{due} ↻"),
+        None => due,
     }
 }
 
+fn tags_label(task: &Task) -> String {
+    task.tags()
+        .iter()
+        .map(|tag| tag.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
 #[derive(Debug, Serialize)]
 struct DeleteOutput {
     id: String,
     deleted: bool,
     message: String,
 }
+
+#[derive(Debug, Serialize)]
+struct BulkOutput<'a> {
+    dry_run: bool,
+    applied: bool,
+    results: Vec<BulkResultOutput<'a>>,
+    message: String,
+}
+
+/// The task as the action left it, or as it was when the action failed.
+#[derive(Debug, Serialize)]
+struct BulkResultOutput<'a> {
+    id: String,
+    ok: bool,
+    error: Option<String>,
+    task: &'a Task,
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectOutput {
+    id: Option<String>,
+    name: String,
+    open_tasks: usize,
+    total_tasks: usize,
+}
+
+impl From<&ProjectSummary> for ProjectOutput {
+    fn from(summary: &ProjectSummary) -> Self {
+        Self {
+            id: summary
+                .project
+                .as_ref()
+                .map(|project| project.project_id().to_string()),
+            name: summary
+                .project
+                .as_ref()
+                .map_or_else(|| INBOX.to_string(), |project| project.name().to_string()),
+            open_tasks: summary.open_tasks,
+            total_tasks: summary.total_tasks,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DeleteProjectOutput {
+    name: String,
+    moved_tasks: usize,
+    deleted_tasks: usize,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SearchHitOutput<'a> {
+    score: u32,
+    #[serde(flatten)]
+    task: &'a Task,
+}
+
+#[derive(Debug, Serialize)]
+struct PurgeOutput {
+    ids: Vec<String>,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+struct JournalOutput {
+    action: String,
+    operation: String,
+    recorded_at: String,
+    tasks: usize,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+struct BackupOutput {
+    name: String,
+    created_at: String,
+    valid: bool,
+    tasks: Option<usize>,
+}
+
+impl From<&Backup> for BackupOutput {
+    fn from(backup: &Backup) -> Self {
+        Self {
+            name: backup.name.clone(),
+            created_at: backup.created_at.to_rfc3339(),
+            valid: backup.is_valid(),
+            tasks: backup.tasks,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct BackupActionOutput {
+    action: String,
+    backup: BackupOutput,
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_table, highlight};
+
+    #[test]
+    fn format_table_pads_columns_to_widest_cell() {
+        let rows = vec![
+            vec!["1".to_string(), "short".to_string()],
+            vec!["2".to_string(), "a longer title".to_string()],
+        ];
+
+        let table = format_table(&["ID", "TITLE"], &rows);
+
+        assert_eq!(
+            table,
+            "| ID | TITLE          |\n\
+             |----|----------------|\n\
+             | 1  | short          |\n\
+             | 2  | a longer title |\n"
+        );
+    }
+
+    #[test]
+    fn highlighted_cells_are_padded_by_their_visible_width() {
+        let rows = vec![vec![highlight("buy milk", &[0..3, 4..8])]];
+
+        let table = format_table(&["TITLE"], &rows);
+
+        assert_eq!(
+            table,
+            "| TITLE    |\n\
+             |----------|\n\
+             | \x1b[1;33mbuy\x1b[0m \x1b[1;33mmilk\x1b[0m |\n"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::tasks::adapters::cli::errors::{CliError, CliResult};
+use crate::tasks::application::errors::ApplicationError;
+use crate::tasks::application::use_cases::list_backups::{ListBackupsService, ListBackupsUseCase};
+use crate::tasks::application::use_cases::restore_backup::{
+    RestoreBackupCommand, RestoreBackupService, RestoreBackupUseCase,
+};
+use crate::tasks::ports::outputs::backup_repository::{Backup, BackupRepository};
+use crate::tasks::ports::outputs::errors::RepoError;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use std::io::{BufRead, Write};
+
+/// Checks that the data file can be read before a command runs. When it is
+/// corrupted and a valid backup exists, an interactive session is asked
+/// whether to restore the newest one; otherwise the error names it so it can
+/// be restored with `todo backup restore`. Other errors are left for the
+/// command itself to report.
+pub fn offer_backup_if_corrupted<R: TaskRepository + BackupRepository + Clone>(
+    repo: &R,
+    interactive: bool,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> CliResult<()> {
+    let error: String = match repo.list(TaskQuery::All) {
+        Err(RepoError::Corrupted { error }) => error,
+        _ => return Ok(()),
+    };
+    let service: ListBackupsService<R> = ListBackupsService::new(repo.clone());
+    let Some(backup) = service.execute()?.into_iter().find(Backup::is_valid) else {
+        return Err(ApplicationError::from(RepoError::Corrupted { error }).into());
+    };
+    let refused = || CliError::CorruptedStore {
+        error: error.clone(),
+        backup: backup.name.clone(),
+    };
+    if !interactive {
+        return Err(refused());
+    }
+
+    write!(
+        out,
+        "the data file is corrupted: {error}\nrestore backup {} taken {} ({} task(s))? [y/N] ",
+        backup.name,
+        backup.created_at.format("%Y-%m-%d %H:%M:%S"),
+        backup.tasks.unwrap_or_default()
+    )?;
+    out.flush()?;
+    let mut answer: String = String::new();
+    input.read_line(&mut answer)?;
+    if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+        return Err(refused());
+    }
+
+    let mut service: RestoreBackupService<R> = RestoreBackupService::new(repo.clone());
+    let restored: Backup = service.execute(RestoreBackupCommand::new(backup.name.clone()))?;
+    writeln!(out, "restored backup {}", restored.name)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::offer_backup_if_corrupted;
+    use crate::tasks::adapters::cli::errors::CliError;
+    use crate::tasks::adapters::persistence::json_file_task_repository::JsonFileTaskRepository;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::backup_repository::BackupRepository;
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use std::fs;
+    use tempfile::{TempDir, tempdir};
+
+    /// A repository whose data file held one task when it was backed up and
+    /// has been corrupted since.
+    fn corrupted_repo() -> (TempDir, JsonFileTaskRepository) {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        repo.save(Task::new("Buy milk".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        repo.create_backup().expect("backup should succeed");
+        fs::write(repo.file_path(), "{\"tasks\": [").expect("write should succeed");
+        (temp, repo)
+    }
+
+    #[test]
+    fn an_accepted_offer_restores_the_newest_valid_backup() {
+        let (_temp, repo) = corrupted_repo();
+        let mut out: Vec<u8> = Vec::new();
+
+        offer_backup_if_corrupted(&repo, true, &mut "y\n".as_bytes(), &mut out)
+            .expect("restore should succeed");
+
+        let prompt = String::from_utf8(out).expect("prompt should be UTF-8");
+        assert!(prompt.contains("the data file is corrupted"));
+        assert!(prompt.contains("restored backup tasks-"));
+        let tasks = repo.list(TaskQuery::All).expect("list should succeed");
+        assert_eq!(tasks.len(), 1);
+    }
+
+    #[test]
+    fn a_declined_offer_or_no_terminal_leaves_the_file_and_names_the_backup() {
+        let (_temp, repo) = corrupted_repo();
+
+        let declined =
+            offer_backup_if_corrupted(&repo, true, &mut "\n".as_bytes(), &mut Vec::new());
+        let unattended =
+            offer_backup_if_corrupted(&repo, false, &mut "y\n".as_bytes(), &mut Vec::new());
+
+        assert!(matches!(declined, Err(CliError::CorruptedStore { .. })));
+        let message = unattended.expect_err("no terminal should fail").to_string();
+        assert!(message.contains("todo backup restore tasks-"));
+        assert!(repo.list(TaskQuery::All).is_err());
+    }
+
+    #[test]
+    fn a_readable_store_is_left_alone() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let mut out: Vec<u8> = Vec::new();
+
+        offer_backup_if_corrupted(&repo, true, &mut "y\n".as_bytes(), &mut out)
+            .expect("check should succeed");
+
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,20 @@
+use crate::tasks::ports::outputs::clock::Clock;
+use chrono::{DateTime, Utc};
+
+/// Clock frozen at a given instant, for deterministic tests.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock {
+    now: DateTime<Utc>,
+}
+
+impl FixedClock {
+    pub fn at(now: DateTime<Utc>) -> Self {
+        Self { now }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now
+    }
+}
@@ -0,0 +1,2 @@
+pub mod fixed_clock;
+pub mod system_clock;
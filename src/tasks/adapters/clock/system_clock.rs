@@ -0,0 +1,11 @@
+use crate::tasks::ports::outputs::clock::Clock;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
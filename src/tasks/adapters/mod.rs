@@ -1,2 +1,4 @@
+pub mod cli;
+pub mod clock;
 pub mod persistence;
 pub mod tui;
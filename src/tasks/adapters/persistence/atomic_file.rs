@@ -0,0 +1,153 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use tempfile::{Builder, NamedTempFile};
+
+/// Replaces the file at `path` with `payload` so that a crash or a failed
+/// write leaves either the old or the new contents, never a truncated file:
+/// the payload goes to a temporary file in the same directory, which is
+/// synced and then renamed over `path`, and the directory is synced so that
+/// the rename itself survives a crash.
+pub fn write_atomically(path: &Path, payload: &[u8]) -> io::Result<()> {
+    let temp: NamedTempFile = stage(path, |file| file.write_all(payload))?;
+    commit(temp, path)
+}
+
+/// Writes and syncs the temporary file; dropping it on an error removes it.
+fn stage(
+    path: &Path,
+    write: impl FnOnce(&mut File) -> io::Result<()>,
+) -> io::Result<NamedTempFile> {
+    let dir: &Path = parent_dir(path);
+    fs::create_dir_all(dir)?;
+    let name: String = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut temp: NamedTempFile = Builder::new()
+        .prefix(&format!(".{name}."))
+        .suffix(".tmp")
+        .tempfile_in(dir)?;
+    write(temp.as_file_mut())?;
+    temp.as_file().sync_all()?;
+    Ok(temp)
+}
+
+fn commit(temp: NamedTempFile, path: &Path) -> io::Result<()> {
+    temp.persist(path).map_err(|error| error.error)?;
+    sync_dir(parent_dir(path))
+}
+
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Directories cannot be opened for syncing here; the rename is as durable
+/// as the platform makes it.
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{commit, stage, write_atomically};
+    use std::fs;
+    use std::io::{self, Write};
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn entries(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .expect("dir should be readable")
+            .map(|entry| {
+                entry
+                    .expect("entry should be readable")
+                    .file_name()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn replaces_the_file_and_leaves_no_temporary_file() {
+        let temp = tempdir().expect("temp dir should be created");
+        let path = temp.path().join("tasks.json");
+        fs::write(&path, "old").expect("write should succeed");
+
+        write_atomically(&path, b"new").expect("atomic write should succeed");
+
+        assert_eq!(
+            fs::read_to_string(&path).expect("read should succeed"),
+            "new"
+        );
+        assert_eq!(entries(temp.path()), vec!["tasks.json"]);
+    }
+
+    #[test]
+    fn a_write_failing_halfway_keeps_the_previous_file() {
+        let temp = tempdir().expect("temp dir should be created");
+        let path = temp.path().join("tasks.json");
+        fs::write(&path, "previous contents").expect("write should succeed");
+
+        let result = stage(&path, |file| {
+            file.write_all(b"{\"tasks\":[")?;
+            Err(io::Error::other("no space left on device"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(
+            fs::read_to_string(&path).expect("read should succeed"),
+            "previous contents"
+        );
+        assert_eq!(entries(temp.path()), vec!["tasks.json"]);
+    }
+
+    #[test]
+    fn a_crash_before_the_rename_keeps_the_previous_file() {
+        let temp = tempdir().expect("temp dir should be created");
+        let path = temp.path().join("tasks.json");
+        fs::write(&path, "previous contents").expect("write should succeed");
+
+        // The process dies after writing the temporary file: nothing removes
+        // it and nothing renames it.
+        let staged =
+            stage(&path, |file| file.write_all(b"{\"tasks\":[")).expect("staging should succeed");
+        staged.keep().expect("temporary file should be kept");
+
+        assert_eq!(
+            fs::read_to_string(&path).expect("read should succeed"),
+            "previous contents"
+        );
+        write_atomically(&path, b"next").expect("a later write should succeed");
+        assert_eq!(
+            fs::read_to_string(&path).expect("read should succeed"),
+            "next"
+        );
+    }
+
+    #[test]
+    fn commit_creates_a_missing_file() {
+        let temp = tempdir().expect("temp dir should be created");
+        let path = temp.path().join("data").join("tasks.json");
+
+        let staged = stage(&path, |file| file.write_all(b"[]")).expect("staging should succeed");
+        commit(staged, &path).expect("commit should succeed");
+
+        assert_eq!(
+            fs::read_to_string(&path).expect("read should succeed"),
+            "[]"
+        );
+    }
+}
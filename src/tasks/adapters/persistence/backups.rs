@@ -0,0 +1,217 @@
+use crate::tasks::ports::outputs::errors::{RepoError, RepoResult};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.3fZ";
+
+/// How many automatic backups to keep and how often to take them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackupPolicy {
+    /// `0` turns automatic backups, and pruning, off.
+    pub keep: usize,
+    /// A write only takes a backup when the newest one is at least this old.
+    pub min_interval: Duration,
+}
+
+pub const DEFAULT_BACKUP_POLICY: BackupPolicy = BackupPolicy {
+    keep: 10,
+    min_interval: Duration::from_secs(60 * 60),
+};
+
+impl BackupPolicy {
+    /// The default policy, overridden by `TODO_BACKUP_KEEP` and
+    /// `TODO_BACKUP_INTERVAL_MINUTES` when they are set.
+    pub fn from_env() -> RepoResult<Self> {
+        Self::from_settings(
+            env::var(KEEP_VAR).ok().as_deref(),
+            env::var(INTERVAL_VAR).ok().as_deref(),
+        )
+    }
+
+    fn from_settings(keep: Option<&str>, interval_minutes: Option<&str>) -> RepoResult<Self> {
+        let keep: usize = match keep {
+            None => DEFAULT_BACKUP_POLICY.keep,
+            Some(value) => parse_number(KEEP_VAR, value)?,
+        };
+        let min_interval: Duration = match interval_minutes {
+            None => DEFAULT_BACKUP_POLICY.min_interval,
+            Some(value) => parse_number::<u64>(INTERVAL_VAR, value)?
+                .checked_mul(60)
+                .map(Duration::from_secs)
+                .ok_or_else(|| RepoError::InternalError {
+                    error: format!(
+                        "{INTERVAL_VAR} must be a whole number of at most {} minutes, got '{value}'",
+                        u64::MAX / 60
+                    ),
+                })?,
+        };
+        Ok(Self { keep, min_interval })
+    }
+}
+
+const KEEP_VAR: &str = "TODO_BACKUP_KEEP";
+const INTERVAL_VAR: &str = "TODO_BACKUP_INTERVAL_MINUTES";
+
+fn parse_number<T: FromStr>(name: &str, value: &str) -> RepoResult<T> {
+    value.trim().parse().map_err(|_| RepoError::InternalError {
+        error: format!("{name} must be a whole number, got '{value}'"),
+    })
+}
+
+/// Timestamped copies of one data file, kept in a directory of their own:
+/// `tasks.json` is copied to `backups/tasks-20260321T180000.000Z.json`.
+#[derive(Debug, Clone)]
+pub struct BackupDir {
+    dir: PathBuf,
+    stem: String,
+}
+
+impl BackupDir {
+    pub fn new(dir: PathBuf, data_file: &Path) -> Self {
+        let stem: String = data_file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self { dir, stem }
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Backup names with the time each was taken, in no particular order.
+    /// Files not named like a backup are ignored.
+    pub fn list(&self) -> io::Result<Vec<(String, DateTime<Utc>)>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut backups: Vec<(String, DateTime<Utc>)> = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let name: String = entry?.file_name().to_string_lossy().into_owned();
+            if let Some(created_at) = self.created_at(&name) {
+                backups.push((name, created_at));
+            }
+        }
+        Ok(backups)
+    }
+
+    pub fn newest(&self) -> io::Result<Option<DateTime<Utc>>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .map(|(_, created_at)| created_at)
+            .max())
+    }
+
+    /// A name for a backup taken at `now` that is not taken yet.
+    pub fn next_name(&self, now: DateTime<Utc>) -> String {
+        let mut at: DateTime<Utc> = now;
+        loop {
+            let name: String = format!("{}-{}.json", self.stem, at.format(TIMESTAMP_FORMAT));
+            if !self.path(&name).exists() {
+                return name;
+            }
+            at += chrono::Duration::milliseconds(1);
+        }
+    }
+
+    /// The time encoded in a backup name, if it is one.
+    pub fn created_at(&self, name: &str) -> Option<DateTime<Utc>> {
+        let timestamp: &str = name
+            .strip_prefix(&self.stem)?
+            .strip_prefix('-')?
+            .strip_suffix(".json")?;
+        NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+            .ok()
+            .map(|naive| naive.and_utc())
+    }
+
+    /// Removes all but the `keep` newest backups.
+    pub fn prune(&self, keep: usize) -> io::Result<()> {
+        let mut backups: Vec<(String, DateTime<Utc>)> = self.list()?;
+        backups.sort_by_key(|(_, created_at)| std::cmp::Reverse(*created_at));
+        for (name, _) in backups.into_iter().skip(keep) {
+            fs::remove_file(self.path(&name))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BackupDir, BackupPolicy, DEFAULT_BACKUP_POLICY};
+    use chrono::{Duration, TimeZone, Utc};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    #[test]
+    fn names_round_trip_to_their_timestamp_and_never_collide() {
+        let temp = tempdir().expect("temp dir should be created");
+        let backups = BackupDir::new(temp.path().to_path_buf(), Path::new("tasks.json"));
+        let at = Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap();
+
+        let first = backups.next_name(at);
+        fs::write(backups.path(&first), "{}").expect("write should succeed");
+        let second = backups.next_name(at);
+
+        assert_eq!(first, "tasks-20260321T180000.000Z.json");
+        assert_eq!(backups.created_at(&first), Some(at));
+        assert_eq!(
+            backups.created_at(&second),
+            Some(at + Duration::milliseconds(1))
+        );
+        assert_eq!(backups.created_at("notes.txt"), None);
+    }
+
+    #[test]
+    fn settings_override_the_default_policy_and_are_validated() {
+        let policy =
+            BackupPolicy::from_settings(Some("3"), Some(" 30 ")).expect("settings should be valid");
+
+        assert_eq!(policy.keep, 3);
+        assert_eq!(policy.min_interval, std::time::Duration::from_secs(30 * 60));
+        assert_eq!(
+            BackupPolicy::from_settings(None, None).expect("defaults should be valid"),
+            DEFAULT_BACKUP_POLICY
+        );
+        assert!(BackupPolicy::from_settings(Some("-1"), None).is_err());
+        assert!(BackupPolicy::from_settings(None, Some("soon")).is_err());
+        assert!(BackupPolicy::from_settings(None, Some(&u64::MAX.to_string())).is_err());
+    }
+
+    #[test]
+    fn prune_keeps_the_newest_backups_and_ignores_other_files() {
+        let temp = tempdir().expect("temp dir should be created");
+        let backups = BackupDir::new(temp.path().to_path_buf(), Path::new("tasks.json"));
+        let start = Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap();
+        for hour in 0..4 {
+            let name = backups.next_name(start + Duration::hours(hour));
+            fs::write(backups.path(&name), "{}").expect("write should succeed");
+        }
+        fs::write(temp.path().join("README"), "mine").expect("write should succeed");
+
+        backups.prune(2).expect("prune should succeed");
+
+        let mut left: Vec<String> = backups
+            .list()
+            .expect("list should succeed")
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        left.sort();
+        assert_eq!(
+            left,
+            vec![
+                "tasks-20260321T200000.000Z.json",
+                "tasks-20260321T210000.000Z.json"
+            ]
+        );
+        assert!(temp.path().join("README").exists());
+    }
+}
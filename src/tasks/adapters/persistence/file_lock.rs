@@ -0,0 +1,109 @@
+use crate::tasks::ports::outputs::errors::{RepoError, RepoResult};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait for another process before giving up.
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Any number of readers at once.
+    Shared,
+    /// One writer, and no readers, for a whole read-modify-write.
+    Exclusive,
+}
+
+/// An advisory lock on a lock file, held until dropped. Only processes that
+/// take the lock too are kept out; each open of the lock file is locked on
+/// its own, so a process must not take it twice.
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Waits up to `timeout` for the lock, creating the lock file if needed.
+    pub fn acquire(path: &Path, mode: LockMode, timeout: Duration) -> RepoResult<Self> {
+        let internal = |e: std::io::Error| RepoError::InternalError {
+            error: format!("Opening lock file '{}'. E: {e:?}", path.display()),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(internal)?;
+        }
+        let file: File = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map_err(internal)?;
+        let started: Instant = Instant::now();
+        loop {
+            let attempt = match mode {
+                LockMode::Shared => file.try_lock_shared(),
+                LockMode::Exclusive => file.try_lock(),
+            };
+            match attempt {
+                Ok(()) => return Ok(Self { file }),
+                Err(TryLockError::WouldBlock) if started.elapsed() < timeout => {
+                    thread::sleep(RETRY_INTERVAL);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(RepoError::Locked {
+                        path: path.display().to_string(),
+                        waited: timeout,
+                    });
+                }
+                Err(TryLockError::Error(e)) => return Err(internal(e)),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // Closing the file releases the lock anyway.
+        let _ = self.file.unlock();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileLock, LockMode};
+    use crate::tasks::ports::outputs::errors::RepoError;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn shared_locks_coexist_but_exclude_a_writer() {
+        let temp = tempdir().expect("temp dir should be created");
+        let path = temp.path().join("tasks.lock");
+        let timeout = Duration::from_millis(50);
+
+        let first = FileLock::acquire(&path, LockMode::Shared, timeout);
+        let second = FileLock::acquire(&path, LockMode::Shared, timeout);
+        let writer = FileLock::acquire(&path, LockMode::Exclusive, timeout);
+
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+        assert!(matches!(writer, Err(RepoError::Locked { .. })));
+    }
+
+    #[test]
+    fn the_lock_is_released_when_dropped() {
+        let temp = tempdir().expect("temp dir should be created");
+        let path = temp.path().join("tasks.lock");
+        let timeout = Duration::from_millis(50);
+
+        let writer =
+            FileLock::acquire(&path, LockMode::Exclusive, timeout).expect("lock should be taken");
+        assert!(FileLock::acquire(&path, LockMode::Shared, timeout).is_err());
+        drop(writer);
+
+        assert!(FileLock::acquire(&path, LockMode::Shared, timeout).is_ok());
+    }
+}
@@ -1,18 +1,33 @@
+use crate::tasks::domain::journal::Journal;
+use crate::tasks::domain::project::Project;
 use crate::tasks::domain::task::{Task, TaskStatus};
-use crate::tasks::ports::outputs::errors::RepoResult;
-use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::backup_repository::{Backup, BackupRepository};
+use crate::tasks::ports::outputs::errors::{RepoError, RepoResult};
+use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository, next_version};
+use chrono::Utc;
 use std::collections::HashMap;
 use uuid::Uuid;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct InMemoryTaskRepository {
     cache: HashMap<Uuid, Task>,
+    projects: HashMap<Uuid, Project>,
+    journal: Journal,
+    archived: HashMap<Uuid, Task>,
+    backups: Vec<(Backup, HashMap<Uuid, Task>)>,
 }
 
 impl InMemoryTaskRepository {
     pub fn new() -> Self {
         Self {
             cache: HashMap::default(),
+            projects: HashMap::default(),
+            journal: Journal::default(),
+            archived: HashMap::default(),
+            backups: Vec::default(),
         }
     }
 
@@ -45,19 +60,38 @@ impl Default for InMemoryTaskRepository {
 }
 
 impl TaskRepository for InMemoryTaskRepository {
-    fn save(&mut self, task: Task) -> RepoResult<()> {
-        Self::add_task(self, task)
+    fn save(&mut self, task: Task) -> RepoResult<Task> {
+        let task: Task = next_version(self.cache.get(&task.task_id()), task)?;
+        Self::add_task(self, task.clone())?;
+        Ok(task)
+    }
+
+    /// Checks every task before storing any, like the JSON store's single
+    /// write.
+    fn save_all(&mut self, tasks: Vec<Task>) -> RepoResult<Vec<Task>> {
+        let mut staged: HashMap<Uuid, Task> = self.cache.clone();
+        let saved: Vec<Task> = tasks
+            .into_iter()
+            .map(|task| {
+                let task: Task = next_version(staged.get(&task.task_id()), task)?;
+                staged.insert(task.task_id(), task.clone());
+                Ok(task)
+            })
+            .collect::<RepoResult<Vec<Task>>>()?;
+        self.cache = staged;
+        Ok(saved)
     }
 
     fn list(&self, query: TaskQuery) -> RepoResult<Vec<Task>> {
-        let result = match query {
-            TaskQuery::All => self.cache.values().cloned().collect(),
-            TaskQuery::ByStatus(task_status) => self.get_task_by_status(task_status),
-        };
-        Ok(result)
+        Ok(self
+            .cache
+            .values()
+            .filter(|task| query.matches(task))
+            .cloned()
+            .collect())
     }
 
-    fn find_by_id(&self, task_id: Uuid) -> RepoResult<Option<Task>> {
+    fn find_any(&self, task_id: Uuid) -> RepoResult<Option<Task>> {
         Ok(self.get_task_by_id(task_id).cloned())
     }
 
@@ -69,11 +103,101 @@ impl TaskRepository for InMemoryTaskRepository {
     }
 }
 
+impl ProjectRepository for InMemoryTaskRepository {
+    fn save_project(&mut self, project: Project) -> RepoResult<()> {
+        self.projects.insert(project.project_id(), project);
+        Ok(())
+    }
+
+    fn list_projects(&self) -> RepoResult<Vec<Project>> {
+        Ok(self.projects.values().cloned().collect())
+    }
+
+    fn find_project_by_id(&self, id: Uuid) -> RepoResult<Option<Project>> {
+        Ok(self.projects.get(&id).cloned())
+    }
+
+    fn find_project_by_name(&self, name: &str) -> RepoResult<Option<Project>> {
+        Ok(self
+            .projects
+            .values()
+            .find(|project| project.has_name(name))
+            .cloned())
+    }
+
+    fn delete_project(&mut self, id: Uuid) -> RepoResult<bool> {
+        Ok(self.projects.remove(&id).is_some())
+    }
+}
+
+impl ArchiveRepository for InMemoryTaskRepository {
+    fn list_archived(&self) -> RepoResult<Vec<Task>> {
+        Ok(self.archived.values().cloned().collect())
+    }
+
+    fn archive(&mut self, tasks: Vec<Task>) -> RepoResult<()> {
+        self.archived
+            .extend(tasks.into_iter().map(|task| (task.task_id(), task)));
+        Ok(())
+    }
+
+    fn delete_archived(&mut self, id: Uuid) -> RepoResult<bool> {
+        Ok(self.archived.remove(&id).is_some())
+    }
+}
+
+impl BackupRepository for InMemoryTaskRepository {
+    fn list_backups(&self) -> RepoResult<Vec<Backup>> {
+        Ok(self
+            .backups
+            .iter()
+            .map(|(backup, _)| backup.clone())
+            .collect())
+    }
+
+    fn create_backup(&mut self) -> RepoResult<Backup> {
+        let backup: Backup = Backup {
+            name: format!("backup-{}", self.backups.len() + 1),
+            created_at: Utc::now(),
+            tasks: Some(self.cache.len()),
+        };
+        self.backups.push((backup.clone(), self.cache.clone()));
+        Ok(backup)
+    }
+
+    fn restore_backup(&mut self, name: &str) -> RepoResult<Backup> {
+        let (backup, tasks) = self
+            .backups
+            .iter()
+            .find(|(backup, _)| backup.name == name)
+            .cloned()
+            .ok_or_else(|| RepoError::BackupNotFound {
+                name: name.to_string(),
+            })?;
+        self.cache = tasks;
+        self.journal = Journal::default();
+        Ok(backup)
+    }
+}
+
+impl JournalRepository for InMemoryTaskRepository {
+    fn load_journal(&self) -> RepoResult<Journal> {
+        Ok(self.journal.clone())
+    }
+
+    fn save_journal(&mut self, journal: Journal) -> RepoResult<()> {
+        self.journal = journal;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::InMemoryTaskRepository;
+    use crate::tasks::domain::filter::FilterExpr;
     use crate::tasks::domain::task::{Task, TaskStatus};
     use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use chrono::Utc;
 
     fn new_task(title: &str) -> Task {
         Task::new(title.to_string()).expect("task should be created")
@@ -156,7 +280,7 @@ mod tests {
         let mut repo = InMemoryTaskRepository::new();
         let original = new_task("pay rent");
         let id = original.task_id();
-        repo.save(original.clone()).expect("save should succeed");
+        let original = repo.save(original).expect("save should succeed");
 
         let updated = original
             .mark_done()
@@ -170,4 +294,76 @@ mod tests {
         let found = found.expect("task should exist");
         assert_eq!(found.status(), TaskStatus::Done);
     }
+
+    #[test]
+    fn trashed_tasks_only_show_up_in_the_trash() {
+        let mut repo = InMemoryTaskRepository::new();
+        let task = new_task("old idea");
+        let id = task.task_id();
+        repo.save(task.trash(Utc::now()).expect("trash should succeed"))
+            .expect("save should succeed");
+        repo.save(new_task("live")).expect("save should succeed");
+
+        let all = repo.list(TaskQuery::All).expect("list should succeed");
+        let trashed = repo.list(TaskQuery::Trashed).expect("list should succeed");
+
+        assert_eq!(all.len(), 1);
+        assert_eq!(trashed.len(), 1);
+        assert!(repo.find_by_id(id).expect("find should succeed").is_none());
+        assert!(repo.find_any(id).expect("find should succeed").is_some());
+    }
+
+    #[test]
+    fn children_and_descendants_follow_parent_links() {
+        let mut repo = InMemoryTaskRepository::new();
+        let root = new_task("release");
+        let child = new_task("docs")
+            .set_parent(Some(root.task_id()))
+            .expect("parent should be set");
+        let grandchild = new_task("changelog")
+            .set_parent(Some(child.task_id()))
+            .expect("parent should be set");
+        let [_, child, grandchild] = [root.clone(), child, grandchild]
+            .map(|task| repo.save(task).expect("save should succeed"));
+        repo.save(new_task("unrelated"))
+            .expect("save should succeed");
+
+        let children = repo
+            .children(root.task_id())
+            .expect("children should succeed");
+        let descendants = repo
+            .descendants(root.task_id())
+            .expect("descendants should succeed");
+
+        assert_eq!(children, vec![child.clone()]);
+        assert_eq!(descendants, vec![child, grandchild]);
+    }
+
+    #[test]
+    fn filters_push_an_and_ed_equality_condition_down() {
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(new_task("deploy api"))
+            .expect("save should succeed");
+        repo.save(
+            new_task("deploy docs")
+                .mark_done()
+                .expect("status transition should succeed"),
+        )
+        .expect("save should succeed");
+        let query = |text: &str| TaskQuery::Where {
+            filter: FilterExpr::parse(text).expect("filter should parse"),
+            now: Utc::now(),
+        };
+
+        assert!(matches!(
+            query("title~deploy and status:done").pushable(),
+            Some(TaskQuery::ByStatus(TaskStatus::Done))
+        ));
+        assert!(query("status:done or title~api").pushable().is_none());
+        let tasks = repo
+            .list_matching(&[TaskQuery::All, query("title~deploy and status:done")])
+            .expect("list should succeed");
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title(), "deploy docs");
+    }
 }
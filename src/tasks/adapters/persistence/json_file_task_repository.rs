@@ -1,15 +1,40 @@
+use crate::tasks::adapters::persistence::atomic_file::write_atomically;
+use crate::tasks::adapters::persistence::backups::{
+    BackupDir, BackupPolicy, DEFAULT_BACKUP_POLICY,
+};
+use crate::tasks::adapters::persistence::file_lock::{DEFAULT_LOCK_TIMEOUT, FileLock, LockMode};
+use crate::tasks::adapters::persistence::schema::{
+    SCHEMA_VERSION, migrate, migrate_archive, migrate_journal, schema_version,
+};
+use crate::tasks::domain::journal::Journal;
+use crate::tasks::domain::project::Project;
 use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::backup_repository::{Backup, BackupRepository};
 use crate::tasks::ports::outputs::errors::{RepoError, RepoResult};
-use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository, next_version};
+use chrono::{DateTime, Utc};
 use directories::ProjectDirs;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use uuid::Uuid;
 
+/// Every method takes an advisory lock on `tasks.lock` next to the data
+/// file for its duration: shared to read, exclusive to read, modify and write
+/// back, so that concurrent processes do not overwrite each other's changes.
+/// Before the data file is rewritten, it is copied to `backups/` when the
+/// newest backup there is older than the backup policy's interval.
 #[derive(Debug, Clone)]
 pub struct JsonFileTaskRepository {
     file_path: PathBuf,
+    lock_timeout: Duration,
+    backup_policy: BackupPolicy,
 }
 
 impl JsonFileTaskRepository {
@@ -27,30 +52,217 @@ impl JsonFileTaskRepository {
             ),
         })?;
         let file_path = data_dir.join("tasks.json");
-        Ok(Self { file_path })
+        Ok(Self::using(file_path).with_backup_policy(BackupPolicy::from_env()?))
     }
     pub fn using(file_path: PathBuf) -> Self {
-        Self { file_path }
+        Self {
+            file_path,
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+            backup_policy: DEFAULT_BACKUP_POLICY,
+        }
+    }
+
+    pub fn with_backup_policy(self, backup_policy: BackupPolicy) -> Self {
+        Self {
+            backup_policy,
+            ..self
+        }
+    }
+
+    /// How long to wait for another process holding the lock.
+    pub fn with_lock_timeout(self, lock_timeout: Duration) -> Self {
+        Self {
+            lock_timeout,
+            ..self
+        }
     }
     pub fn file_path(&self) -> &Path {
         &self.file_path
     }
+    /// The undo journal lives next to the data file (`tasks.journal.json`).
+    pub fn journal_path(&self) -> PathBuf {
+        self.file_path.with_extension("journal.json")
+    }
+
+    /// Archived tasks live next to the data file (`tasks.archive.json`).
+    pub fn archive_path(&self) -> PathBuf {
+        self.file_path.with_extension("archive.json")
+    }
+
+    /// Where a data file written with an older `schema_version` is kept
+    /// before it is upgraded (`tasks.v1.backup.json`).
+    pub fn schema_backup_path(&self, version: u32) -> PathBuf {
+        schema_backup_path(&self.file_path, version)
+    }
+
+    /// Rotating backups of the data file live in `backups/` next to it.
+    pub fn backups_path(&self) -> PathBuf {
+        self.file_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join("backups")
+    }
+
+    fn backups(&self) -> BackupDir {
+        BackupDir::new(self.backups_path(), &self.file_path)
+    }
+
+    /// The lock file guarding the data, archive and journal files
+    /// (`tasks.lock`).
+    pub fn lock_path(&self) -> PathBuf {
+        self.file_path.with_extension("lock")
+    }
+
+    fn lock(&self, mode: LockMode) -> RepoResult<FileLock> {
+        FileLock::acquire(&self.lock_path(), mode, self.lock_timeout)
+    }
+
+    fn read_archive_file(&self) -> RepoResult<Vec<Task>> {
+        let ArchiveFile { tasks, .. } = self.read_for_update(&self.archive_path())?;
+        Ok(tasks)
+    }
+
+    fn write_archive_file(&self, tasks: Vec<Task>) -> RepoResult<()> {
+        let archive_file = ArchiveFile {
+            schema_version: SCHEMA_VERSION,
+            tasks,
+        };
+        let payload =
+            serde_json::to_string(&archive_file).map_err(|e| RepoError::InternalError {
+                error: format!("Serializing archive. E: {e:?}"),
+            })?;
+        write_atomically(&self.archive_path(), payload.as_bytes()).map_err(|e| {
+            RepoError::InternalError {
+                error: format!("Writing archive. E: {e:?}"),
+            }
+        })
+    }
 
     fn read_task_file(&self) -> RepoResult<TasksFile> {
-        if !self.file_path.exists() {
-            Ok(TasksFile::default())
-        } else {
-            let file =
-                fs::read_to_string(&self.file_path).map_err(|e| RepoError::InternalError {
-                    error: format!("Reading data from file. E: {e:?}"),
-                })?;
-            serde_json::from_str(file.as_str()).map_err(|e| RepoError::InternalError {
-                error: format!("Parsing data from file to tasks. E: {e:?}"),
-            })
+        self.read_for_update(&self.file_path)
+    }
+
+    /// Reads a file under a shared lock. Backing up a file written with an
+    /// older schema is a write, so when one is due the read is done again
+    /// under the exclusive lock, where another process may have done it
+    /// first.
+    fn read_shared<F: VersionedFile>(&self, path: &Path) -> RepoResult<F> {
+        let read: Upgraded<F> = {
+            let _lock: FileLock = self.lock(LockMode::Shared)?;
+            self.read_versioned(path)?
+        };
+        if read.pending_backup.is_none() {
+            return Ok(read.file);
+        }
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
+        self.read_for_update(path)
+    }
+
+    /// Reads a file while the exclusive lock is held, backing it up first
+    /// if it was written with an older schema.
+    fn read_for_update<F: VersionedFile>(&self, path: &Path) -> RepoResult<F> {
+        let Upgraded {
+            file,
+            pending_backup,
+        } = self.read_versioned(path)?;
+        if let Some((version, contents)) = pending_backup {
+            back_up_before_migrating(&schema_backup_path(path, version), &contents)?;
+        }
+        Ok(file)
+    }
+
+    /// Files written with an older schema are upgraded in memory, and
+    /// reach the disk in the current format with the next write.
+    fn read_versioned<F: VersionedFile>(&self, path: &Path) -> RepoResult<Upgraded<F>> {
+        if !path.exists() {
+            return Ok(Upgraded {
+                file: F::default(),
+                pending_backup: None,
+            });
+        }
+        let contents: String = fs::read_to_string(path).map_err(|e| RepoError::InternalError {
+            error: format!("Reading {} from file. E: {e:?}", F::NAME),
+        })?;
+        let document: Value = serde_json::from_str(&contents).map_err(F::parse_error)?;
+        let found: u32 = schema_version(&document)?;
+        let file: F = serde_json::from_value(F::migrate(document)?).map_err(F::parse_error)?;
+        let pending_backup: Option<(u32, String)> = (found < SCHEMA_VERSION
+            && !schema_backup_path(path, found).exists())
+        .then_some((found, contents));
+        Ok(Upgraded {
+            file,
+            pending_backup,
+        })
+    }
+
+    fn read_data_file(&self) -> RepoResult<String> {
+        fs::read_to_string(&self.file_path).map_err(|e| RepoError::InternalError {
+            error: format!("Reading data from file. E: {e:?}"),
+        })
+    }
+
+    fn read_journal_file(&self) -> RepoResult<Journal> {
+        let JournalFile { journal, .. } = self.read_for_update(&self.journal_path())?;
+        Ok(journal)
+    }
+
+    fn write_journal_file(&self, journal: Journal) -> RepoResult<()> {
+        let journal_file = JournalFile {
+            schema_version: SCHEMA_VERSION,
+            journal,
+        };
+        let payload =
+            serde_json::to_string(&journal_file).map_err(|e| RepoError::InternalError {
+                error: format!("Serializing journal. E: {e:?}"),
+            })?;
+        write_atomically(&self.journal_path(), payload.as_bytes()).map_err(|e| {
+            RepoError::InternalError {
+                error: format!("Writing journal. E: {e:?}"),
+            }
+        })
+    }
+
+    /// Copies the data file as it is now to a new backup.
+    fn copy_to_backup(&self, backups: &BackupDir, now: DateTime<Utc>) -> RepoResult<Backup> {
+        let contents: String = self.read_data_file()?;
+        let name: String = backups.next_name(now);
+        write_atomically(&backups.path(&name), contents.as_bytes()).map_err(|e| {
+            RepoError::InternalError {
+                error: format!("Writing backup. E: {e:?}"),
+            }
+        })?;
+        Ok(Backup {
+            name,
+            created_at: now,
+            tasks: parse_tasks_file(&contents)
+                .ok()
+                .map(|tasks_file| tasks_file.tasks.len()),
+        })
+    }
+
+    /// Takes a backup before a write when the newest one is old enough, and
+    /// drops those beyond the policy's count.
+    fn rotate_backups(&self) -> RepoResult<()> {
+        let BackupPolicy { keep, min_interval } = self.backup_policy;
+        if keep == 0 || !self.file_path.exists() {
+            return Ok(());
+        }
+        let backups: BackupDir = self.backups();
+        let now: DateTime<Utc> = Utc::now();
+        let newest: Option<DateTime<Utc>> = backups.newest().map_err(backup_error)?;
+        let due: bool = newest
+            .is_none_or(|newest| (now - newest).to_std().is_ok_and(|age| age >= min_interval));
+        if !due {
+            return Ok(());
         }
+        self.copy_to_backup(&backups, now)?;
+        backups.prune(keep).map_err(backup_error)
     }
 
+    /// The data file is replaced atomically, so an interrupted write leaves
+    /// the previous version in place.
     fn write_tasks_file(&self, tasks_file: &TasksFile) -> RepoResult<()> {
+        self.rotate_backups()?;
         if let Some(parent) = self.file_path.parent() {
             fs::create_dir_all(parent).map_err(|e| RepoError::InternalError {
                 error: format!(
@@ -64,47 +276,54 @@ impl JsonFileTaskRepository {
             error: format!("Serializing data. E: {e:?}"),
         })?;
 
-        fs::write(&self.file_path, payload).map_err(|e| RepoError::InternalError {
-            error: format!("Writing data. E: {e:?}"),
+        write_atomically(&self.file_path, payload.as_bytes()).map_err(|e| {
+            RepoError::InternalError {
+                error: format!("Writing data. E: {e:?}"),
+            }
         })
     }
 }
 
 impl TaskRepository for JsonFileTaskRepository {
-    fn save(&mut self, task: Task) -> RepoResult<()> {
+    fn save(&mut self, task: Task) -> RepoResult<Task> {
+        let mut saved: Vec<Task> = self.save_all(vec![task])?;
+        Ok(saved.remove(0))
+    }
+
+    fn save_all(&mut self, tasks: Vec<Task>) -> RepoResult<Vec<Task>> {
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
         let mut tasks_file = self.read_task_file()?;
 
-        if let Some(index) = tasks_file
-            .tasks
-            .iter()
-            .position(|stored| stored.task_id() == task.task_id())
-        {
-            tasks_file.tasks[index] = task;
-        } else {
-            tasks_file.tasks.push(task);
+        let mut saved: Vec<Task> = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let index: Option<usize> = tasks_file
+                .tasks
+                .iter()
+                .position(|stored| stored.task_id() == task.task_id());
+            let task: Task = next_version(index.map(|index| &tasks_file.tasks[index]), task)?;
+            match index {
+                Some(index) => tasks_file.tasks[index] = task.clone(),
+                None => tasks_file.tasks.push(task.clone()),
+            }
+            saved.push(task);
         }
 
-        self.write_tasks_file(&tasks_file)
+        self.write_tasks_file(&tasks_file)?;
+        Ok(saved)
     }
 
     fn list(&self, query: TaskQuery) -> RepoResult<Vec<Task>> {
-        let TasksFile { tasks } = self.read_task_file()?;
-        match query {
-            TaskQuery::All => Ok(tasks),
-            TaskQuery::ByStatus(status) => Ok(tasks
-                .iter()
-                .filter(|&t| t.status() == status)
-                .cloned()
-                .collect()),
-        }
+        let TasksFile { tasks, .. } = self.read_shared(&self.file_path)?;
+        Ok(tasks.into_iter().filter(|t| query.matches(t)).collect())
     }
 
-    fn find_by_id(&self, id: Uuid) -> RepoResult<Option<Task>> {
-        let TasksFile { tasks } = self.read_task_file()?;
+    fn find_any(&self, id: Uuid) -> RepoResult<Option<Task>> {
+        let TasksFile { tasks, .. } = self.read_shared(&self.file_path)?;
         Ok(tasks.iter().find(|&t| t.task_id() == id).cloned())
     }
 
     fn delete(&mut self, id: Uuid) -> RepoResult<bool> {
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
         let mut tasks_file = self.read_task_file()?;
         let initial_len = tasks_file.tasks.len();
         tasks_file.tasks.retain(|task| task.task_id() != id);
@@ -118,23 +337,345 @@ impl TaskRepository for JsonFileTaskRepository {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+impl ProjectRepository for JsonFileTaskRepository {
+    fn save_project(&mut self, project: Project) -> RepoResult<()> {
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
+        let mut tasks_file = self.read_task_file()?;
+
+        if let Some(index) = tasks_file
+            .projects
+            .iter()
+            .position(|stored| stored.project_id() == project.project_id())
+        {
+            tasks_file.projects[index] = project;
+        } else {
+            tasks_file.projects.push(project);
+        }
+
+        self.write_tasks_file(&tasks_file)
+    }
+
+    fn list_projects(&self) -> RepoResult<Vec<Project>> {
+        let TasksFile { projects, .. } = self.read_shared(&self.file_path)?;
+        Ok(projects)
+    }
+
+    fn find_project_by_id(&self, id: Uuid) -> RepoResult<Option<Project>> {
+        let TasksFile { projects, .. } = self.read_shared(&self.file_path)?;
+        Ok(projects.into_iter().find(|p| p.project_id() == id))
+    }
+
+    fn find_project_by_name(&self, name: &str) -> RepoResult<Option<Project>> {
+        let TasksFile { projects, .. } = self.read_shared(&self.file_path)?;
+        Ok(projects.into_iter().find(|p| p.has_name(name)))
+    }
+
+    fn delete_project(&mut self, id: Uuid) -> RepoResult<bool> {
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
+        let mut tasks_file = self.read_task_file()?;
+        let initial_len = tasks_file.projects.len();
+        tasks_file
+            .projects
+            .retain(|project| project.project_id() != id);
+
+        if tasks_file.projects.len() == initial_len {
+            return Ok(false);
+        }
+
+        self.write_tasks_file(&tasks_file)?;
+        Ok(true)
+    }
+}
+
+impl ArchiveRepository for JsonFileTaskRepository {
+    fn list_archived(&self) -> RepoResult<Vec<Task>> {
+        let ArchiveFile { tasks, .. } = self.read_shared(&self.archive_path())?;
+        Ok(tasks)
+    }
+
+    fn archive(&mut self, tasks: Vec<Task>) -> RepoResult<()> {
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
+        let mut archived: Vec<Task> = self.read_archive_file()?;
+        for task in tasks {
+            archived.retain(|stored| stored.task_id() != task.task_id());
+            archived.push(task);
+        }
+        self.write_archive_file(archived)
+    }
+
+    fn delete_archived(&mut self, id: Uuid) -> RepoResult<bool> {
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
+        let mut archived: Vec<Task> = self.read_archive_file()?;
+        let initial_len = archived.len();
+        archived.retain(|task| task.task_id() != id);
+        if archived.len() == initial_len {
+            return Ok(false);
+        }
+        self.write_archive_file(archived)?;
+        Ok(true)
+    }
+}
+
+impl BackupRepository for JsonFileTaskRepository {
+    fn list_backups(&self) -> RepoResult<Vec<Backup>> {
+        let _lock: FileLock = self.lock(LockMode::Shared)?;
+        let backups: BackupDir = self.backups();
+        Ok(backups
+            .list()
+            .map_err(backup_error)?
+            .into_iter()
+            .map(|(name, created_at)| {
+                let tasks: Option<usize> = fs::read_to_string(backups.path(&name))
+                    .ok()
+                    .and_then(|contents| parse_tasks_file(&contents).ok())
+                    .map(|tasks_file| tasks_file.tasks.len());
+                Backup {
+                    name,
+                    created_at,
+                    tasks,
+                }
+            })
+            .collect())
+    }
+
+    fn create_backup(&mut self) -> RepoResult<Backup> {
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
+        if !self.file_path.exists() {
+            return Err(RepoError::NothingToBackUp);
+        }
+        let backups: BackupDir = self.backups();
+        let backup: Backup = self.copy_to_backup(&backups, Utc::now())?;
+        if self.backup_policy.keep > 0 {
+            backups
+                .prune(self.backup_policy.keep)
+                .map_err(backup_error)?;
+        }
+        Ok(backup)
+    }
+
+    /// The data file being replaced is backed up first when it is readable,
+    /// so a restore can itself be undone by restoring that backup. The undo
+    /// journal is cleared, as its entries describe the replaced file.
+    fn restore_backup(&mut self, name: &str) -> RepoResult<Backup> {
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
+        let backups: BackupDir = self.backups();
+        let not_found = || RepoError::BackupNotFound {
+            name: name.to_string(),
+        };
+        let created_at: DateTime<Utc> = backups.created_at(name).ok_or_else(not_found)?;
+        let path: PathBuf = backups.path(name);
+        if !path.is_file() {
+            return Err(not_found());
+        }
+        let contents: String = fs::read_to_string(&path).map_err(backup_error)?;
+        let restored: TasksFile =
+            parse_tasks_file(&contents).map_err(|e| RepoError::InvalidBackup {
+                name: name.to_string(),
+                error: e.to_string(),
+            })?;
+        if self.file_path.exists() && self.read_task_file().is_ok() {
+            self.copy_to_backup(&backups, Utc::now())?;
+        }
+        write_atomically(&self.file_path, contents.as_bytes()).map_err(|e| {
+            RepoError::InternalError {
+                error: format!("Writing data. E: {e:?}"),
+            }
+        })?;
+        // The journal describes the store that was just replaced.
+        if self.journal_path().exists() {
+            self.write_journal_file(Journal::default())?;
+        }
+        Ok(Backup {
+            name: name.to_string(),
+            created_at,
+            tasks: Some(restored.tasks.len()),
+        })
+    }
+}
+
+impl JournalRepository for JsonFileTaskRepository {
+    fn load_journal(&self) -> RepoResult<Journal> {
+        let JournalFile { journal, .. } = self.read_shared(&self.journal_path())?;
+        Ok(journal)
+    }
+
+    fn save_journal(&mut self, journal: Journal) -> RepoResult<()> {
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
+        self.write_journal_file(journal)
+    }
+
+    fn update_journal<T>(&mut self, update: impl FnOnce(&mut Journal) -> T) -> RepoResult<T> {
+        let _lock: FileLock = self.lock(LockMode::Exclusive)?;
+        let mut journal: Journal = self.read_journal_file()?;
+        let result: T = update(&mut journal);
+        self.write_journal_file(journal)?;
+        Ok(result)
+    }
+}
+
+/// Reads data file contents written with any schema version this build
+/// supports.
+fn parse_tasks_file(contents: &str) -> RepoResult<TasksFile> {
+    tasks_file_from(serde_json::from_str(contents).map_err(corrupted)?)
+}
+
+fn tasks_file_from(document: Value) -> RepoResult<TasksFile> {
+    serde_json::from_value(migrate(document)?).map_err(corrupted)
+}
+
+/// `tasks.json` is kept as `tasks.v1.backup.json`, `tasks.archive.json` as
+/// `tasks.archive.v1.backup.json`.
+fn schema_backup_path(path: &Path, version: u32) -> PathBuf {
+    path.with_extension(format!("v{version}.backup.json"))
+}
+
+/// Keeps the file as the older build wrote it, once: later reads find
+/// the backup there and leave it alone.
+fn back_up_before_migrating(path: &Path, contents: &str) -> RepoResult<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    write_atomically(path, contents.as_bytes()).map_err(|e| RepoError::InternalError {
+        error: format!("Backing up data before migrating it. E: {e:?}"),
+    })
+}
+
+fn corrupted(e: serde_json::Error) -> RepoError {
+    RepoError::Corrupted {
+        error: e.to_string(),
+    }
+}
+
+fn backup_error(e: std::io::Error) -> RepoError {
+    RepoError::InternalError {
+        error: format!("Reading backups. E: {e:?}"),
+    }
+}
+
+/// The data file; see [`schema`](crate::tasks::adapters::persistence::schema)
+/// for how older versions are read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TasksFile {
+    schema_version: u32,
+    tasks: Vec<Task>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    projects: Vec<Project>,
+}
+
+impl TasksFile {
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+    pub fn tasks(&self) -> &[Task] {
+        &self.tasks
+    }
+    pub fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+}
+
+/// A file stamped with a `schema_version`, read through the migrations for
+/// its kind.
+trait VersionedFile: DeserializeOwned + Default {
+    /// How errors name the file.
+    const NAME: &'static str;
+
+    fn migrate(document: Value) -> RepoResult<Value>;
+
+    fn parse_error(e: serde_json::Error) -> RepoError {
+        RepoError::InternalError {
+            error: format!("Parsing {} from file. E: {e:?}", Self::NAME),
+        }
+    }
+}
+
+/// A file as read, brought up to [`SCHEMA_VERSION`] in memory.
+struct Upgraded<F> {
+    file: F,
+    /// The version and contents of a file written with an older schema,
+    /// when they have not been backed up yet.
+    pending_backup: Option<(u32, String)>,
+}
+
+impl VersionedFile for TasksFile {
+    const NAME: &'static str = "data";
+
+    fn migrate(document: Value) -> RepoResult<Value> {
+        migrate(document)
+    }
+
+    fn parse_error(e: serde_json::Error) -> RepoError {
+        corrupted(e)
+    }
+}
+
+/// Archived tasks (`tasks.archive.json`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ArchiveFile {
+    schema_version: u32,
     tasks: Vec<Task>,
 }
 
+impl VersionedFile for ArchiveFile {
+    const NAME: &'static str = "archive";
+
+    fn migrate(document: Value) -> RepoResult<Value> {
+        migrate_archive(document)
+    }
+}
+
+/// The undo journal (`tasks.journal.json`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct JournalFile {
+    schema_version: u32,
+    #[serde(flatten)]
+    journal: Journal,
+}
+
+impl VersionedFile for JournalFile {
+    const NAME: &'static str = "journal";
+
+    fn migrate(document: Value) -> RepoResult<Value> {
+        migrate_journal(document)
+    }
+}
+
+impl Default for TasksFile {
+    fn default() -> Self {
+        Self::from(Vec::new())
+    }
+}
+
 impl From<Vec<Task>> for TasksFile {
     fn from(value: Vec<Task>) -> Self {
-        Self { tasks: value }
+        Self {
+            schema_version: SCHEMA_VERSION,
+            tasks: value,
+            projects: Vec::new(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::JsonFileTaskRepository;
+    use crate::tasks::adapters::persistence::backups::BackupPolicy;
+    use crate::tasks::adapters::persistence::file_lock::{FileLock, LockMode};
+    use crate::tasks::domain::due_date::DueDate;
+    use crate::tasks::domain::journal::{Journal, JournalEntry, TaskChange};
+    use crate::tasks::domain::priority::Priority;
+    use crate::tasks::domain::project::Project;
+    use crate::tasks::domain::tag::Tag;
     use crate::tasks::domain::task::{Task, TaskStatus};
+    use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+    use crate::tasks::ports::outputs::backup_repository::BackupRepository;
+    use crate::tasks::ports::outputs::errors::RepoError;
+    use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+    use crate::tasks::ports::outputs::project_repository::ProjectRepository;
     use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use chrono::Utc;
     use std::fs;
+    use std::time::Duration;
     use tempfile::tempdir;
 
     fn new_task(title: &str) -> Task {
@@ -223,6 +764,23 @@ mod tests {
         assert!(found.is_some());
     }
 
+    #[test]
+    fn a_temporary_file_left_by_an_interrupted_write_is_ignored() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let mut repo = JsonFileTaskRepository::using(file_path);
+        let first = new_task("first");
+        repo.save(first.clone()).expect("save should succeed");
+        fs::write(temp.path().join(".tasks.json.x1y2z3.tmp"), "{\"tasks\":[")
+            .expect("write should succeed");
+
+        repo.save(new_task("second")).expect("save should succeed");
+
+        let all = repo.list(TaskQuery::All).expect("list should succeed");
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(|task| task.task_id() == first.task_id()));
+    }
+
     #[test]
     fn invalid_json_returns_error() {
         let temp = tempdir().expect("temp dir should be created");
@@ -232,6 +790,395 @@ mod tests {
         let repo = JsonFileTaskRepository::using(file_path);
         let result = repo.list(TaskQuery::All);
 
-        assert!(result.is_err());
+        assert!(matches!(result, Err(RepoError::Corrupted { .. })));
+    }
+
+    #[test]
+    fn priority_is_persisted_and_filterable() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let mut repo = JsonFileTaskRepository::using(file_path.clone());
+
+        let urgent = new_task("fix prod")
+            .set_priority(Priority::Urgent)
+            .expect("priority should be set");
+        let id = urgent.task_id();
+        repo.save(urgent).expect("save should succeed");
+        repo.save(new_task("water plants"))
+            .expect("save should succeed");
+
+        let reader = JsonFileTaskRepository::using(file_path);
+        let urgent_tasks = reader
+            .list(TaskQuery::ByPriority(Priority::Urgent))
+            .expect("list should succeed");
+        assert_eq!(urgent_tasks.len(), 1);
+        assert_eq!(urgent_tasks[0].task_id(), id);
+    }
+
+    #[test]
+    fn tasks_without_priority_default_to_none() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let payload = r#"{"tasks":[{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8","title":"legacy","status":"Todo","created_at":"2024-01-01T00:00:00Z","modified_at":"2024-01-01T00:00:00Z"}]}"#;
+        fs::write(&file_path, payload).expect("legacy payload should be written");
+
+        let repo = JsonFileTaskRepository::using(file_path);
+        let all = repo.list(TaskQuery::All).expect("list should succeed");
+
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].priority(), Priority::None);
+    }
+
+    #[test]
+    fn due_dates_round_trip_as_dates_and_datetimes() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let mut repo = JsonFileTaskRepository::using(file_path.clone());
+        let date = DueDate::parse("2026-04-30").expect("due date should parse");
+        let date_time = DueDate::parse("2026-04-30T09:15").expect("due date should parse");
+
+        let by_date = new_task("by date")
+            .set_due(Some(date))
+            .expect("due date should be set");
+        let by_time = new_task("by time")
+            .set_due(Some(date_time))
+            .expect("due date should be set");
+        repo.save(by_date.clone()).expect("save should succeed");
+        repo.save(by_time.clone()).expect("save should succeed");
+
+        let reader = JsonFileTaskRepository::using(file_path);
+        let found_date = reader
+            .find_by_id(by_date.task_id())
+            .expect("find should succeed");
+        let found_time = reader
+            .find_by_id(by_time.task_id())
+            .expect("find should succeed");
+        assert_eq!(found_date.and_then(|task| task.due()), Some(date));
+        assert_eq!(found_time.and_then(|task| task.due()), Some(date_time));
+    }
+
+    #[test]
+    fn tags_are_persisted_and_filterable() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let mut repo = JsonFileTaskRepository::using(file_path.clone());
+        let backend = Tag::parse("backend").expect("tag should parse");
+
+        let tagged = new_task("fix api")
+            .add_tag(backend.clone())
+            .expect("tag should be added");
+        let tagged = repo.save(tagged).expect("save should succeed");
+        repo.save(new_task("untagged"))
+            .expect("save should succeed");
+
+        let reader = JsonFileTaskRepository::using(file_path);
+        let found = reader
+            .list(TaskQuery::HasTag(backend))
+            .expect("list should succeed");
+        assert_eq!(found, vec![tagged]);
+    }
+
+    #[test]
+    fn projects_are_persisted_alongside_tasks() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let mut repo = JsonFileTaskRepository::using(file_path.clone());
+        let work = Project::new("Work".to_string()).expect("project should be created");
+        repo.save_project(work.clone())
+            .expect("save should succeed");
+        let filed = new_task("write report")
+            .move_to_project(Some(work.project_id()))
+            .expect("task should be moved");
+        let filed = repo.save(filed).expect("save should succeed");
+        repo.save(new_task("loose end"))
+            .expect("save should succeed");
+
+        let reader = JsonFileTaskRepository::using(file_path);
+        let found = reader
+            .find_project_by_name("work")
+            .expect("find should succeed");
+        let in_project = reader
+            .list(TaskQuery::ByProject(Some(work.project_id())))
+            .expect("list should succeed");
+        assert_eq!(found, Some(work));
+        assert_eq!(in_project, vec![filed]);
+    }
+
+    #[test]
+    fn markdown_descriptions_round_trip_unchanged() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let mut repo = JsonFileTaskRepository::using(file_path.clone());
+        let notes = "# Plan \u{1F680}\n\n- [ ] call \"Ana\"\n  - nested `code`  \n\n> quote\ttab \\ backslash";
+        let task = new_task("plan")
+            .set_description(Some(notes.to_string()))
+            .expect("description should be set");
+        let task = repo.save(task).expect("save should succeed");
+
+        let reader = JsonFileTaskRepository::using(file_path);
+        let found = reader
+            .find_by_id(task.task_id())
+            .expect("find should succeed")
+            .expect("task should exist");
+        assert_eq!(found.description(), Some(notes));
+        assert_eq!(found, task);
+    }
+
+    #[test]
+    fn archive_is_kept_in_its_own_file_next_to_the_data_file() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let mut repo = JsonFileTaskRepository::using(file_path.clone());
+        let task = new_task("old").mark_done().expect("done should succeed");
+        repo.archive(vec![task.clone()])
+            .expect("archive should succeed");
+        repo.archive(vec![task.clone()])
+            .expect("archiving again should succeed");
+
+        let reader = JsonFileTaskRepository::using(file_path.clone());
+        assert!(temp.path().join("tasks.archive.json").exists());
+        assert!(!file_path.exists());
+        assert_eq!(
+            reader.list_archived().expect("list should succeed"),
+            vec![task.clone()]
+        );
+        assert!(
+            repo.delete_archived(task.task_id())
+                .expect("delete should succeed")
+        );
+        assert!(
+            reader
+                .list_archived()
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn a_save_based_on_an_outdated_read_is_a_conflict() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let mut first = JsonFileTaskRepository::using(file_path.clone());
+        let mut second = JsonFileTaskRepository::using(file_path);
+        let task = first.save(new_task("deploy")).expect("save should succeed");
+        assert_eq!(task.version(), 1);
+
+        let stale = second
+            .find_by_id(task.task_id())
+            .expect("find should succeed")
+            .expect("task should exist");
+        let renamed = first
+            .save(
+                task.edit_title("deploy v2".to_string())
+                    .expect("rename should succeed"),
+            )
+            .expect("save should succeed");
+        let result = second.save(stale.mark_done().expect("task should complete"));
+
+        assert!(matches!(
+            result,
+            Err(RepoError::Conflict {
+                expected: 1,
+                actual: 2,
+                ..
+            })
+        ));
+        let stored = second
+            .find_by_id(renamed.task_id())
+            .expect("find should succeed")
+            .expect("task should exist");
+        assert_eq!(stored, renamed);
+    }
+
+    #[test]
+    fn an_older_file_is_backed_up_and_upgraded_on_the_next_write() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let old = include_str!("../../../../tests/fixtures/tasks.v1.first.json");
+        fs::write(&file_path, old).expect("write should succeed");
+        let mut repo = JsonFileTaskRepository::using(file_path.clone());
+
+        let tasks = repo.list(TaskQuery::All).expect("list should succeed");
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(
+            fs::read_to_string(&file_path).expect("read should succeed"),
+            old
+        );
+        repo.save(new_task("new")).expect("save should succeed");
+
+        let backup = fs::read_to_string(repo.schema_backup_path(1)).expect("backup should exist");
+        assert_eq!(backup, old);
+        let upgraded = fs::read_to_string(&file_path).expect("read should succeed");
+        assert!(upgraded.starts_with("{\"schema_version\":2,"));
+    }
+
+    #[test]
+    fn reading_an_older_file_backs_it_up_under_the_exclusive_lock() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        fs::write(
+            &file_path,
+            include_str!("../../../../tests/fixtures/tasks.v1.first.json"),
+        )
+        .expect("write should succeed");
+        let repo = JsonFileTaskRepository::using(file_path.clone())
+            .with_lock_timeout(Duration::from_millis(50));
+        let reader = FileLock::acquire(&repo.lock_path(), LockMode::Shared, Duration::ZERO)
+            .expect("lock should be taken");
+
+        let blocked = repo.list(TaskQuery::All);
+
+        assert!(matches!(blocked, Err(RepoError::Locked { .. })));
+        assert!(!repo.schema_backup_path(1).exists());
+        drop(reader);
+        assert_eq!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .len(),
+            2
+        );
+        assert!(repo.schema_backup_path(1).exists());
+        let _reader = FileLock::acquire(&repo.lock_path(), LockMode::Shared, Duration::ZERO)
+            .expect("lock should be taken");
+        assert!(repo.list(TaskQuery::All).is_ok());
+    }
+
+    #[test]
+    fn an_unversioned_archive_and_journal_are_upgraded_on_the_next_write() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let archived = r#"[{"id":"5d4c3b2a-1f0e-4d9c-8b7a-6f5e4d3c2b1a","title":"Pay rent","status":"Done","created_at":"2026-01-05T08:01:00Z","modified_at":"2026-01-06T19:30:00Z"}]"#;
+        fs::write(repo.archive_path(), archived).expect("write should succeed");
+        fs::write(repo.journal_path(), r#"{"undo":[],"redo":[]}"#).expect("write should succeed");
+
+        let listed = repo.list_archived().expect("list should succeed");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].version(), 1);
+        repo.archive(vec![new_task("new")])
+            .expect("archive should succeed");
+        repo.save_journal(Journal::default())
+            .expect("journal should be saved");
+
+        let upgraded = fs::read_to_string(repo.archive_path()).expect("read should succeed");
+        assert!(upgraded.starts_with("{\"schema_version\":2,\"tasks\":["));
+        let journal = fs::read_to_string(repo.journal_path()).expect("read should succeed");
+        assert!(journal.starts_with("{\"schema_version\":2,"));
+        let backup = temp.path().join("tasks.archive.v1.backup.json");
+        assert_eq!(
+            fs::read_to_string(backup).expect("backup should exist"),
+            archived
+        );
+    }
+
+    #[test]
+    fn a_file_from_a_newer_build_is_left_alone() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let newer = r#"{"schema_version":99,"tasks":[],"boards":[]}"#;
+        fs::write(&file_path, newer).expect("write should succeed");
+        let mut repo = JsonFileTaskRepository::using(file_path.clone());
+
+        let result = repo.save(new_task("new"));
+
+        assert!(matches!(
+            result,
+            Err(RepoError::NewerSchema { found: 99, .. })
+        ));
+        assert_eq!(
+            fs::read_to_string(&file_path).expect("read should succeed"),
+            newer
+        );
+    }
+
+    #[test]
+    fn writes_back_up_the_previous_file_once_per_interval() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+
+        for title in ["first", "second", "third"] {
+            repo.save(new_task(title)).expect("save should succeed");
+        }
+
+        let backups = repo.list_backups().expect("list should succeed");
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].tasks, Some(1));
+    }
+
+    #[test]
+    fn only_the_configured_number_of_backups_is_kept() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"))
+            .with_backup_policy(BackupPolicy {
+                keep: 2,
+                min_interval: Duration::ZERO,
+            });
+
+        for title in ["first", "second", "third", "fourth"] {
+            repo.save(new_task(title)).expect("save should succeed");
+        }
+
+        let mut counts: Vec<Option<usize>> = repo
+            .list_backups()
+            .expect("list should succeed")
+            .into_iter()
+            .map(|backup| backup.tasks)
+            .collect();
+        counts.sort();
+        assert_eq!(counts, vec![Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn restoring_a_backup_keeps_a_copy_of_the_file_it_replaces() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        repo.save(new_task("first")).expect("save should succeed");
+        let backup = repo.create_backup().expect("backup should succeed");
+        let second = repo.save(new_task("second")).expect("save should succeed");
+        let mut journal = Journal::default();
+        journal.record(JournalEntry::new(
+            "add".to_string(),
+            Utc::now(),
+            vec![TaskChange::new(None, Some(second))],
+        ));
+        repo.save_journal(journal).expect("journal should be saved");
+
+        let restored = repo
+            .restore_backup(&backup.name)
+            .expect("restore should succeed");
+
+        assert_eq!(restored.tasks, Some(1));
+        assert_eq!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .len(),
+            1
+        );
+        let kept = repo.list_backups().expect("list should succeed");
+        assert!(kept.iter().any(|backup| backup.tasks == Some(2)));
+        let journal = repo.load_journal().expect("journal should load");
+        assert!(!journal.can_undo());
+    }
+
+    #[test]
+    fn an_unreadable_or_unknown_backup_is_not_restored() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        repo.save(new_task("first")).expect("save should succeed");
+        let backup = repo.create_backup().expect("backup should succeed");
+        fs::write(repo.backups_path().join(&backup.name), "{\"tasks\": [")
+            .expect("write should succeed");
+        let before = fs::read_to_string(repo.file_path()).expect("read should succeed");
+
+        let invalid = repo.restore_backup(&backup.name);
+        let unknown = repo.restore_backup("../tasks.json");
+
+        assert!(matches!(invalid, Err(RepoError::InvalidBackup { .. })));
+        assert!(matches!(unknown, Err(RepoError::BackupNotFound { .. })));
+        let listed = repo.list_backups().expect("list should succeed");
+        assert!(!listed[0].is_valid());
+        assert_eq!(
+            fs::read_to_string(repo.file_path()).expect("read should succeed"),
+            before
+        );
     }
 }
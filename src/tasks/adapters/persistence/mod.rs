@@ -1,2 +1,6 @@
+pub mod atomic_file;
+pub mod backups;
+pub mod file_lock;
 pub mod in_memory_task_repository;
 pub mod json_file_task_repository;
+pub mod schema;
@@ -0,0 +1,225 @@
+use crate::tasks::ports::outputs::errors::{RepoError, RepoResult};
+use serde_json::{Map, Value};
+
+/// The version of the data, archive and journal files this build writes.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Files written before the format carried a `schema_version`.
+pub const UNVERSIONED: u32 = 1;
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[n]` upgrades a version `n + 1` document to version `n + 2`.
+const MIGRATIONS: [Migration; (SCHEMA_VERSION - UNVERSIONED) as usize] = [stamp_task_versions];
+
+/// The same steps for the journal, whose tasks are snapshots in entries.
+const JOURNAL_MIGRATIONS: [Migration; (SCHEMA_VERSION - UNVERSIONED) as usize] =
+    [stamp_snapshot_versions];
+
+/// The version a data file was written with; files without one predate
+/// versioning.
+pub fn schema_version(document: &Value) -> RepoResult<u32> {
+    match document.get("schema_version") {
+        None => Ok(UNVERSIONED),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| RepoError::Corrupted {
+                error: format!("invalid schema_version {version}"),
+            }),
+    }
+}
+
+/// Upgrades a data file one version at a time up to [`SCHEMA_VERSION`].
+/// Files from a newer build are refused rather than read with fields
+/// silently dropped.
+pub fn migrate(document: Value) -> RepoResult<Value> {
+    apply(document, &MIGRATIONS)
+}
+
+/// Upgrades an archive file. Version 1 archives were a bare array of tasks;
+/// wrapped in an object they migrate like the data file.
+pub fn migrate_archive(document: Value) -> RepoResult<Value> {
+    match document {
+        Value::Array(tasks) => migrate(Value::Object(Map::from_iter([(
+            "tasks".to_string(),
+            Value::Array(tasks),
+        )]))),
+        document => migrate(document),
+    }
+}
+
+/// Upgrades a journal file.
+pub fn migrate_journal(document: Value) -> RepoResult<Value> {
+    apply(document, &JOURNAL_MIGRATIONS)
+}
+
+fn apply(mut document: Value, migrations: &[Migration]) -> RepoResult<Value> {
+    let found: u32 = schema_version(&document)?;
+    if found > SCHEMA_VERSION {
+        return Err(RepoError::NewerSchema {
+            found,
+            supported: SCHEMA_VERSION,
+        });
+    }
+    let Some(fields) = document.as_object_mut() else {
+        return Err(RepoError::Corrupted {
+            error: "expected a JSON object".to_string(),
+        });
+    };
+    for version in found..SCHEMA_VERSION {
+        migrations[(version - UNVERSIONED) as usize](fields);
+        fields.insert("schema_version".to_string(), Value::from(version + 1));
+    }
+    Ok(document)
+}
+
+/// 1 -> 2: tasks saved before tasks had versions count as saved once.
+fn stamp_task_versions(fields: &mut Map<String, Value>) {
+    let Some(Value::Array(tasks)) = fields.get_mut("tasks") else {
+        return;
+    };
+    tasks
+        .iter_mut()
+        .filter_map(Value::as_object_mut)
+        .for_each(stamp_version);
+}
+
+/// 1 -> 2: snapshots taken before tasks had versions match the tasks the
+/// data file migration stamped.
+fn stamp_snapshot_versions(fields: &mut Map<String, Value>) {
+    let changes = fields
+        .iter_mut()
+        .filter(|(stack, _)| matches!(stack.as_str(), "undo" | "redo"))
+        .filter_map(|(_, entries)| entries.as_array_mut())
+        .flatten()
+        .filter_map(|entry| entry.get_mut("changes"))
+        .filter_map(Value::as_array_mut)
+        .flatten()
+        .filter_map(Value::as_object_mut);
+    for change in changes {
+        change
+            .iter_mut()
+            .filter(|(side, _)| matches!(side.as_str(), "before" | "after"))
+            .filter_map(|(_, task)| task.as_object_mut())
+            .for_each(stamp_version);
+    }
+}
+
+fn stamp_version(task: &mut Map<String, Value>) {
+    if task.get("version").and_then(Value::as_u64).unwrap_or(0) == 0 {
+        task.insert("version".to_string(), Value::from(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SCHEMA_VERSION, migrate, migrate_archive, migrate_journal, schema_version};
+    use crate::tasks::adapters::persistence::json_file_task_repository::TasksFile;
+    use crate::tasks::ports::outputs::errors::RepoError;
+    use serde_json::Value;
+
+    /// Written by the first release: title, status and timestamps only.
+    const V1_FIRST_RELEASE: &str = include_str!("../../../../tests/fixtures/tasks.v1.first.json");
+    /// Written just before versioning: every task field and projects, some
+    /// tasks already carrying a `version`.
+    const V1_LAST_RELEASE: &str = include_str!("../../../../tests/fixtures/tasks.v1.last.json");
+    const V2: &str = include_str!("../../../../tests/fixtures/tasks.v2.json");
+
+    fn parse(fixture: &str) -> Value {
+        serde_json::from_str(fixture).expect("fixture should be valid JSON")
+    }
+
+    fn load(fixture: &str) -> TasksFile {
+        let migrated = migrate(parse(fixture)).expect("migration should succeed");
+        serde_json::from_value(migrated).expect("migrated file should deserialize")
+    }
+
+    fn task_versions(file: &TasksFile) -> Vec<u64> {
+        file.tasks().iter().map(|task| task.version()).collect()
+    }
+
+    #[test]
+    fn the_first_release_format_is_upgraded() {
+        let file = load(V1_FIRST_RELEASE);
+
+        assert_eq!(file.schema_version(), SCHEMA_VERSION);
+        assert_eq!(file.tasks().len(), 2);
+        assert_eq!(file.tasks()[0].title(), "Buy milk");
+        assert_eq!(task_versions(&file), vec![1, 1]);
+    }
+
+    #[test]
+    fn the_last_unversioned_format_is_upgraded_keeping_task_versions() {
+        let file = load(V1_LAST_RELEASE);
+
+        assert_eq!(file.schema_version(), SCHEMA_VERSION);
+        assert_eq!(file.projects().len(), 1);
+        assert_eq!(task_versions(&file), vec![1, 7, 1]);
+        assert!(file.tasks()[1].is_trashed());
+        assert_eq!(file.tasks()[2].parent(), Some(file.tasks()[0].task_id()));
+    }
+
+    #[test]
+    fn the_current_format_is_read_unchanged() {
+        let document = parse(V2);
+
+        let migrated = migrate(document.clone()).expect("migration should succeed");
+
+        assert_eq!(migrated, document);
+        assert_eq!(task_versions(&load(V2)), vec![3]);
+    }
+
+    #[test]
+    fn an_unversioned_archive_is_wrapped_and_upgraded() {
+        let document =
+            parse(r#"[{"title": "Old", "version": 0}, {"title": "Newer", "version": 4}]"#);
+
+        let migrated = migrate_archive(document).expect("migration should succeed");
+
+        assert_eq!(
+            migrated,
+            parse(
+                r#"{"schema_version": 2, "tasks": [{"title": "Old", "version": 1}, {"title": "Newer", "version": 4}]}"#
+            )
+        );
+        assert_eq!(migrate_archive(migrated.clone()).ok(), Some(migrated));
+    }
+
+    #[test]
+    fn an_unversioned_journal_has_its_snapshots_stamped() {
+        let document = parse(
+            r#"{"undo": [{"changes": [{"before": null, "after": {"title": "Old"}}]}],
+                "redo": [{"changes": [{"before": {"version": 3}, "after": null}]}]}"#,
+        );
+
+        let migrated = migrate_journal(document).expect("migration should succeed");
+
+        assert_eq!(migrated["schema_version"], 2);
+        assert_eq!(migrated["undo"][0]["changes"][0]["after"]["version"], 1);
+        assert_eq!(migrated["undo"][0]["changes"][0]["before"], Value::Null);
+        assert_eq!(migrated["redo"][0]["changes"][0]["before"]["version"], 3);
+    }
+
+    #[test]
+    fn a_file_from_a_newer_build_is_refused() {
+        let document = parse(r#"{"schema_version": 99, "tasks": []}"#);
+
+        let result = migrate(document);
+
+        assert!(matches!(
+            result,
+            Err(RepoError::NewerSchema {
+                found: 99,
+                supported: SCHEMA_VERSION
+            })
+        ));
+    }
+
+    #[test]
+    fn a_malformed_schema_version_is_an_error() {
+        let document = parse(r#"{"schema_version": "two", "tasks": []}"#);
+
+        assert!(schema_version(&document).is_err());
+    }
+}
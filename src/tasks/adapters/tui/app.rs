@@ -1,74 +1,233 @@
-use crate::tasks::adapters::tui::errors::TuiResult;
+use crate::tasks::adapters::tui::errors::{TuiError, TuiResult};
+use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::application::journal::{JournalingRepository, run_journaled};
+use crate::tasks::application::subtasks::SubtaskDonePolicy;
 use crate::tasks::application::use_cases::add_task::{
     AddTaskCommand, AddTaskService, AddTaskUseCase,
 };
+use crate::tasks::application::use_cases::change_task_status::{
+    ChangeTaskStatusCommand, ChangeTaskStatusService, ChangeTaskStatusUseCase,
+};
 use crate::tasks::application::use_cases::delete_task::{
     DeleteTaskCommand, DeleteTaskService, DeleteTaskUseCase,
 };
 use crate::tasks::application::use_cases::edit_task::{
-    EditTaskCommand, EditTaskService, EditTaskUseCase,
+    EditFields, EditTaskCommand, EditTaskService, EditTaskUseCase,
+};
+use crate::tasks::application::use_cases::list_projects::{
+    ListProjectsService, ListProjectsUseCase,
 };
 use crate::tasks::application::use_cases::list_tasks::{
-    FilterTask, ListTasksCommand, ListTasksService, ListTasksUseCase,
+    FilterTask, ListTasksCommand, ListTasksService, ListTasksUseCase, SortKey, TaskSort,
 };
+use crate::tasks::application::use_cases::list_trash::{ListTrashService, ListTrashUseCase};
 use crate::tasks::application::use_cases::mark_task_done::{
     MarkTaskDoneCommand, MarkTaskDoneService, MarkTaskDoneUseCase,
 };
-use crate::tasks::application::use_cases::mark_task_todo::{
-    MarkTaskTodoCommand, MarkTaskTodoService, MarkTaskTodoUseCase,
+use crate::tasks::application::use_cases::redo::{RedoService, RedoUseCase};
+use crate::tasks::application::use_cases::restore_task::{
+    RestoreTaskCommand, RestoreTaskService, RestoreTaskUseCase,
 };
+use crate::tasks::application::use_cases::undo::{UndoService, UndoUseCase};
+use crate::tasks::domain::project::{INBOX, Project};
+use crate::tasks::domain::tag::Tag;
 use crate::tasks::domain::task::{Task, TaskStatus};
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::clock::Clock;
+use crate::tasks::ports::outputs::errors::RepoError;
+use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
 use crate::tasks::ports::outputs::task_repository::TaskRepository;
 use std::cmp::min;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum InputMode {
     Normal,
     Adding,
+    AddingSubtask,
     Editing,
+    Blocking,
     ConfirmDelete,
+    /// Typing a filter expression for the task list.
+    Filtering,
+    /// Browsing the trash instead of the task list.
+    Trash,
+    /// A change was refused because the task changed elsewhere; asks
+    /// whether to reload the list.
+    ConfirmReload,
+}
+
+/// Which project the task list is scoped to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectScope {
+    All,
+    Inbox,
+    Project(Project),
+}
+
+impl ProjectScope {
+    pub fn label(&self) -> &str {
+        match self {
+            ProjectScope::All => "All",
+            ProjectScope::Inbox => INBOX,
+            ProjectScope::Project(project) => project.name(),
+        }
+    }
+
+    fn project_name(&self) -> Option<String> {
+        match self {
+            ProjectScope::All => None,
+            scope => Some(scope.label().to_string()),
+        }
+    }
+}
+
+/// Done and total (non-cancelled) direct subtasks of a parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SubtaskProgress {
+    pub done: usize,
+    pub total: usize,
 }
 
-pub struct App<R: TaskRepository> {
+pub struct App<R: TaskRepository + ProjectRepository, C: Clock> {
+    /// Visible tasks in tree order (parents before their subtasks).
     pub tasks: Vec<Task>,
+    /// Tree depth of each entry in `tasks`.
+    pub depths: Vec<usize>,
+    pub progress: HashMap<Uuid, SubtaskProgress>,
+    /// Blocker tasks of each task that has any, whatever their status.
+    pub blockers: HashMap<Uuid, Vec<Task>>,
+    pub collapsed: HashSet<Uuid>,
     pub selected: usize,
     pub input_mode: InputMode,
     pub input_buffer: String,
     pub status_message: Option<String>,
     pub filter: FilterTask,
+    pub tag_filter: Option<Tag>,
+    pub project_scope: ProjectScope,
+    /// Filter expression narrowing the list further, as typed.
+    pub where_filter: Option<String>,
+    pub sort: TaskSort,
+    pub show_details: bool,
+    /// Browsing archived tasks (read-only) instead of the task store.
+    pub show_archived: bool,
+    /// Trashed tasks, most recently deleted first, while browsing the trash.
+    pub trash: Vec<Task>,
+    pub trash_selected: usize,
     pub should_quit: bool,
     repo: R,
+    clock: C,
 }
 
-impl<R: TaskRepository + Clone> App<R> {
-    pub fn new(repository: R) -> TuiResult<Self> {
+impl<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+> App<R, C>
+{
+    pub fn new(repository: R, clock: C) -> TuiResult<Self> {
         let mut app = Self {
             tasks: Vec::default(),
+            depths: Vec::default(),
+            progress: HashMap::default(),
+            blockers: HashMap::default(),
+            collapsed: HashSet::default(),
             selected: usize::default(),
             input_mode: InputMode::Normal,
             input_buffer: String::default(),
             status_message: None,
             filter: FilterTask::All,
+            tag_filter: None,
+            project_scope: ProjectScope::All,
+            where_filter: None,
+            sort: TaskSort::default(),
+            show_details: false,
+            show_archived: false,
+            trash: Vec::default(),
+            trash_selected: usize::default(),
             should_quit: false,
             repo: repository,
+            clock,
         };
         app.refresh_tasks()?;
         Ok(app)
     }
 
     pub fn refresh_tasks(&mut self) -> TuiResult<()> {
-        let list_service: ListTasksService<R> = ListTasksService::new(self.repo.clone());
-        let tasks: Vec<Task> = list_service.execute(ListTasksCommand::new(self.filter))?;
-        let len = tasks.len();
+        let list_service: ListTasksService<R, C> =
+            ListTasksService::new(self.repo.clone(), self.clock.clone());
+        let tags: Vec<String> = self
+            .tag_filter
+            .iter()
+            .map(|tag| tag.as_str().to_string())
+            .collect();
+        let mut command = ListTasksCommand::new(self.filter)
+            .with_all_tags(tags)
+            .with_sort(self.sort);
+        let mut all_command = ListTasksCommand::new(FilterTask::All);
+        if let Some(project) = self.project_scope.project_name() {
+            command = command.with_project(project);
+        }
+        if let Some(filter) = &self.where_filter {
+            command = command.with_where(filter.clone());
+        }
+        if self.show_archived {
+            command = command.with_archived();
+            all_command = all_command.with_archived();
+        }
+        let tasks: Vec<Task> = list_service.execute(command)?;
+        let all_tasks: Vec<Task> = list_service.execute(all_command)?;
+        self.progress = subtask_progress(&all_tasks);
+        self.blockers = blocker_tasks(&all_tasks);
+        let (tasks, depths) = tree_order(tasks, &self.collapsed).into_iter().unzip();
         self.tasks = tasks;
-        self.selected = min(self.selected, len.saturating_sub(1));
+        self.depths = depths;
+        self.selected = min(self.selected, self.tasks.len().saturating_sub(1));
         Ok(())
     }
 
+    /// How many of the task's blockers are still open.
+    pub fn open_blocker_count(&self, task: &Task) -> usize {
+        self.blockers.get(&task.task_id()).map_or(0, |blockers| {
+            blockers
+                .iter()
+                .filter(|blocker| blocker.status().is_open())
+                .count()
+        })
+    }
+
+    pub fn has_subtasks(&self, task: &Task) -> bool {
+        self.progress.contains_key(&task.task_id())
+    }
+
+    pub fn collapse_selected(&mut self) -> TuiResult<()> {
+        if let Some(task) = self.tasks.get(self.selected)
+            && self.has_subtasks(task)
+        {
+            self.collapsed.insert(task.task_id());
+        }
+        self.refresh_tasks()
+    }
+
+    pub fn expand_selected(&mut self) -> TuiResult<()> {
+        if let Some(task) = self.tasks.get(self.selected) {
+            self.collapsed.remove(&task.task_id());
+        }
+        self.refresh_tasks()
+    }
+
     pub fn add_task(&mut self) -> TuiResult<()> {
         self.status_message = None;
-        let mut add_task_service: AddTaskService<R> = AddTaskService::new(self.repo.clone());
-        match add_task_service.execute(AddTaskCommand::new(self.input_buffer.clone())) {
+        let mut command = AddTaskCommand::new(self.input_buffer.clone());
+        if self.input_mode == InputMode::AddingSubtask {
+            let parent = self.tasks[self.selected].task_id();
+            self.collapsed.remove(&parent);
+            command = command.with_parent(parent);
+        } else if let Some(project) = self.project_scope.project_name() {
+            command = command.with_project(project);
+        }
+        match self.journaled("add", |repo| AddTaskService::new(repo).execute(command)) {
             Ok(task) => {
                 self.status_message = Some(format!("Task added: {}", task.title()));
             }
@@ -81,51 +240,166 @@ impl<R: TaskRepository + Clone> App<R> {
 
     pub fn delete_task(&mut self) -> TuiResult<()> {
         self.status_message = None;
-        let title = self.tasks[self.selected].title().to_string();
-        let mut delete_task_service: DeleteTaskService<R> =
-            DeleteTaskService::new(self.repo.clone());
-        let task_id = self.tasks[self.selected].task_id();
-        match delete_task_service.execute(DeleteTaskCommand::new(task_id)) {
+        self.input_mode = InputMode::Normal;
+        let task = &self.tasks[self.selected];
+        let title = task.title().to_string();
+        let command = DeleteTaskCommand::new(task.task_id()).with_expected_version(task.version());
+        let clock = self.clock.clone();
+        match self.journaled("delete", |repo| {
+            DeleteTaskService::new(repo, clock).execute(command)
+        }) {
             Ok(true) => {
-                self.status_message = Some(format!("Deleted: {title}"));
+                self.status_message = Some(format!("Moved to trash: {title}"));
             }
             Ok(false) => {
                 self.status_message = Some("Task not found".to_string());
             }
-            Err(e) => self.status_message = Some(format!("Error: {e}")),
+            Err(e) => self.report_error(e),
         }
-        self.input_mode = InputMode::Normal;
-        self.refresh_tasks()
+        self.refresh_unless_conflicted()
     }
 
-    fn mark_done(&mut self) -> TuiResult<()> {
+    /// Marks the selected task and all of its open subtasks done.
+    pub fn complete_with_subtasks(&mut self) -> TuiResult<()> {
+        let task = &self.tasks[self.selected];
+        self.mark_done(
+            MarkTaskDoneCommand::new(task.task_id())
+                .with_subtask_policy(SubtaskDonePolicy::Cascade)
+                .with_expected_version(task.version()),
+        )
+    }
+
+    fn mark_done(&mut self, command: MarkTaskDoneCommand) -> TuiResult<()> {
         self.status_message = None;
-        let mut mark_done_service: MarkTaskDoneService<R> =
-            MarkTaskDoneService::new(self.repo.clone());
-        let task_id = self.tasks[self.selected].task_id();
-        match mark_done_service.execute(MarkTaskDoneCommand::new(task_id)) {
-            Ok(task) => {
-                self.status_message = Some(format!("Done: {}", task.title()));
+        let clock = self.clock.clone();
+        match self.journaled("done", |repo| {
+            MarkTaskDoneService::new(repo, clock).execute(command)
+        }) {
+            Ok(completed) => {
+                let mut message = format!("done: {}", completed.task.title());
+                if let Some(due) = completed.next_occurrence.and_then(|next| next.due()) {
+                    message.push_str(&format!(" (next due {due})"));
+                }
+                self.status_message = Some(message);
             }
-            Err(e) => self.status_message = Some(format!("Error: {e}")),
+            Err(e) => self.report_error(e),
         }
-        self.refresh_tasks()
+        self.refresh_unless_conflicted()
     }
 
-    fn mark_todo(&mut self) -> TuiResult<()> {
+    fn change_status(&mut self, command: ChangeTaskStatusCommand) -> TuiResult<()> {
         self.status_message = None;
-        let mut mark_todo_service: MarkTaskTodoService<R> =
-            MarkTaskTodoService::new(self.repo.clone());
-        let task_id = self.tasks[self.selected].task_id();
-        match mark_todo_service.execute(MarkTaskTodoCommand::new(task_id)) {
+        match self.journaled("status", |repo| {
+            ChangeTaskStatusService::new(repo).execute(command)
+        }) {
             Ok(task) => {
-                self.status_message = Some(format!("Todo: {}", task.title()));
+                self.status_message = Some(format!("{}: {}", task.status(), task.title()));
             }
-            Err(e) => self.status_message = Some(format!("Error: {e}")),
+            Err(e) => self.report_error(e),
+        }
+        self.refresh_unless_conflicted()
+    }
+
+    /// Shows why a change failed. A conflict leaves the list as it was and
+    /// asks whether to reload it, so the user sees what they acted on.
+    fn report_error(&mut self, error: ApplicationError) {
+        if let ApplicationError::Repository(RepoError::Conflict { .. }) = error {
+            self.input_mode = InputMode::ConfirmReload;
+        }
+        self.status_message = Some(format!("Error: {error}"));
+    }
+
+    fn refresh_unless_conflicted(&mut self) -> TuiResult<()> {
+        match self.input_mode {
+            InputMode::ConfirmReload => Ok(()),
+            _ => self.refresh_tasks(),
+        }
+    }
+
+    /// Answers the reload prompt shown after a conflict.
+    pub fn confirm_reload(&mut self, reload: bool) -> TuiResult<()> {
+        self.input_mode = InputMode::Normal;
+        if !reload {
+            return Ok(());
         }
+        self.status_message = Some("Reloaded".to_string());
         self.refresh_tasks()
     }
 
+    /// Runs a mutating use case so that its changes can be undone.
+    fn journaled<T>(
+        &self,
+        label: &str,
+        run: impl FnOnce(JournalingRepository<R>) -> ApplicationResult<T>,
+    ) -> ApplicationResult<T> {
+        run_journaled(self.repo.clone(), label, self.clock.now(), run)
+    }
+
+    pub fn undo(&mut self) -> TuiResult<()> {
+        let mut undo_service: UndoService<R> = UndoService::new(self.repo.clone());
+        self.status_message = Some(match undo_service.execute() {
+            Ok(entry) => format!("Undone: {}", entry.summary()),
+            Err(e) => format!("Error: {e}"),
+        });
+        self.refresh_tasks()
+    }
+
+    pub fn redo(&mut self) -> TuiResult<()> {
+        let mut redo_service: RedoService<R> = RedoService::new(self.repo.clone());
+        self.status_message = Some(match redo_service.execute() {
+            Ok(entry) => format!("Redone: {}", entry.summary()),
+            Err(e) => format!("Error: {e}"),
+        });
+        self.refresh_tasks()
+    }
+
+    pub fn open_trash(&mut self) -> TuiResult<()> {
+        self.trash_selected = 0;
+        self.input_mode = InputMode::Trash;
+        self.refresh_trash()
+    }
+
+    pub fn close_trash(&mut self) -> TuiResult<()> {
+        self.trash = Vec::default();
+        self.input_mode = InputMode::Normal;
+        self.refresh_tasks()
+    }
+
+    fn refresh_trash(&mut self) -> TuiResult<()> {
+        let list_service: ListTrashService<R> = ListTrashService::new(self.repo.clone());
+        self.trash = list_service.execute()?;
+        self.trash_selected = min(self.trash_selected, self.trash.len().saturating_sub(1));
+        Ok(())
+    }
+
+    /// Takes the selected trashed task, and the subtasks deleted with it,
+    /// back to the task list.
+    pub fn restore_task(&mut self) -> TuiResult<()> {
+        let Some(task) = self.trash.get(self.trash_selected) else {
+            return Ok(());
+        };
+        let task_id = task.task_id();
+        match self.journaled("restore", |repo| {
+            RestoreTaskService::new(repo).execute(RestoreTaskCommand::new(task_id))
+        }) {
+            Ok(task) => self.status_message = Some(format!("Restored: {}", task.title())),
+            Err(e) => self.status_message = Some(format!("Error: {e}")),
+        }
+        self.refresh_trash()
+    }
+
+    /// Row to highlight in whichever list is on screen.
+    pub fn cursor(&self) -> usize {
+        match self.input_mode {
+            InputMode::Trash => self.trash_selected,
+            _ => self.selected,
+        }
+    }
+
+    pub fn is_overdue(&self, task: &Task) -> bool {
+        task.is_overdue(self.clock.now())
+    }
+
     pub fn clear_status(&mut self) {
         self.status_message = None;
     }
@@ -138,36 +412,198 @@ impl<R: TaskRepository + Clone> App<R> {
 
     pub fn edit_task(&mut self) -> TuiResult<()> {
         self.status_message = None;
-        let task_id = self.tasks[self.selected].task_id();
-        let mut edit_service: EditTaskService<R> = EditTaskService::new(self.repo.clone());
-        match edit_service.execute(EditTaskCommand::new(task_id, self.input_buffer.clone())) {
+        self.input_mode = InputMode::Normal;
+        let task = &self.tasks[self.selected];
+        let fields = EditFields::new().with_title(std::mem::take(&mut self.input_buffer));
+        let command =
+            EditTaskCommand::new(task.task_id(), fields).with_expected_version(task.version());
+        match self.journaled("edit", |repo| EditTaskService::new(repo).execute(command)) {
             Ok(task) => {
                 self.status_message = Some(format!("Edited: {}", task.title()));
             }
-            Err(e) => self.status_message = Some(format!("Error: {e}")),
+            Err(e) => self.report_error(e),
         }
-        self.input_buffer = String::default();
-        self.input_mode = InputMode::Normal;
-        self.refresh_tasks()
+        self.refresh_unless_conflicted()
+    }
+
+    pub fn cycle_priority(&mut self) -> TuiResult<()> {
+        self.status_message = None;
+        let task = &self.tasks[self.selected];
+        let fields = EditFields::new().with_priority(task.priority().next());
+        let command =
+            EditTaskCommand::new(task.task_id(), fields).with_expected_version(task.version());
+        match self.journaled("edit", |repo| EditTaskService::new(repo).execute(command)) {
+            Ok(task) => {
+                self.status_message =
+                    Some(format!("Priority {}: {}", task.priority(), task.title()));
+            }
+            Err(e) => self.report_error(e),
+        }
+        self.refresh_unless_conflicted()
     }
 
-    pub fn cycle_todo_done(&mut self) -> TuiResult<()> {
-        let task_status = self.tasks[self.selected].status();
-        match task_status {
-            TaskStatus::Todo => self.mark_done(),
-            TaskStatus::Done => self.mark_todo(),
+    /// Moves the selected task along todo -> in progress -> done -> todo;
+    /// blocked tasks are resumed and cancelled ones reopened.
+    pub fn cycle_status(&mut self) -> TuiResult<()> {
+        let task = &self.tasks[self.selected];
+        let next = match task.status() {
+            TaskStatus::Todo | TaskStatus::Blocked => TaskStatus::InProgress,
+            TaskStatus::InProgress => TaskStatus::Done,
+            TaskStatus::Done | TaskStatus::Cancelled => TaskStatus::Todo,
+        };
+        if next == TaskStatus::Done {
+            // Completion goes through MarkTaskDone so recurring tasks spawn.
+            return self.mark_done(
+                MarkTaskDoneCommand::new(task.task_id()).with_expected_version(task.version()),
+            );
         }
+        self.change_status(
+            ChangeTaskStatusCommand::new(task.task_id(), next)
+                .with_expected_version(task.version()),
+        )
+    }
+
+    pub fn start_blocking(&mut self) {
+        self.input_buffer = self.tasks[self.selected]
+            .blocked_reason()
+            .unwrap_or_default()
+            .to_string();
+        self.input_mode = InputMode::Blocking;
+    }
+
+    pub fn block_task(&mut self) -> TuiResult<()> {
+        let task = &self.tasks[self.selected];
+        let command = ChangeTaskStatusCommand::new(task.task_id(), TaskStatus::Blocked)
+            .with_reason(std::mem::take(&mut self.input_buffer))
+            .with_expected_version(task.version());
+        self.input_mode = InputMode::Normal;
+        self.change_status(command)
+    }
+
+    pub fn cancel_task(&mut self) -> TuiResult<()> {
+        let task = &self.tasks[self.selected];
+        self.change_status(
+            ChangeTaskStatusCommand::new(task.task_id(), TaskStatus::Cancelled)
+                .with_expected_version(task.version()),
+        )
     }
 
     pub fn cycle_filter(&mut self) -> TuiResult<()> {
-        match self.filter {
-            FilterTask::All => self.filter = FilterTask::Done,
-            FilterTask::Done => self.filter = FilterTask::Todo,
-            FilterTask::Todo => self.filter = FilterTask::All,
+        self.filter = match self.filter {
+            FilterTask::All => FilterTask::Todo,
+            FilterTask::Todo => FilterTask::InProgress,
+            FilterTask::InProgress => FilterTask::Blocked,
+            FilterTask::Blocked => FilterTask::Done,
+            FilterTask::Done => FilterTask::Cancelled,
+            FilterTask::Cancelled => FilterTask::All,
+        };
+        self.refresh_tasks()
+    }
+
+    /// Filters by the selected task's tags one after another, then clears the
+    /// tag filter once they are exhausted.
+    pub fn cycle_tag_filter(&mut self) -> TuiResult<()> {
+        let next_tag: Option<Tag> = self.tasks.get(self.selected).and_then(|task| {
+            task.tags()
+                .iter()
+                .find(|tag| {
+                    self.tag_filter
+                        .as_ref()
+                        .is_none_or(|current| *tag > current)
+                })
+                .cloned()
+        });
+        self.tag_filter = next_tag;
+        self.refresh_tasks()
+    }
+
+    /// Switches between all tasks, the inbox and each project in turn; new
+    /// tasks are added to the project in scope.
+    pub fn cycle_project(&mut self) -> TuiResult<()> {
+        let list_service: ListProjectsService<R> = ListProjectsService::new(self.repo.clone());
+        let scopes: Vec<ProjectScope> = std::iter::once(ProjectScope::All)
+            .chain(
+                list_service
+                    .execute()?
+                    .into_iter()
+                    .map(|summary| match summary.project {
+                        None => ProjectScope::Inbox,
+                        Some(project) => ProjectScope::Project(project),
+                    }),
+            )
+            .collect();
+        let current = scopes
+            .iter()
+            .position(|scope| *scope == self.project_scope)
+            .unwrap_or(0);
+        self.project_scope = scopes[(current + 1) % scopes.len()].clone();
+        self.selected = 0;
+        self.refresh_tasks()
+    }
+
+    pub fn start_filtering(&mut self) {
+        self.input_buffer = self.where_filter.clone().unwrap_or_default();
+        self.input_mode = InputMode::Filtering;
+    }
+
+    /// Applies the typed filter expression, or clears the filter when the
+    /// prompt is empty. An invalid expression keeps the prompt open with the
+    /// error in the status line.
+    pub fn apply_filter(&mut self) -> TuiResult<()> {
+        let input: String = self.input_buffer.trim().to_string();
+        let previous: Option<String> = std::mem::replace(
+            &mut self.where_filter,
+            Some(input).filter(|input| !input.is_empty()),
+        );
+        self.selected = 0;
+        match self.refresh_tasks() {
+            Ok(()) => {
+                self.status_message = None;
+                self.input_buffer = String::new();
+                self.input_mode = InputMode::Normal;
+                Ok(())
+            }
+            Err(TuiError::Application(e)) => {
+                self.status_message = Some(format!("Error: {e}"));
+                self.where_filter = previous;
+                self.refresh_tasks()
+            }
+            Err(e) => Err(e),
         }
+    }
+
+    /// Sorts by the next key, keeping the direction.
+    pub fn cycle_sort(&mut self) -> TuiResult<()> {
+        let current = SortKey::ALL
+            .iter()
+            .position(|key| *key == self.sort.key)
+            .unwrap_or(0);
+        self.sort = TaskSort {
+            key: SortKey::ALL[(current + 1) % SortKey::ALL.len()],
+            ..self.sort
+        };
+        self.refresh_tasks()
+    }
+
+    pub fn reverse_sort(&mut self) -> TuiResult<()> {
+        self.sort = self.sort.reversed();
+        self.refresh_tasks()
+    }
+
+    pub fn toggle_archived(&mut self) -> TuiResult<()> {
+        self.show_archived = !self.show_archived;
+        self.selected = 0;
         self.refresh_tasks()
     }
 
+    pub fn toggle_details(&mut self) {
+        self.show_details = !self.show_details;
+    }
+
+    pub fn selected_task(&self) -> Option<&Task> {
+        self.tasks.get(self.selected)
+    }
+
     pub fn select_next(&mut self) -> TuiResult<()> {
         if self.selected != self.tasks.len().saturating_sub(1) {
             self.selected += 1;
@@ -181,4 +617,372 @@ impl<R: TaskRepository + Clone> App<R> {
         }
         Ok(())
     }
+
+    pub fn select_next_trashed(&mut self) {
+        if self.trash_selected != self.trash.len().saturating_sub(1) {
+            self.trash_selected += 1;
+        }
+    }
+
+    pub fn select_previous_trashed(&mut self) {
+        self.trash_selected = self.trash_selected.saturating_sub(1);
+    }
+}
+
+fn subtask_progress(tasks: &[Task]) -> HashMap<Uuid, SubtaskProgress> {
+    let mut progress: HashMap<Uuid, SubtaskProgress> = HashMap::new();
+    for task in tasks {
+        if let Some(parent) = task.parent() {
+            let entry = progress.entry(parent).or_default();
+            match task.status() {
+                TaskStatus::Cancelled => {}
+                TaskStatus::Done => {
+                    entry.done += 1;
+                    entry.total += 1;
+                }
+                _ => entry.total += 1,
+            }
+        }
+    }
+    progress
+}
+
+fn blocker_tasks(tasks: &[Task]) -> HashMap<Uuid, Vec<Task>> {
+    let by_id: HashMap<Uuid, &Task> = tasks.iter().map(|task| (task.task_id(), task)).collect();
+    tasks
+        .iter()
+        .filter(|task| !task.blocked_by().is_empty())
+        .map(|task| {
+            let blockers: Vec<Task> = task
+                .blocked_by()
+                .iter()
+                .filter_map(|id| by_id.get(id).map(|blocker| (*blocker).clone()))
+                .collect();
+            (task.task_id(), blockers)
+        })
+        .collect()
+}
+
+/// Orders tasks depth-first with each subtask under its parent. Tasks whose
+/// parent is not in the list (filtered out) are shown at the top level.
+fn tree_order(tasks: Vec<Task>, collapsed: &HashSet<Uuid>) -> Vec<(Task, usize)> {
+    let visible: HashSet<Uuid> = tasks.iter().map(Task::task_id).collect();
+    let mut children: HashMap<Uuid, Vec<Task>> = HashMap::new();
+    let mut roots: Vec<Task> = Vec::new();
+    for task in tasks {
+        match task.parent().filter(|parent| visible.contains(parent)) {
+            Some(parent) => children.entry(parent).or_default().push(task),
+            None => roots.push(task),
+        }
+    }
+
+    let mut ordered: Vec<(Task, usize)> = Vec::new();
+    let mut pending: Vec<(Task, usize)> = roots.into_iter().rev().map(|task| (task, 0)).collect();
+    while let Some((task, depth)) = pending.pop() {
+        if !collapsed.contains(&task.task_id())
+            && let Some(subtasks) = children.remove(&task.task_id())
+        {
+            pending.extend(subtasks.into_iter().rev().map(|task| (task, depth + 1)));
+        }
+        ordered.push((task, depth));
+    }
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{App, InputMode, ProjectScope, SubtaskProgress};
+    use crate::tasks::adapters::clock::fixed_clock::FixedClock;
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::adapters::persistence::json_file_task_repository::JsonFileTaskRepository;
+    use crate::tasks::application::use_cases::list_tasks::SortKey;
+    use crate::tasks::domain::due_date::DueDate;
+    use crate::tasks::domain::project::Project;
+    use crate::tasks::domain::tag::Tag;
+    use crate::tasks::domain::task::{Task, TaskStatus};
+    use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+    use crate::tasks::ports::outputs::task_repository::TaskRepository;
+    use chrono::{TimeZone, Utc};
+    use tempfile::tempdir;
+
+    fn due_task(title: &str, due: &str) -> Task {
+        Task::new(title.to_string())
+            .and_then(|task| task.set_due(Some(DueDate::parse(due)?)))
+            .expect("task should be created")
+    }
+
+    #[test]
+    fn overdue_is_computed_against_the_injected_clock() {
+        let mut repo = InMemoryTaskRepository::new();
+        let late = due_task("late", "2026-03-20");
+        let on_time = due_task("on time", "2026-03-21");
+        let done_late = due_task("done late", "2026-03-01")
+            .mark_done()
+            .expect("status transition should succeed");
+        repo.save(late.clone()).expect("save should succeed");
+        repo.save(on_time.clone()).expect("save should succeed");
+        repo.save(done_late.clone()).expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+
+        let app = App::new(repo, clock).expect("app should start");
+
+        assert!(app.is_overdue(&late));
+        assert!(!app.is_overdue(&on_time));
+        assert!(!app.is_overdue(&done_late));
+    }
+
+    #[test]
+    fn tag_filter_cycles_through_selected_task_tags() {
+        let mut repo = InMemoryTaskRepository::new();
+        let task = Task::new("hotfix".to_string())
+            .and_then(|task| task.add_tag(Tag::parse("backend")?))
+            .and_then(|task| task.add_tag(Tag::parse("urgent")?))
+            .expect("task should be created");
+        repo.save(task).expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut app = App::new(repo, clock).expect("app should start");
+
+        app.cycle_tag_filter().expect("filter should apply");
+        assert_eq!(app.tag_filter, Some(Tag::parse("backend").unwrap()));
+        app.cycle_tag_filter().expect("filter should apply");
+        assert_eq!(app.tag_filter, Some(Tag::parse("urgent").unwrap()));
+        app.cycle_tag_filter().expect("filter should apply");
+        assert_eq!(app.tag_filter, None);
+        assert_eq!(app.tasks.len(), 1);
+    }
+
+    #[test]
+    fn project_switcher_scopes_the_list_and_new_tasks() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let work = Project::new("work".to_string()).expect("project should be created");
+        repo.save_project(work.clone())
+            .expect("save should succeed");
+        repo.save(Task::new("loose end".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut app = App::new(repo, clock).expect("app should start");
+
+        app.cycle_project().expect("switch should succeed");
+        assert_eq!(app.project_scope, ProjectScope::Inbox);
+        assert_eq!(app.tasks.len(), 1);
+
+        app.cycle_project().expect("switch should succeed");
+        assert_eq!(app.project_scope, ProjectScope::Project(work.clone()));
+        assert!(app.tasks.is_empty());
+        app.input_buffer = "write report".to_string();
+        app.add_task().expect("add should succeed");
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(app.tasks[0].project(), Some(work.project_id()));
+
+        app.cycle_project().expect("switch should succeed");
+        assert_eq!(app.project_scope, ProjectScope::All);
+        assert_eq!(app.tasks.len(), 2);
+    }
+
+    #[test]
+    fn status_key_cycles_todo_in_progress_done() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        repo.save(Task::new("deploy".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut app = App::new(repo, clock).expect("app should start");
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            app.cycle_status().expect("status should change");
+            seen.push(app.tasks[0].status());
+        }
+
+        assert_eq!(
+            seen,
+            [TaskStatus::InProgress, TaskStatus::Done, TaskStatus::Todo]
+        );
+    }
+
+    #[test]
+    fn blocking_from_the_prompt_keeps_the_reason() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        repo.save(Task::new("deploy".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut app = App::new(repo, clock).expect("app should start");
+
+        app.start_blocking();
+        app.input_buffer.push_str("waiting on QA");
+        app.block_task().expect("block should succeed");
+
+        assert_eq!(app.tasks[0].status(), TaskStatus::Blocked);
+        assert_eq!(app.tasks[0].blocked_reason(), Some("waiting on QA"));
+    }
+
+    #[test]
+    fn subtasks_are_indented_under_their_parent_and_collapsible() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let parent = Task::new("release".to_string()).expect("task should be created");
+        let done_child = Task::new("changelog".to_string())
+            .and_then(|task| task.set_parent(Some(parent.task_id())))
+            .and_then(Task::mark_done)
+            .expect("task should be created");
+        let open_child = Task::new("tag".to_string())
+            .and_then(|task| task.set_parent(Some(parent.task_id())))
+            .expect("task should be created");
+        for task in [&open_child, &parent, &done_child] {
+            repo.save(task.clone()).expect("save should succeed");
+        }
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut app = App::new(repo, clock).expect("app should start");
+
+        assert_eq!(app.tasks[0].task_id(), parent.task_id());
+        assert_eq!(app.depths, [0, 1, 1]);
+        assert_eq!(
+            app.progress[&parent.task_id()],
+            SubtaskProgress { done: 1, total: 2 }
+        );
+
+        app.selected = 0;
+        app.collapse_selected().expect("collapse should succeed");
+        assert_eq!(app.tasks.len(), 1);
+        app.expand_selected().expect("expand should succeed");
+        assert_eq!(app.tasks.len(), 3);
+    }
+
+    #[test]
+    fn open_blockers_are_counted_per_task() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let design = Task::new("design".to_string()).expect("task should be created");
+        let research = Task::new("research".to_string())
+            .and_then(Task::mark_done)
+            .expect("task should be created");
+        let build = Task::new("build".to_string())
+            .and_then(|task| task.add_blocker(design.task_id()))
+            .and_then(|task| task.add_blocker(research.task_id()))
+            .expect("task should be created");
+        for task in [&design, &research, &build] {
+            repo.save(task.clone()).expect("save should succeed");
+        }
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let app = App::new(repo, clock).expect("app should start");
+
+        assert_eq!(app.blockers[&build.task_id()].len(), 2);
+        assert_eq!(app.open_blocker_count(&build), 1);
+        assert_eq!(app.open_blocker_count(&design), 0);
+    }
+
+    #[test]
+    fn deleting_can_be_undone_and_redone() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        repo.save(Task::new("deploy".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut app = App::new(repo, clock).expect("app should start");
+
+        app.delete_task().expect("delete should succeed");
+        assert!(app.tasks.is_empty());
+
+        app.undo().expect("undo should succeed");
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Undone: delete \"deploy\"")
+        );
+
+        app.redo().expect("redo should succeed");
+        assert!(app.tasks.is_empty());
+    }
+
+    #[test]
+    fn filter_prompt_narrows_the_list_and_keeps_invalid_input() {
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(Task::new("deploy api".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        repo.save(Task::new("write docs".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut app = App::new(repo, clock).expect("app should start");
+
+        app.start_filtering();
+        app.input_buffer.push_str("title~deploy");
+        app.apply_filter().expect("filter should apply");
+        assert!(app.input_mode == InputMode::Normal);
+        assert_eq!(app.tasks.len(), 1);
+
+        app.start_filtering();
+        assert_eq!(app.input_buffer, "title~deploy");
+        app.input_buffer = "title~".to_string();
+        app.apply_filter()
+            .expect("invalid filter should not fail the app");
+        assert!(app.input_mode == InputMode::Filtering);
+        assert_eq!(app.where_filter.as_deref(), Some("title~deploy"));
+        assert!(
+            app.status_message
+                .as_deref()
+                .is_some_and(|message| message.contains("column 7"))
+        );
+
+        app.input_buffer = String::new();
+        app.apply_filter().expect("clearing should succeed");
+        assert_eq!(app.where_filter, None);
+        assert_eq!(app.tasks.len(), 2);
+    }
+
+    #[test]
+    fn sort_key_cycles_and_reverses_the_list() {
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(Task::new("beta".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        repo.save(Task::new("alpha".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut app = App::new(repo, clock).expect("app should start");
+        let titles = |app: &App<InMemoryTaskRepository, FixedClock>| {
+            app.tasks
+                .iter()
+                .map(|task| task.title().to_string())
+                .collect::<Vec<String>>()
+        };
+
+        app.cycle_sort().expect("sort should apply");
+        app.cycle_sort().expect("sort should apply");
+        assert_eq!(app.sort.key, SortKey::Title);
+        assert_eq!(titles(&app), ["alpha", "beta"]);
+
+        app.reverse_sort().expect("sort should apply");
+        assert_eq!(titles(&app), ["beta", "alpha"]);
+    }
+
+    #[test]
+    fn a_conflicting_change_offers_to_reload() {
+        let temp = tempdir().expect("temp dir should be created");
+        let file_path = temp.path().join("tasks.json");
+        let mut repo = JsonFileTaskRepository::using(file_path.clone());
+        let task = repo
+            .save(Task::new("deploy".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut app = App::new(repo, clock).expect("app should start");
+        // Another process renames the task after the list was loaded.
+        JsonFileTaskRepository::using(file_path)
+            .save(
+                task.edit_title("deploy v2".to_string())
+                    .expect("rename should succeed"),
+            )
+            .expect("save should succeed");
+
+        app.cycle_status().expect("status change should be handled");
+        assert!(app.input_mode == InputMode::ConfirmReload);
+        assert_eq!(app.tasks[0].title(), "deploy");
+        assert_eq!(app.tasks[0].status(), TaskStatus::Todo);
+
+        app.confirm_reload(true).expect("reload should succeed");
+        assert!(app.input_mode == InputMode::Normal);
+        assert_eq!(app.tasks[0].title(), "deploy v2");
+        app.cycle_status().expect("status change should succeed");
+        assert_eq!(app.tasks[0].status(), TaskStatus::InProgress);
+    }
 }
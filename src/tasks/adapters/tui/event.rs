@@ -1,51 +1,94 @@
 use crate::tasks::adapters::tui::app::{App, InputMode};
 use crate::tasks::adapters::tui::errors::TuiResult;
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::clock::Clock;
+use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
 use crate::tasks::ports::outputs::task_repository::TaskRepository;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
 use std::time::Duration;
-pub fn handle_events<R: TaskRepository + Clone>(app: &mut App<R>) -> TuiResult<()> {
+pub fn handle_events<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &mut App<R, C>,
+) -> TuiResult<()> {
     // Poll con timeout de 16ms (~60fps)
-    if event::poll(Duration::from_millis(16))? {
-        if let Event::Key(key) = event::read()?
-            && key.kind == KeyEventKind::Press
-        {
-            match app.input_mode {
-                InputMode::Normal => handle_normal_mode(app, key)?,
-                InputMode::Adding => handle_adding_mode(app, key)?,
-                InputMode::Editing => handle_editing_mode(app, key)?,
-                InputMode::ConfirmDelete => handle_confirm_delete_mode(app, key)?,
-            }
+    if event::poll(Duration::from_millis(16))?
+        && let Event::Key(key) = event::read()?
+        && key.kind == KeyEventKind::Press
+    {
+        match app.input_mode {
+            InputMode::Normal if app.show_archived => handle_archive_mode(app, key)?,
+            InputMode::Normal => handle_normal_mode(app, key)?,
+            InputMode::Adding | InputMode::AddingSubtask => handle_adding_mode(app, key)?,
+            InputMode::Editing => handle_editing_mode(app, key)?,
+            InputMode::Blocking => handle_blocking_mode(app, key)?,
+            InputMode::ConfirmDelete => handle_confirm_delete_mode(app, key)?,
+            InputMode::Filtering => handle_filtering_mode(app, key)?,
+            InputMode::Trash => handle_trash_mode(app, key)?,
+            InputMode::ConfirmReload => handle_confirm_reload_mode(app, key)?,
         }
     }
     Ok(())
 }
 
-fn handle_normal_mode<R: TaskRepository + Clone>(app: &mut App<R>, key: KeyEvent) -> TuiResult<()> {
+fn handle_normal_mode<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &mut App<R, C>,
+    key: KeyEvent,
+) -> TuiResult<()> {
     match key.code {
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => app.redo()?,
         KeyCode::Char('q') => app.should_quit = true,
+        KeyCode::Char('u') => app.undo()?,
         KeyCode::Char('a') => {
             app.clear_status();
             app.input_mode = InputMode::Adding;
         }
-        KeyCode::Char('e') => {
-            if !app.tasks.is_empty() {
-                app.clear_status();
-                app.start_editing();
-            }
+        KeyCode::Char('A') if !app.tasks.is_empty() => {
+            app.clear_status();
+            app.input_mode = InputMode::AddingSubtask;
+        }
+        KeyCode::Char('e') if !app.tasks.is_empty() => {
+            app.clear_status();
+            app.start_editing();
         }
-        KeyCode::Char('d') => {
-            if !app.tasks.is_empty() {
-                app.clear_status();
-                app.input_mode = InputMode::ConfirmDelete;
-            }
+        KeyCode::Char('d') if !app.tasks.is_empty() => {
+            app.clear_status();
+            app.input_mode = InputMode::ConfirmDelete;
         }
-        KeyCode::Char('x') => {
-            if !app.tasks.is_empty() {
-                app.cycle_todo_done()?;
-            }
+        KeyCode::Char('x') if !app.tasks.is_empty() => app.cycle_status()?,
+        KeyCode::Char('b') if !app.tasks.is_empty() => {
+            app.clear_status();
+            app.start_blocking();
         }
+        KeyCode::Char('c') if !app.tasks.is_empty() => app.cancel_task()?,
+        KeyCode::Char('X') if !app.tasks.is_empty() => app.complete_with_subtasks()?,
+        KeyCode::Char('h') | KeyCode::Left => app.collapse_selected()?,
+        KeyCode::Char('l') | KeyCode::Right => app.expand_selected()?,
+        KeyCode::Char('p') if !app.tasks.is_empty() => app.cycle_priority()?,
         KeyCode::Char('f') => app.cycle_filter()?,
+        KeyCode::Char('t') => app.cycle_tag_filter()?,
+        KeyCode::Char('P') => app.cycle_project()?,
+        KeyCode::Char('w') => {
+            app.clear_status();
+            app.start_filtering();
+        }
+        KeyCode::Char('s') => app.cycle_sort()?,
+        KeyCode::Char('S') => app.reverse_sort()?,
+        KeyCode::Char('i') => app.toggle_details(),
+        KeyCode::Char('v') => {
+            app.clear_status();
+            app.toggle_archived()?;
+        }
+        KeyCode::Char('T') => {
+            app.clear_status();
+            app.open_trash()?;
+        }
         KeyCode::Char('j') | KeyCode::Down => {
             app.clear_status();
             app.select_next()?;
@@ -59,7 +102,52 @@ fn handle_normal_mode<R: TaskRepository + Clone>(app: &mut App<R>, key: KeyEvent
     Ok(())
 }
 
-fn handle_adding_mode<R: TaskRepository + Clone>(app: &mut App<R>, key: KeyEvent) -> TuiResult<()> {
+/// The archive is read-only: only browsing keys apply.
+fn handle_archive_mode<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &mut App<R, C>,
+    key: KeyEvent,
+) -> TuiResult<()> {
+    match key.code {
+        KeyCode::Char('q') => app.should_quit = true,
+        KeyCode::Char('v') | KeyCode::Esc => {
+            app.clear_status();
+            app.toggle_archived()?;
+        }
+        KeyCode::Char('h') | KeyCode::Left => app.collapse_selected()?,
+        KeyCode::Char('l') | KeyCode::Right => app.expand_selected()?,
+        KeyCode::Char('f') => app.cycle_filter()?,
+        KeyCode::Char('t') => app.cycle_tag_filter()?,
+        KeyCode::Char('P') => app.cycle_project()?,
+        KeyCode::Char('w') => {
+            app.clear_status();
+            app.start_filtering();
+        }
+        KeyCode::Char('s') => app.cycle_sort()?,
+        KeyCode::Char('S') => app.reverse_sort()?,
+        KeyCode::Char('i') => app.toggle_details(),
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.clear_status();
+            app.select_next()?;
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.clear_status();
+            app.select_previous()?;
+        }
+        _ => (),
+    };
+    Ok(())
+}
+
+fn handle_adding_mode<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &mut App<R, C>,
+    key: KeyEvent,
+) -> TuiResult<()> {
     match key.code {
         KeyCode::Enter => app.add_task()?,
         KeyCode::Esc => {
@@ -75,8 +163,11 @@ fn handle_adding_mode<R: TaskRepository + Clone>(app: &mut App<R>, key: KeyEvent
     Ok(())
 }
 
-fn handle_editing_mode<R: TaskRepository + Clone>(
-    app: &mut App<R>,
+fn handle_editing_mode<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &mut App<R, C>,
     key: KeyEvent,
 ) -> TuiResult<()> {
     match key.code {
@@ -94,8 +185,55 @@ fn handle_editing_mode<R: TaskRepository + Clone>(
     Ok(())
 }
 
-fn handle_confirm_delete_mode<R: TaskRepository + Clone>(
-    app: &mut App<R>,
+fn handle_blocking_mode<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &mut App<R, C>,
+    key: KeyEvent,
+) -> TuiResult<()> {
+    match key.code {
+        KeyCode::Enter => app.block_task()?,
+        KeyCode::Esc => {
+            app.input_buffer = String::new();
+            app.input_mode = InputMode::Normal;
+        }
+        KeyCode::Backspace => {
+            app.input_buffer.pop();
+        }
+        KeyCode::Char(c) => app.input_buffer.push(c),
+        _ => (),
+    };
+    Ok(())
+}
+
+fn handle_filtering_mode<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &mut App<R, C>,
+    key: KeyEvent,
+) -> TuiResult<()> {
+    match key.code {
+        KeyCode::Enter => app.apply_filter()?,
+        KeyCode::Esc => {
+            app.input_buffer = String::new();
+            app.input_mode = InputMode::Normal;
+        }
+        KeyCode::Backspace => {
+            app.input_buffer.pop();
+        }
+        KeyCode::Char(c) => app.input_buffer.push(c),
+        _ => (),
+    };
+    Ok(())
+}
+
+fn handle_confirm_delete_mode<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &mut App<R, C>,
     key: KeyEvent,
 ) -> TuiResult<()> {
     match key.code {
@@ -105,3 +243,42 @@ fn handle_confirm_delete_mode<R: TaskRepository + Clone>(
     };
     Ok(())
 }
+
+fn handle_confirm_reload_mode<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &mut App<R, C>,
+    key: KeyEvent,
+) -> TuiResult<()> {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('r') | KeyCode::Enter => app.confirm_reload(true)?,
+        KeyCode::Char('n') | KeyCode::Esc => app.confirm_reload(false)?,
+        _ => (),
+    };
+    Ok(())
+}
+
+fn handle_trash_mode<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &mut App<R, C>,
+    key: KeyEvent,
+) -> TuiResult<()> {
+    match key.code {
+        KeyCode::Char('T') | KeyCode::Esc => app.close_trash()?,
+        KeyCode::Char('q') => app.should_quit = true,
+        KeyCode::Char('r') if !app.trash.is_empty() => app.restore_task()?,
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.clear_status();
+            app.select_next_trashed();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.clear_status();
+            app.select_previous_trashed();
+        }
+        _ => (),
+    };
+    Ok(())
+}
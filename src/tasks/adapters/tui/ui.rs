@@ -1,13 +1,26 @@
 use crate::tasks::adapters::tui::app::{App, InputMode};
-use crate::tasks::domain::task::TaskStatus;
+use crate::tasks::application::use_cases::list_tasks::FilterTask;
+use crate::tasks::domain::priority::Priority;
+use crate::tasks::domain::task::{Task, TaskStatus};
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::clock::Clock;
+use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
 use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style, Stylize};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, Row, Table, TableState};
-use ratatui::Frame;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Row, Table, TableState, Wrap};
 
-pub fn draw<R: TaskRepository>(frame: &mut Frame, app: &App<R>, table_state: &mut TableState) {
+pub fn draw<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    frame: &mut Frame,
+    app: &App<R, C>,
+    table_state: &mut TableState,
+) {
     let layout = Layout::vertical([
         Constraint::Min(5),
         Constraint::Length(1),
@@ -16,28 +29,58 @@ pub fn draw<R: TaskRepository>(frame: &mut Frame, app: &App<R>, table_state: &mu
     .split(frame.area());
     let [main, command, status] = [layout[0], layout[1], layout[2]];
 
-    render_table(frame, main, app, table_state);
+    if app.input_mode == InputMode::Trash {
+        render_trash(frame, main, app, table_state);
+    } else if app.show_details {
+        let [list, details] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(main);
+        render_table(frame, list, app, table_state);
+        render_details(frame, details, app);
+    } else {
+        render_table(frame, main, app, table_state);
+    }
     render_command(frame, command, app);
     render_status(frame, status, app);
-    if app.input_mode == InputMode::Adding || app.input_mode == InputMode::Editing {
+    if matches!(
+        app.input_mode,
+        InputMode::Adding
+            | InputMode::AddingSubtask
+            | InputMode::Editing
+            | InputMode::Blocking
+            | InputMode::Filtering
+    ) {
         render_input_popup(frame, app);
     }
 }
 
-fn render_table<R: TaskRepository>(
+fn render_table<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
     frame: &mut Frame,
     area: Rect,
-    app: &App<R>,
+    app: &App<R, C>,
     table_state: &mut TableState,
 ) {
+    let title = if app.show_archived {
+        " Archive (read-only) "
+    } else {
+        " TODO Tasks "
+    };
     let border_title = Block::bordered()
-        .title(" TODO Tasks ")
+        .title(title)
         .borders(Borders::ALL)
         .title_alignment(Alignment::Center)
         .border_style(Style::default().fg(Color::Cyan));
 
     if app.tasks.is_empty() {
-        let empty_msg = Paragraph::new("No tasks yet. Press [a] to add one.")
+        let message = if app.show_archived {
+            "No archived tasks. Press [v] to go back."
+        } else {
+            "No tasks yet. Press [a] to add one."
+        };
+        let empty_msg = Paragraph::new(message)
             .alignment(Alignment::Center)
             .style(Style::default().fg(Color::DarkGray))
             .block(border_title);
@@ -45,26 +88,24 @@ fn render_table<R: TaskRepository>(
         return;
     }
 
-    let header = Row::new(["ID", "STATUS", "TITLE", "CREATED", "MODIFIED"])
-        .style(
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )
-        .bottom_margin(1);
+    let header = Row::new([
+        "ID", "STATUS", "PRIORITY", "TITLE", "TAGS", "DUE", "CREATED", "MODIFIED",
+    ])
+    .style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+    .bottom_margin(1);
 
     let rows: Vec<Row> = app
         .tasks
         .iter()
-        .map(|task| {
-            let status_style = match task.status() {
-                TaskStatus::Todo => Style::default().fg(Color::Yellow),
-                TaskStatus::Done => Style::default().fg(Color::Green),
-            };
-            let status_label = match task.status() {
-                TaskStatus::Todo => "[ ] TODO",
-                TaskStatus::Done => "[x] DONE",
-            };
+        .zip(&app.depths)
+        .map(|(task, depth)| {
+            let (status_label, status_style) = status_cell(task.status());
+
+            let (priority_label, priority_style) = priority_cell(task.priority());
 
             // Truncate UUID to first 8 chars
             let short_id = &format!("{}", task.task_id())[..8];
@@ -72,6 +113,25 @@ fn render_table<R: TaskRepository>(
             // Format timestamps as "Mar 21 14:30"
             let created = task.created_at().format("%b %d %H:%M").to_string();
             let modified = task.modified_at().format("%b %d %H:%M").to_string();
+            let mut due = task
+                .due()
+                .map(|due| due.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            if task.recurrence().is_some() {
+                due.push_str(" ↻");
+            }
+            let tags = task
+                .tags()
+                .iter()
+                .map(|tag| tag.to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+            let overdue = app.is_overdue(task);
+            let due_style = if overdue {
+                Style::default().fg(Color::Red).bold()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
 
             Row::new([
                 Line::from(Span::styled(
@@ -79,17 +139,28 @@ fn render_table<R: TaskRepository>(
                     Style::default().fg(Color::DarkGray),
                 )),
                 Line::from(Span::styled(status_label, status_style)),
-                Line::from(Span::raw(task.title())),
+                Line::from(Span::styled(priority_label, priority_style)),
+                title_cell(app, task, *depth),
+                Line::from(Span::styled(tags, Style::default().fg(Color::Cyan))),
+                Line::from(Span::styled(due, due_style)),
                 Line::from(Span::styled(created, Style::default().fg(Color::DarkGray))),
                 Line::from(Span::styled(modified, Style::default().fg(Color::DarkGray))),
             ])
+            .style(if overdue {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            })
         })
         .collect();
 
     let widths = [
         Constraint::Length(8),  // ID (truncated)
-        Constraint::Length(10), // STATUS
+        Constraint::Length(13), // STATUS
+        Constraint::Length(8),  // PRIORITY
         Constraint::Fill(1),    // TITLE (takes remaining space)
+        Constraint::Length(20), // TAGS
+        Constraint::Length(16), // DUE
         Constraint::Length(12), // CREATED
         Constraint::Length(12), // MODIFIED
     ];
@@ -108,19 +179,311 @@ fn render_table<R: TaskRepository>(
     frame.render_stateful_widget(table, area, table_state);
 }
 
-fn render_command<R: TaskRepository>(frame: &mut Frame, area: Rect, app: &App<R>) {
+fn render_trash<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    frame: &mut Frame,
+    area: Rect,
+    app: &App<R, C>,
+    table_state: &mut TableState,
+) {
+    let border_title = Block::bordered()
+        .title(" Trash ")
+        .borders(Borders::ALL)
+        .title_alignment(Alignment::Center)
+        .border_style(Style::default().fg(Color::Red));
+
+    if app.trash.is_empty() {
+        let empty_msg = Paragraph::new("The trash is empty.")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::DarkGray))
+            .block(border_title);
+        frame.render_widget(empty_msg, area);
+        return;
+    }
+
+    let header = Row::new(["ID", "STATUS", "TITLE", "DELETED"])
+        .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+        .bottom_margin(1);
+    let rows: Vec<Row> = app
+        .trash
+        .iter()
+        .map(|task| {
+            let (status_label, status_style) = status_cell(task.status());
+            let short_id = &format!("{}", task.task_id())[..8];
+            let deleted = task
+                .deleted_at()
+                .map(|deleted_at| deleted_at.format("%b %d %H:%M").to_string())
+                .unwrap_or_default();
+            Row::new([
+                Line::from(Span::styled(
+                    short_id.to_string(),
+                    Style::default().fg(Color::DarkGray),
+                )),
+                Line::from(Span::styled(status_label, status_style)),
+                Line::from(task.title().to_string()),
+                Line::from(Span::styled(deleted, Style::default().fg(Color::DarkGray))),
+            ])
+        })
+        .collect();
+    let widths = [
+        Constraint::Length(8),  // ID (truncated)
+        Constraint::Length(13), // STATUS
+        Constraint::Fill(1),    // TITLE
+        Constraint::Length(12), // DELETED
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(border_title)
+        .column_spacing(2)
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    frame.render_stateful_widget(table, area, table_state);
+}
+
+fn render_details<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    frame: &mut Frame,
+    area: Rect,
+    app: &App<R, C>,
+) {
+    let block = Block::bordered()
+        .title(" Details ")
+        .title_alignment(Alignment::Center)
+        .border_style(Style::default().fg(Color::Cyan));
+    let Some(task) = app.selected_task() else {
+        frame.render_widget(block, area);
+        return;
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            task.title().to_string(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            task.task_id().to_string(),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let (status_label, status_style) = status_cell(task.status());
+    let mut status_line = vec![Span::styled(status_label, status_style)];
+    if let Some(reason) = task.blocked_reason() {
+        status_line.push(Span::raw(format!(" ({reason})")));
+    }
+    lines.push(Line::from(status_line));
+    if let Some(recurrence) = task.recurrence() {
+        lines.push(Line::from(Span::styled(
+            format!("↻ {recurrence}"),
+            Style::default().fg(Color::Cyan),
+        )));
+    }
+    if let Some(blockers) = app.blockers.get(&task.task_id()) {
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled(
+            "Blocked by",
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        for blocker in blockers {
+            let (label, style) = status_cell(blocker.status());
+            lines.push(Line::from(vec![
+                Span::styled(label, style),
+                Span::raw(format!(" {}", blocker.title())),
+            ]));
+        }
+    }
+    lines.push(Line::raw(""));
+    match task.description() {
+        Some(description) => lines.extend(description.lines().map(markdown_line)),
+        None => lines.push(Line::from(Span::styled(
+            "No notes. Use `todo note <id>` to write some.",
+            Style::default().fg(Color::DarkGray),
+        ))),
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+/// Light Markdown styling: headings, list bullets and quotes; everything
+/// else is shown as written.
+fn markdown_line(line: &str) -> Line<'_> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') {
+        Line::from(Span::styled(
+            trimmed.trim_start_matches('#').trim_start(),
+            Style::default().fg(Color::Cyan).bold(),
+        ))
+    } else if let Some(item) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+    {
+        let indent = &line[..line.len() - trimmed.len()];
+        Line::from(vec![
+            Span::raw(indent),
+            Span::styled("• ", Style::default().fg(Color::Yellow)),
+            Span::raw(item),
+        ])
+    } else if trimmed.starts_with('>') {
+        Line::from(Span::styled(
+            line,
+            Style::default().fg(Color::DarkGray).italic(),
+        ))
+    } else {
+        Line::raw(line)
+    }
+}
+
+/// Indents subtasks, marks collapsible parents and shows their progress and
+/// how many open tasks they wait on.
+fn title_cell<
+    'a,
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    app: &App<R, C>,
+    task: &'a Task,
+    depth: usize,
+) -> Line<'a> {
+    let marker = match (
+        app.has_subtasks(task),
+        app.collapsed.contains(&task.task_id()),
+    ) {
+        (false, _) => "  ",
+        (true, false) => "▾ ",
+        (true, true) => "▸ ",
+    };
+    let mut spans = vec![
+        Span::raw("  ".repeat(depth)),
+        Span::styled(marker, Style::default().fg(Color::Cyan)),
+        Span::raw(task.title()),
+    ];
+    if let Some(progress) = app.progress.get(&task.task_id())
+        && progress.total > 0
+    {
+        let style = if progress.done == progress.total {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        spans.push(Span::styled(
+            format!(" {}/{} done", progress.done, progress.total),
+            style,
+        ));
+    }
+    let waiting = app.open_blocker_count(task);
+    if waiting > 0 {
+        spans.push(Span::styled(
+            format!(" ⧗ waits on {waiting}"),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn status_cell(status: TaskStatus) -> (&'static str, Style) {
+    match status {
+        TaskStatus::Todo => ("[ ] TODO", Style::default().fg(Color::Yellow)),
+        TaskStatus::InProgress => ("[~] DOING", Style::default().fg(Color::Blue).bold()),
+        TaskStatus::Blocked => ("[!] BLOCKED", Style::default().fg(Color::Magenta)),
+        TaskStatus::Done => ("[x] DONE", Style::default().fg(Color::Green)),
+        TaskStatus::Cancelled => (
+            "[-] CANCELLED",
+            Style::default().fg(Color::DarkGray).crossed_out(),
+        ),
+    }
+}
+
+fn priority_cell(priority: Priority) -> (&'static str, Style) {
+    match priority {
+        Priority::None => ("-", Style::default().fg(Color::DarkGray)),
+        Priority::Low => ("low", Style::default().fg(Color::Blue)),
+        Priority::Medium => ("medium", Style::default().fg(Color::Yellow)),
+        Priority::High => ("high", Style::default().fg(Color::LightRed)),
+        Priority::Urgent => ("urgent", Style::default().fg(Color::Red).bold()),
+    }
+}
+
+fn render_command<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    frame: &mut Frame,
+    area: Rect,
+    app: &App<R, C>,
+) {
     let line = match app.input_mode {
+        InputMode::Normal if app.show_archived => Line::from(vec![
+            Span::styled(" [v/Esc]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw(" back "),
+            Span::styled("[h/l]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw(" fold "),
+            Span::styled("[f]", Style::default().fg(Color::Magenta).bold()),
+            Span::raw("ilter "),
+            Span::styled("[t]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw("ag filter "),
+            Span::styled("[w]", Style::default().fg(Color::Magenta).bold()),
+            Span::raw("here "),
+            Span::styled("[s/S]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw(" sort "),
+            Span::styled("[P]", Style::default().fg(Color::Green).bold()),
+            Span::raw("roject "),
+            Span::styled("[i]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw("nfo "),
+            Span::styled("[q]", Style::default().fg(Color::Red).bold()),
+            Span::raw("uit"),
+        ]),
         InputMode::Normal => Line::from(vec![
             Span::styled(" [a]", Style::default().fg(Color::Cyan).bold()),
             Span::raw("dd "),
+            Span::styled("[A]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw("dd subtask "),
             Span::styled("[e]", Style::default().fg(Color::Cyan).bold()),
             Span::raw("dit "),
             Span::styled("[d]", Style::default().fg(Color::Red).bold()),
             Span::raw("el "),
             Span::styled("[x]", Style::default().fg(Color::Yellow).bold()),
-            Span::raw("done/todo "),
+            Span::raw("status "),
+            Span::styled("[X]", Style::default().fg(Color::Green).bold()),
+            Span::raw(" done+subtasks "),
+            Span::styled("[h/l]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw(" fold "),
+            Span::styled("[b]", Style::default().fg(Color::Magenta).bold()),
+            Span::raw("lock "),
+            Span::styled("[c]", Style::default().fg(Color::DarkGray).bold()),
+            Span::raw("ancel "),
+            Span::styled("[p]", Style::default().fg(Color::LightRed).bold()),
+            Span::raw("riority "),
             Span::styled("[f]", Style::default().fg(Color::Magenta).bold()),
             Span::raw("ilter "),
+            Span::styled("[t]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw("ag filter "),
+            Span::styled("[w]", Style::default().fg(Color::Magenta).bold()),
+            Span::raw("here "),
+            Span::styled("[s/S]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw(" sort "),
+            Span::styled("[P]", Style::default().fg(Color::Green).bold()),
+            Span::raw("roject "),
+            Span::styled("[i]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw("nfo "),
+            Span::styled("[v]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw(" archive "),
+            Span::styled("[T]", Style::default().fg(Color::Red).bold()),
+            Span::raw("rash "),
+            Span::styled("[u]", Style::default().fg(Color::Yellow).bold()),
+            Span::raw("ndo "),
+            Span::styled("[^r]", Style::default().fg(Color::Yellow).bold()),
+            Span::raw("edo "),
             Span::styled("[q]", Style::default().fg(Color::Red).bold()),
             Span::raw("uit"),
         ]),
@@ -134,17 +497,45 @@ fn render_command<R: TaskRepository>(frame: &mut Frame, area: Rect, app: &App<R>
             Span::styled("[n]", Style::default().fg(Color::Red).bold()),
             Span::raw("o"),
         ]),
+        InputMode::ConfirmReload => Line::from(vec![
+            Span::styled(
+                " Changed elsewhere. Reload? ",
+                Style::default().fg(Color::Yellow).bold(),
+            ),
+            Span::styled("[y]", Style::default().fg(Color::Green).bold()),
+            Span::raw("es "),
+            Span::styled("[n]", Style::default().fg(Color::Red).bold()),
+            Span::raw("o"),
+        ]),
+        InputMode::Trash => Line::from(vec![
+            Span::styled(" [r]", Style::default().fg(Color::Green).bold()),
+            Span::raw("estore "),
+            Span::styled("[T/Esc]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw(" back "),
+            Span::styled("[q]", Style::default().fg(Color::Red).bold()),
+            Span::raw("uit"),
+        ]),
         _ => Line::from(vec![]),
     };
     let paragraph = Paragraph::new(line).style(Style::default().bg(Color::Black));
     frame.render_widget(paragraph, area);
 }
 
-fn render_status<R: TaskRepository>(frame: &mut Frame, area: Rect, app: &App<R>) {
+fn render_status<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    frame: &mut Frame,
+    area: Rect,
+    app: &App<R, C>,
+) {
     let filter_label = match app.filter {
-        crate::tasks::application::use_cases::list_tasks::FilterTask::All => "All",
-        crate::tasks::application::use_cases::list_tasks::FilterTask::Todo => "Todo",
-        crate::tasks::application::use_cases::list_tasks::FilterTask::Done => "Done",
+        FilterTask::All => "All",
+        FilterTask::Todo => "Todo",
+        FilterTask::InProgress => "In progress",
+        FilterTask::Blocked => "Blocked",
+        FilterTask::Done => "Done",
+        FilterTask::Cancelled => "Cancelled",
     };
 
     let mut spans = vec![
@@ -155,11 +546,56 @@ fn render_status<R: TaskRepository>(frame: &mut Frame, area: Rect, app: &App<R>)
                 .fg(Color::Magenta)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(
-            format!(" | {} tasks", app.tasks.len()),
+    ];
+    spans.push(Span::styled(
+        " Project: ",
+        Style::default().fg(Color::DarkGray),
+    ));
+    spans.push(Span::styled(
+        app.project_scope.label().to_string(),
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+    ));
+    if let Some(tag) = &app.tag_filter {
+        spans.push(Span::styled(" Tag: ", Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(
+            tag.to_string(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(filter) = &app.where_filter {
+        spans.push(Span::styled(
+            " Where: ",
             Style::default().fg(Color::DarkGray),
+        ));
+        spans.push(Span::styled(
+            filter.clone(),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans.push(Span::styled(
+        " Sort: ",
+        Style::default().fg(Color::DarkGray),
+    ));
+    spans.push(Span::styled(
+        format!(
+            "{} {}",
+            app.sort.key.label(),
+            if app.sort.descending { "↓" } else { "↑" }
         ),
-    ];
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    ));
+    spans.push(Span::styled(
+        format!(" | {} tasks", app.tasks.len()),
+        Style::default().fg(Color::DarkGray),
+    ));
 
     if let Some(ref msg) = app.status_message {
         spans.push(Span::styled(
@@ -188,12 +624,24 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     .split(vertical[1]);
     horizontal[1]
 }
-fn render_input_popup<R: TaskRepository>(frame: &mut Frame, app: &App<R>) {
+fn render_input_popup<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + JournalRepository + Clone,
+    C: Clock + Clone,
+>(
+    frame: &mut Frame,
+    app: &App<R, C>,
+) {
     let area = centered_rect(50, 20, frame.area());
     frame.render_widget(Clear, area);
     let (title, label_style) = match app.input_mode {
         InputMode::Adding => (" New Task ", Style::default().fg(Color::Cyan)),
+        InputMode::AddingSubtask => (" New Subtask ", Style::default().fg(Color::Cyan)),
         InputMode::Editing => (" Edit Task ", Style::default().fg(Color::Yellow)),
+        InputMode::Blocking => (" Blocked Because ", Style::default().fg(Color::Magenta)),
+        InputMode::Filtering => (
+            " Filter (e.g. status:todo and tag:backend) ",
+            Style::default().fg(Color::Magenta),
+        ),
         _ => unreachable!(),
     };
     let block = Block::bordered()
@@ -0,0 +1,66 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Ids of every task that is neither done nor cancelled.
+pub fn open_task_ids<R: TaskRepository>(repo: &R) -> ApplicationResult<HashSet<Uuid>> {
+    Ok(repo
+        .list(TaskQuery::All)?
+        .iter()
+        .filter(|task| task.status().is_open())
+        .map(Task::task_id)
+        .collect())
+}
+
+/// Blockers of `task` that are still open; deleted blockers no longer count.
+pub fn open_blockers<R: TaskRepository>(repo: &R, task: &Task) -> ApplicationResult<Vec<Uuid>> {
+    let mut open: Vec<Uuid> = Vec::new();
+    for id in task.blocked_by() {
+        if repo
+            .find_by_id(*id)?
+            .is_some_and(|blocker| blocker.status().is_open())
+        {
+            open.push(*id);
+        }
+    }
+    Ok(open)
+}
+
+pub fn ensure_unblocked<R: TaskRepository>(repo: &R, task: &Task) -> ApplicationResult<()> {
+    Ok(task.ensure_unblocked(open_blockers(repo, task)?)?)
+}
+
+/// Refuses blockers that do not exist and blockers that already depend,
+/// directly or not, on `task_id`.
+pub fn ensure_valid_blocker<R: TaskRepository>(
+    repo: &R,
+    task_id: Uuid,
+    blocker: Uuid,
+) -> ApplicationResult<()> {
+    let blocker_task: Task = repo
+        .find_by_id(blocker)?
+        .ok_or(DomainError::BlockerNotFound { id: blocker })?;
+    let mut visited: HashSet<Uuid> = HashSet::new();
+    let mut pending: Vec<Task> = vec![blocker_task];
+    while let Some(current) = pending.pop() {
+        if current.task_id() == task_id {
+            return Err(DomainError::DependencyCycle {
+                id: task_id,
+                blocker,
+            }
+            .into());
+        }
+        if !visited.insert(current.task_id()) {
+            continue;
+        }
+        for id in current.blocked_by() {
+            if let Some(next) = repo.find_by_id(*id)? {
+                pending.push(next);
+            }
+        }
+    }
+    Ok(())
+}
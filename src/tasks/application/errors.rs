@@ -1,6 +1,7 @@
 use crate::tasks::domain::errors::DomainError;
 use crate::tasks::ports::outputs::errors::RepoError;
 use thiserror::Error;
+use uuid::Uuid;
 
 pub type ApplicationResult<T> = Result<T, ApplicationError>;
 #[derive(Debug, Error)]
@@ -9,4 +10,36 @@ pub enum ApplicationError {
     Domain(#[from] DomainError),
     #[error(transparent)]
     Repository(#[from] RepoError),
+    #[error("no changes requested for task {id}")]
+    NothingToEdit { id: Uuid },
+    #[error("cannot move tasks into project '{name}' while deleting it")]
+    MoveIntoDeletedProject { name: String },
+    #[error("task {id} is still open; only done or cancelled tasks can be archived")]
+    ArchiveOpenTask { id: Uuid },
+    #[error(
+        "invalid task reference '{value}' (expected a task id, a unique id prefix or a list number)"
+    )]
+    InvalidTaskRef { value: String },
+    #[error("no task matches '{reference}'")]
+    TaskRefNotFound { reference: String },
+    #[error("'{reference}' matches several tasks: {}", join_ids(.candidates))]
+    AmbiguousTaskRef {
+        reference: String,
+        candidates: Vec<Uuid>,
+    },
+    #[error("{days} days ago is before the earliest supported date")]
+    AgeOutOfRange { days: u32 },
+    #[error("nothing to undo")]
+    NothingToUndo,
+    #[error("nothing to redo")]
+    NothingToRedo,
+    #[error("the undo history was changed by another todo process; nothing was changed, try again")]
+    JournalChanged,
+}
+
+fn join_ids(ids: &[Uuid]) -> String {
+    ids.iter()
+        .map(Uuid::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
 }
@@ -0,0 +1,259 @@
+use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::domain::journal::{JournalEntry, TaskChange};
+use crate::tasks::domain::project::Project;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::errors::{RepoError, RepoResult};
+use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use chrono::{DateTime, Utc};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use uuid::Uuid;
+
+/// Repository decorator that remembers every task it saves or deletes, so
+/// that whatever a use case did can be stored as one undoable journal
+/// entry. Clones share the recorded changes, because services take their
+/// repository by value. Projects and the archive are passed through
+/// unrecorded.
+#[derive(Debug, Clone)]
+pub struct JournalingRepository<R> {
+    inner: R,
+    changes: Rc<RefCell<Vec<TaskChange>>>,
+}
+
+impl<R: TaskRepository + JournalRepository> JournalingRepository<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            changes: Rc::default(),
+        }
+    }
+
+    /// Stores the recorded changes as a journal entry; returns whether there
+    /// was anything to record.
+    pub fn commit(mut self, label: &str, now: DateTime<Utc>) -> ApplicationResult<bool> {
+        let changes: Vec<TaskChange> = self.changes.take();
+        if changes.is_empty() {
+            return Ok(false);
+        }
+        let entry: JournalEntry = JournalEntry::new(label.to_string(), now, changes);
+        self.inner.update_journal(|journal| journal.record(entry))?;
+        Ok(true)
+    }
+}
+
+/// Runs `operation` against a journaling view of `repo` and records what it
+/// saved or deleted as one entry. The entry is recorded when the operation
+/// fails partway too, so undo can revert the writes that did happen.
+pub fn run_journaled<R, T, E>(
+    repo: R,
+    label: &str,
+    now: DateTime<Utc>,
+    operation: impl FnOnce(JournalingRepository<R>) -> Result<T, E>,
+) -> Result<T, E>
+where
+    R: TaskRepository + JournalRepository + Clone,
+    E: From<ApplicationError>,
+{
+    let journaling: JournalingRepository<R> = JournalingRepository::new(repo);
+    let result: Result<T, E> = operation(journaling.clone());
+    let committed: ApplicationResult<bool> = journaling.commit(label, now);
+    let value: T = result?;
+    committed?;
+    Ok(value)
+}
+
+impl<R: TaskRepository> TaskRepository for JournalingRepository<R> {
+    fn save(&mut self, task: Task) -> RepoResult<Task> {
+        let before: Option<Task> = self.inner.find_any(task.task_id())?;
+        let stored: Task = self.inner.save(task)?;
+        self.changes
+            .borrow_mut()
+            .push(TaskChange::new(before, Some(stored.clone())));
+        Ok(stored)
+    }
+
+    fn save_all(&mut self, tasks: Vec<Task>) -> RepoResult<Vec<Task>> {
+        let mut befores: Vec<Option<Task>> = Vec::new();
+        for task in &tasks {
+            befores.push(self.inner.find_any(task.task_id())?);
+        }
+        let stored: Vec<Task> = self.inner.save_all(tasks)?;
+        self.changes.borrow_mut().extend(
+            befores
+                .into_iter()
+                .zip(&stored)
+                .map(|(before, after)| TaskChange::new(before, Some(after.clone()))),
+        );
+        Ok(stored)
+    }
+
+    fn list(&self, query: TaskQuery) -> RepoResult<Vec<Task>> {
+        self.inner.list(query)
+    }
+
+    fn find_any(&self, id: Uuid) -> RepoResult<Option<Task>> {
+        self.inner.find_any(id)
+    }
+
+    fn delete(&mut self, id: Uuid) -> RepoResult<bool> {
+        let before: Option<Task> = self.inner.find_any(id)?;
+        let deleted: bool = self.inner.delete(id)?;
+        if deleted {
+            self.changes
+                .borrow_mut()
+                .push(TaskChange::new(before, None));
+        }
+        Ok(deleted)
+    }
+}
+
+impl<R: ProjectRepository> ProjectRepository for JournalingRepository<R> {
+    fn save_project(&mut self, project: Project) -> RepoResult<()> {
+        self.inner.save_project(project)
+    }
+
+    fn list_projects(&self) -> RepoResult<Vec<Project>> {
+        self.inner.list_projects()
+    }
+
+    fn find_project_by_id(&self, id: Uuid) -> RepoResult<Option<Project>> {
+        self.inner.find_project_by_id(id)
+    }
+
+    fn find_project_by_name(&self, name: &str) -> RepoResult<Option<Project>> {
+        self.inner.find_project_by_name(name)
+    }
+
+    fn delete_project(&mut self, id: Uuid) -> RepoResult<bool> {
+        self.inner.delete_project(id)
+    }
+}
+
+impl<R: ArchiveRepository> ArchiveRepository for JournalingRepository<R> {
+    fn list_archived(&self) -> RepoResult<Vec<Task>> {
+        self.inner.list_archived()
+    }
+
+    fn archive(&mut self, tasks: Vec<Task>) -> RepoResult<()> {
+        self.inner.archive(tasks)
+    }
+
+    fn delete_archived(&mut self, id: Uuid) -> RepoResult<bool> {
+        self.inner.delete_archived(id)
+    }
+}
+
+/// Puts every task of the entry back the way it was before the operation
+/// and returns the entry with its `before` snapshots as now stored, which is
+/// the state [`reapply`] expects to find. Tasks archived since are left in
+/// the archive: archiving is not journaled, so restoring them would put the
+/// same task in both stores.
+pub fn revert<R: TaskRepository + ArchiveRepository>(
+    repo: &mut R,
+    entry: &JournalEntry,
+) -> ApplicationResult<JournalEntry> {
+    let archived: HashSet<Uuid> = archived_ids(repo)?;
+    let pending: Vec<(usize, &TaskChange)> = entry
+        .changes()
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, change)| !change.task_id().is_some_and(|id| archived.contains(&id)))
+        .collect();
+    ensure_unchanged(
+        repo,
+        pending
+            .iter()
+            .filter_map(|(_, change)| Some((change.task_id()?, change.after()))),
+    )?;
+    let mut changes: Vec<TaskChange> = entry.changes().to_vec();
+    for (index, change) in pending {
+        let restored: Option<Task> = restore(repo, change, change.before())?;
+        changes[index] = TaskChange::new(restored, change.after().cloned());
+    }
+    Ok(entry.clone().with_changes(changes))
+}
+
+/// Applies the entry's changes again and returns the entry with its `after`
+/// snapshots as now stored, skipping archived tasks like [`revert`].
+pub fn reapply<R: TaskRepository + ArchiveRepository>(
+    repo: &mut R,
+    entry: &JournalEntry,
+) -> ApplicationResult<JournalEntry> {
+    let archived: HashSet<Uuid> = archived_ids(repo)?;
+    let pending: Vec<(usize, &TaskChange)> = entry
+        .changes()
+        .iter()
+        .enumerate()
+        .filter(|(_, change)| !change.task_id().is_some_and(|id| archived.contains(&id)))
+        .collect();
+    ensure_unchanged(
+        repo,
+        pending
+            .iter()
+            .filter_map(|(_, change)| Some((change.task_id()?, change.before()))),
+    )?;
+    let mut changes: Vec<TaskChange> = entry.changes().to_vec();
+    for (index, change) in pending {
+        let restored: Option<Task> = restore(repo, change, change.after())?;
+        changes[index] = TaskChange::new(change.before().cloned(), restored);
+    }
+    Ok(entry.clone().with_changes(changes))
+}
+
+fn archived_ids<R: ArchiveRepository>(repo: &R) -> ApplicationResult<HashSet<Uuid>> {
+    Ok(repo.list_archived()?.iter().map(Task::task_id).collect())
+}
+
+/// Refuses to undo or redo over changes made since: each task the entry
+/// touches must still be at the version the journal recorded for the side
+/// being replaced, or be missing if that side is `None`. Only the first
+/// snapshot of each task counts, as later ones follow from it within the
+/// entry. Nothing is written unless every task passes.
+fn ensure_unchanged<'a, R: TaskRepository>(
+    repo: &R,
+    expected: impl Iterator<Item = (Uuid, Option<&'a Task>)>,
+) -> RepoResult<()> {
+    let mut seen: HashSet<Uuid> = HashSet::new();
+    for (id, expected) in expected {
+        if !seen.insert(id) {
+            continue;
+        }
+        let expected: Option<u64> = expected.map(Task::version);
+        let actual: Option<u64> = repo.find_any(id)?.map(|task| task.version());
+        if actual != expected {
+            return Err(RepoError::Conflict {
+                id,
+                expected: expected.unwrap_or(0),
+                actual: actual.unwrap_or(0),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Writes one side of a change over the store and returns the task as
+/// stored, or `None` if it was removed.
+fn restore<R: TaskRepository>(
+    repo: &mut R,
+    change: &TaskChange,
+    state: Option<&Task>,
+) -> ApplicationResult<Option<Task>> {
+    match (state, change.task_id()) {
+        (Some(task), _) => {
+            let current: u64 = repo
+                .find_any(task.task_id())?
+                .map_or(task.version(), |current| current.version());
+            Ok(Some(repo.save(task.clone().with_version(current))?))
+        }
+        (None, Some(id)) => {
+            repo.delete(id)?;
+            Ok(None)
+        }
+        (None, None) => Ok(None),
+    }
+}
@@ -1,2 +1,7 @@
+pub mod dependencies;
 pub mod errors;
+pub mod journal;
+pub mod project_lookup;
+pub mod staging;
+pub mod subtasks;
 pub mod use_cases;
@@ -0,0 +1,22 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::project::is_inbox;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+use uuid::Uuid;
+
+/// Resolves a project name to the id stored on tasks; the inbox maps to `None`.
+pub fn resolve_project_id<R: ProjectRepository>(
+    repo: &R,
+    name: &str,
+) -> ApplicationResult<Option<Uuid>> {
+    if is_inbox(name) {
+        return Ok(None);
+    }
+    match repo.find_project_by_name(name)? {
+        Some(project) => Ok(Some(project.project_id())),
+        None => Err(DomainError::ProjectNotFound {
+            name: name.trim().to_string(),
+        }
+        .into()),
+    }
+}
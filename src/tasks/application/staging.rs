@@ -0,0 +1,207 @@
+use crate::tasks::domain::project::Project;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::errors::RepoResult;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use std::cell::RefCell;
+use std::rc::Rc;
+use uuid::Uuid;
+
+/// Task changes held back from the underlying store, in the order they were
+/// first made; `None` marks a deleted task.
+type Staged = Vec<(Uuid, Option<Task>)>;
+
+/// Repository decorator that keeps task writes in memory while reading
+/// through to the wrapped store, so that several use cases can run against
+/// the combined state and their changes be written at once, or dropped.
+/// Clones share the staged changes, because services take their repository
+/// by value. Projects are passed through.
+#[derive(Debug, Clone)]
+pub struct StagingRepository<R> {
+    inner: R,
+    staged: Rc<RefCell<Staged>>,
+}
+
+/// The staged changes at some point, to roll back to.
+#[derive(Debug, Clone)]
+pub struct Checkpoint(Staged);
+
+impl<R: TaskRepository> StagingRepository<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            staged: Rc::default(),
+        }
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.staged.borrow().clone())
+    }
+
+    /// Drops every change staged since `checkpoint` was taken.
+    pub fn rollback(&self, checkpoint: Checkpoint) {
+        *self.staged.borrow_mut() = checkpoint.0;
+    }
+
+    /// Writes the staged changes to `repo`, every saved task in one
+    /// [`TaskRepository::save_all`].
+    pub fn apply_to<T: TaskRepository>(&self, repo: &mut T) -> RepoResult<()> {
+        let staged: Staged = self.staged.borrow().clone();
+        let (saved, deleted): (Staged, Staged) =
+            staged.into_iter().partition(|(_, task)| task.is_some());
+        if !saved.is_empty() {
+            repo.save_all(saved.into_iter().filter_map(|(_, task)| task).collect())?;
+        }
+        for (id, _) in deleted {
+            repo.delete(id)?;
+        }
+        Ok(())
+    }
+
+    /// The task as changed by earlier staged writes, if it was.
+    pub fn staged_task(&self, id: Uuid) -> Option<Task> {
+        self.staged(id).flatten()
+    }
+
+    fn stage(&self, id: Uuid, task: Option<Task>) {
+        let mut staged = self.staged.borrow_mut();
+        match staged.iter_mut().find(|(staged_id, _)| *staged_id == id) {
+            Some(entry) => entry.1 = task,
+            None => staged.push((id, task)),
+        }
+    }
+
+    fn staged(&self, id: Uuid) -> Option<Option<Task>> {
+        self.staged
+            .borrow()
+            .iter()
+            .find(|(staged_id, _)| *staged_id == id)
+            .map(|(_, task)| task.clone())
+    }
+}
+
+impl<R: TaskRepository> TaskRepository for StagingRepository<R> {
+    /// Staged tasks keep the version they were read at, so that applying
+    /// them still detects changes made to the store in the meantime.
+    fn save(&mut self, task: Task) -> RepoResult<Task> {
+        self.stage(task.task_id(), Some(task.clone()));
+        Ok(task)
+    }
+
+    fn list(&self, query: TaskQuery) -> RepoResult<Vec<Task>> {
+        let staged = self.staged.borrow();
+        let mut tasks: Vec<Task> = self
+            .inner
+            .list(query.clone())?
+            .into_iter()
+            .filter(|task| !staged.iter().any(|(id, _)| *id == task.task_id()))
+            .collect();
+        tasks.extend(
+            staged
+                .iter()
+                .filter_map(|(_, task)| task.clone())
+                .filter(|task| query.matches(task)),
+        );
+        Ok(tasks)
+    }
+
+    fn find_any(&self, id: Uuid) -> RepoResult<Option<Task>> {
+        match self.staged(id) {
+            Some(task) => Ok(task),
+            None => self.inner.find_any(id),
+        }
+    }
+
+    fn delete(&mut self, id: Uuid) -> RepoResult<bool> {
+        let existed: bool = self.find_any(id)?.is_some();
+        if existed {
+            self.stage(id, None);
+        }
+        Ok(existed)
+    }
+}
+
+impl<R: ProjectRepository> ProjectRepository for StagingRepository<R> {
+    fn save_project(&mut self, project: Project) -> RepoResult<()> {
+        self.inner.save_project(project)
+    }
+
+    fn list_projects(&self) -> RepoResult<Vec<Project>> {
+        self.inner.list_projects()
+    }
+
+    fn find_project_by_id(&self, id: Uuid) -> RepoResult<Option<Project>> {
+        self.inner.find_project_by_id(id)
+    }
+
+    fn find_project_by_name(&self, name: &str) -> RepoResult<Option<Project>> {
+        self.inner.find_project_by_name(name)
+    }
+
+    fn delete_project(&mut self, id: Uuid) -> RepoResult<bool> {
+        self.inner.delete_project(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StagingRepository;
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::domain::task::{Task, TaskStatus};
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+
+    #[test]
+    fn staged_changes_are_visible_but_only_written_when_applied() {
+        let mut repo = InMemoryTaskRepository::new();
+        let task = Task::new("deploy".to_string()).expect("task should be created");
+        let task = repo.save(task).expect("save should succeed");
+        let mut staging = StagingRepository::new(repo.clone());
+
+        staging
+            .save(task.clone().mark_done().expect("task should complete"))
+            .expect("save should succeed");
+
+        let done = staging
+            .list(TaskQuery::ByStatus(TaskStatus::Done))
+            .expect("list should succeed");
+        assert_eq!(done.len(), 1);
+        assert_eq!(
+            repo.list(TaskQuery::ByStatus(TaskStatus::Done))
+                .expect("list should succeed")
+                .len(),
+            0
+        );
+        staging.apply_to(&mut repo).expect("apply should succeed");
+        let stored = repo
+            .find_any(task.task_id())
+            .expect("find should succeed")
+            .expect("task should exist");
+        assert_eq!(stored.status(), TaskStatus::Done);
+    }
+
+    #[test]
+    fn rollback_drops_changes_made_after_the_checkpoint() {
+        let repo = InMemoryTaskRepository::new();
+        let mut staging = StagingRepository::new(repo);
+        let kept = Task::new("kept".to_string()).expect("task should be created");
+        let dropped = Task::new("dropped".to_string()).expect("task should be created");
+        staging.save(kept.clone()).expect("save should succeed");
+
+        let checkpoint = staging.checkpoint();
+        staging.save(dropped.clone()).expect("save should succeed");
+        staging.rollback(checkpoint);
+
+        assert!(
+            staging
+                .find_any(kept.task_id())
+                .expect("find should succeed")
+                .is_some()
+        );
+        assert!(
+            staging
+                .find_any(dropped.task_id())
+                .expect("find should succeed")
+                .is_none()
+        );
+    }
+}
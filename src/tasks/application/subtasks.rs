@@ -0,0 +1,83 @@
+use crate::tasks::application::dependencies::open_blockers;
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// What happens to open subtasks when their parent is marked done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubtaskDonePolicy {
+    /// Refuse while any subtask is still open.
+    #[default]
+    Block,
+    /// Mark every open subtask done as well.
+    Cascade,
+}
+
+/// Refuses parents that do not exist and parents that are the task itself or
+/// one of its descendants.
+pub fn ensure_valid_parent<R: TaskRepository>(
+    repo: &R,
+    task_id: Uuid,
+    parent: Uuid,
+) -> ApplicationResult<()> {
+    let mut ancestor: Task = repo
+        .find_by_id(parent)?
+        .ok_or(DomainError::ParentNotFound { id: parent })?;
+    let mut visited: HashSet<Uuid> = HashSet::new();
+    while visited.insert(ancestor.task_id()) {
+        if ancestor.task_id() == task_id {
+            return Err(DomainError::SubtaskCycle {
+                id: task_id,
+                parent,
+            }
+            .into());
+        }
+        match ancestor.parent() {
+            Some(next) => match repo.find_by_id(next)? {
+                Some(next) => ancestor = next,
+                None => break,
+            },
+            None => break,
+        }
+    }
+    Ok(())
+}
+
+/// Applies `policy` to the open descendants of `task_id` and returns the
+/// subtasks that have to be saved alongside the parent.
+pub fn complete_subtasks<R: TaskRepository>(
+    repo: &R,
+    task_id: Uuid,
+    policy: SubtaskDonePolicy,
+) -> ApplicationResult<Vec<Task>> {
+    let open: Vec<Task> = repo
+        .descendants(task_id)?
+        .into_iter()
+        .filter(|task| task.status().is_open())
+        .collect();
+    match policy {
+        SubtaskDonePolicy::Block if !open.is_empty() => Err(DomainError::OpenSubtasks {
+            id: task_id,
+            open: open.len(),
+        }
+        .into()),
+        SubtaskDonePolicy::Block => Ok(Vec::new()),
+        SubtaskDonePolicy::Cascade => {
+            // Blockers completed by the same cascade do not count.
+            let completing: HashSet<Uuid> = open.iter().map(Task::task_id).collect();
+            let mut completed: Vec<Task> = Vec::new();
+            for task in open {
+                let blockers: Vec<Uuid> = open_blockers(repo, &task)?
+                    .into_iter()
+                    .filter(|id| !completing.contains(id) && *id != task_id)
+                    .collect();
+                task.ensure_unblocked(blockers)?;
+                completed.push(task.mark_done()?);
+            }
+            Ok(completed)
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::tasks::application::dependencies::ensure_valid_blocker;
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use uuid::Uuid;
+
+/// Records that `task_id` cannot start until `blocker_id` is done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddDependencyCommand {
+    task_id: Uuid,
+    blocker_id: Uuid,
+}
+impl AddDependencyCommand {
+    pub fn new(task_id: Uuid, blocker_id: Uuid) -> Self {
+        Self {
+            task_id,
+            blocker_id,
+        }
+    }
+}
+pub trait AddDependencyUseCase {
+    fn execute(&mut self, cmd: AddDependencyCommand) -> ApplicationResult<Task>;
+}
+pub struct AddDependencyService<R: TaskRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository> AddDependencyService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository> AddDependencyUseCase for AddDependencyService<R> {
+    fn execute(&mut self, cmd: AddDependencyCommand) -> ApplicationResult<Task> {
+        let task: Task = self
+            .repo
+            .find_by_id(cmd.task_id)?
+            .ok_or(DomainError::TaskNotFound { id: cmd.task_id })?;
+        ensure_valid_blocker(&self.repo, cmd.task_id, cmd.blocker_id)?;
+        let task: Task = task.add_blocker(cmd.blocker_id)?;
+        Ok(self.repo.save(task)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AddDependencyCommand, AddDependencyService, AddDependencyUseCase};
+    use crate::tasks::adapters::persistence::json_file_task_repository::JsonFileTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::domain::errors::DomainError;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::task_repository::TaskRepository;
+    use tempfile::tempdir;
+    use uuid::Uuid;
+
+    #[test]
+    fn rejects_transitive_cycles() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let a = Task::new("a".to_string()).expect("task should be created");
+        let b = Task::new("b".to_string()).expect("task should be created");
+        let c = Task::new("c".to_string()).expect("task should be created");
+        for task in [&a, &b, &c] {
+            repo.save(task.clone()).expect("save should succeed");
+        }
+        let mut service = AddDependencyService::new(repo);
+        service
+            .execute(AddDependencyCommand::new(b.task_id(), a.task_id()))
+            .expect("b blocked by a should succeed");
+        service
+            .execute(AddDependencyCommand::new(c.task_id(), b.task_id()))
+            .expect("c blocked by b should succeed");
+
+        let result = service.execute(AddDependencyCommand::new(a.task_id(), c.task_id()));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(
+                DomainError::DependencyCycle { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn both_ends_must_exist() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let task = Task::new("a".to_string()).expect("task should be created");
+        repo.save(task.clone()).expect("save should succeed");
+        let mut service = AddDependencyService::new(repo);
+
+        let missing_blocker =
+            service.execute(AddDependencyCommand::new(task.task_id(), Uuid::new_v4()));
+        let missing_task =
+            service.execute(AddDependencyCommand::new(Uuid::new_v4(), task.task_id()));
+
+        assert!(matches!(
+            missing_blocker,
+            Err(ApplicationError::Domain(
+                DomainError::BlockerNotFound { .. }
+            ))
+        ));
+        assert!(matches!(
+            missing_task,
+            Err(ApplicationError::Domain(DomainError::TaskNotFound { .. }))
+        ));
+    }
+}
@@ -0,0 +1,40 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::project::Project;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddProjectCommand {
+    name: String,
+}
+impl AddProjectCommand {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+pub trait AddProjectUseCase {
+    fn execute(&mut self, cmd: AddProjectCommand) -> ApplicationResult<Project>;
+}
+pub struct AddProjectService<R: ProjectRepository> {
+    repo: R,
+}
+
+impl<R: ProjectRepository> AddProjectService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: ProjectRepository> AddProjectUseCase for AddProjectService<R> {
+    fn execute(&mut self, cmd: AddProjectCommand) -> ApplicationResult<Project> {
+        let project: Project = Project::new(cmd.name)?;
+        if self.repo.find_project_by_name(project.name())?.is_some() {
+            return Err(DomainError::DuplicateProjectName {
+                name: project.name().to_string(),
+            }
+            .into());
+        }
+        self.repo.save_project(project.clone())?;
+        Ok(project)
+    }
+}
@@ -1,33 +1,131 @@
 use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::application::project_lookup::resolve_project_id;
+use crate::tasks::application::subtasks::ensure_valid_parent;
+use crate::tasks::domain::due_date::DueDate;
+use crate::tasks::domain::priority::Priority;
+use crate::tasks::domain::recurrence::Recurrence;
+use crate::tasks::domain::tag::Tag;
 use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
 use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AddTaskCommand {
     title: String,
+    priority: Option<Priority>,
+    due: Option<DueDate>,
+    recurrence: Option<Recurrence>,
+    tags: Vec<String>,
+    description: Option<String>,
+    parent: Option<Uuid>,
+    project: Option<String>,
 }
 impl AddTaskCommand {
     pub fn new(title: String) -> Self {
-        Self { title }
+        Self {
+            title,
+            priority: None,
+            due: None,
+            recurrence: None,
+            tags: Vec::new(),
+            description: None,
+            parent: None,
+            project: None,
+        }
+    }
+
+    pub fn with_priority(self, priority: Priority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+
+    pub fn with_due(self, due: DueDate) -> Self {
+        Self {
+            due: Some(due),
+            ..self
+        }
+    }
+
+    pub fn with_recurrence(self, recurrence: Recurrence) -> Self {
+        Self {
+            recurrence: Some(recurrence),
+            ..self
+        }
+    }
+
+    pub fn with_tags(self, tags: Vec<String>) -> Self {
+        Self { tags, ..self }
+    }
+
+    pub fn with_description(self, description: String) -> Self {
+        Self {
+            description: Some(description),
+            ..self
+        }
+    }
+
+    /// Without an explicit project the subtask joins its parent's project.
+    pub fn with_parent(self, parent: Uuid) -> Self {
+        Self {
+            parent: Some(parent),
+            ..self
+        }
+    }
+
+    pub fn with_project(self, project: String) -> Self {
+        Self {
+            project: Some(project),
+            ..self
+        }
     }
 }
 pub trait AddTaskUseCase {
     fn execute(&mut self, cmd: AddTaskCommand) -> ApplicationResult<Task>;
 }
-pub struct AddTaskService<R: TaskRepository> {
+pub struct AddTaskService<R: TaskRepository + ProjectRepository> {
     repo: R,
 }
 
-impl<R: TaskRepository> AddTaskService<R> {
+impl<R: TaskRepository + ProjectRepository> AddTaskService<R> {
     pub fn new(repo: R) -> Self {
         Self { repo }
     }
 }
 
-impl<R: TaskRepository> AddTaskUseCase for AddTaskService<R> {
+impl<R: TaskRepository + ProjectRepository> AddTaskUseCase for AddTaskService<R> {
     fn execute(&mut self, command: AddTaskCommand) -> ApplicationResult<Task> {
-        let task: Task = Task::new(command.title)?;
-        self.repo.save(task.clone())?;
-        Ok(task)
+        let mut task: Task = Task::new(command.title)?;
+        if let Some(priority) = command.priority {
+            task = task.set_priority(priority)?;
+        }
+        if let Some(due) = command.due {
+            task = task.set_due(Some(due))?;
+        }
+        if command.recurrence.is_some() {
+            task = task.set_recurrence(command.recurrence)?;
+        }
+        for tag in command.tags {
+            task = task.add_tag(Tag::parse(&tag)?)?;
+        }
+        if command.description.is_some() {
+            task = task.set_description(command.description)?;
+        }
+        if let Some(parent) = command.parent {
+            ensure_valid_parent(&self.repo, task.task_id(), parent)?;
+            let parent_project: Option<Uuid> = self
+                .repo
+                .find_by_id(parent)?
+                .and_then(|parent| parent.project());
+            task = task
+                .set_parent(Some(parent))?
+                .move_to_project(parent_project)?;
+        }
+        if let Some(project) = command.project {
+            task = task.move_to_project(resolve_project_id(&self.repo, &project)?)?;
+        }
+        Ok(self.repo.save(task)?)
     }
 }
@@ -0,0 +1,210 @@
+use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::clock::Clock;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveTasksCommand {
+    task_ids: Vec<Uuid>,
+    older_than_days: Option<u32>,
+}
+impl ArchiveTasksCommand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Archives these tasks; each one has to be done or cancelled.
+    pub fn with_ids(self, task_ids: Vec<Uuid>) -> Self {
+        Self { task_ids, ..self }
+    }
+
+    /// Also archives every done or cancelled task left untouched for at
+    /// least `days` days.
+    pub fn with_older_than(self, days: u32) -> Self {
+        Self {
+            older_than_days: Some(days),
+            ..self
+        }
+    }
+}
+pub trait ArchiveTasksUseCase {
+    /// Moves the selected tasks to the archive and returns them.
+    fn execute(&mut self, cmd: ArchiveTasksCommand) -> ApplicationResult<Vec<Task>>;
+}
+pub struct ArchiveTasksService<R: TaskRepository + ArchiveRepository, C: Clock> {
+    repo: R,
+    clock: C,
+}
+
+impl<R: TaskRepository + ArchiveRepository, C: Clock> ArchiveTasksService<R, C> {
+    pub fn new(repo: R, clock: C) -> Self {
+        Self { repo, clock }
+    }
+}
+
+impl<R: TaskRepository + ArchiveRepository, C: Clock> ArchiveTasksUseCase
+    for ArchiveTasksService<R, C>
+{
+    fn execute(&mut self, cmd: ArchiveTasksCommand) -> ApplicationResult<Vec<Task>> {
+        let mut selected: Vec<Task> = Vec::new();
+        for id in cmd.task_ids {
+            let task: Task = self
+                .repo
+                .find_by_id(id)?
+                .ok_or(DomainError::TaskNotFound { id })?;
+            if task.status().is_open() {
+                return Err(ApplicationError::ArchiveOpenTask { id });
+            }
+            selected.push(task);
+        }
+        if let Some(days) = cmd.older_than_days {
+            let cutoff: DateTime<Utc> = Duration::try_days(i64::from(days))
+                .and_then(|age| self.clock.now().checked_sub_signed(age))
+                .ok_or(ApplicationError::AgeOutOfRange { days })?;
+            for task in self.repo.list(TaskQuery::All)? {
+                if !task.status().is_open()
+                    && task.modified_at() <= cutoff
+                    && !selected
+                        .iter()
+                        .any(|stored| stored.task_id() == task.task_id())
+                {
+                    selected.push(task);
+                }
+            }
+        }
+        if selected.is_empty() {
+            return Ok(selected);
+        }
+
+        // Archive first: if removing them from the task store fails halfway,
+        // tasks end up in both places rather than in neither.
+        self.repo.archive(selected.clone())?;
+        for task in &selected {
+            self.repo.delete(task.task_id())?;
+        }
+        Ok(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArchiveTasksCommand, ArchiveTasksService, ArchiveTasksUseCase};
+    use crate::tasks::adapters::clock::fixed_clock::FixedClock;
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use chrono::{Duration, Utc};
+
+    fn new_task(title: &str) -> Task {
+        Task::new(title.to_string()).expect("task should be created")
+    }
+
+    #[test]
+    fn older_than_moves_closed_tasks_untouched_long_enough() {
+        let mut repo = InMemoryTaskRepository::new();
+        let done = new_task("shipped")
+            .mark_done()
+            .expect("done should succeed");
+        let open = new_task("pending");
+        let done = repo.save(done).expect("save should succeed");
+        let open = repo.save(open).expect("save should succeed");
+        let later = FixedClock::at(Utc::now() + Duration::days(31));
+        let mut service = ArchiveTasksService::new(repo, later);
+
+        let archived = service
+            .execute(ArchiveTasksCommand::new().with_older_than(30))
+            .expect("archive should succeed");
+
+        assert_eq!(archived, vec![done.clone()]);
+        assert_eq!(
+            service.repo.list_archived().expect("list should succeed"),
+            vec![done]
+        );
+        assert_eq!(
+            service
+                .repo
+                .list(TaskQuery::All)
+                .expect("list should succeed"),
+            vec![open]
+        );
+    }
+
+    #[test]
+    fn an_age_before_the_earliest_date_is_rejected() {
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(
+            new_task("shipped")
+                .mark_done()
+                .expect("done should succeed"),
+        )
+        .expect("save should succeed");
+        let mut service = ArchiveTasksService::new(repo, FixedClock::at(Utc::now()));
+
+        let result = service.execute(ArchiveTasksCommand::new().with_older_than(4_000_000_000));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::AgeOutOfRange {
+                days: 4_000_000_000
+            })
+        ));
+        assert!(
+            service
+                .repo
+                .list_archived()
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn older_than_skips_recently_closed_tasks() {
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(
+            new_task("just done")
+                .mark_done()
+                .expect("done should succeed"),
+        )
+        .expect("save should succeed");
+        let mut service = ArchiveTasksService::new(repo, FixedClock::at(Utc::now()));
+
+        let archived = service
+            .execute(ArchiveTasksCommand::new().with_older_than(30))
+            .expect("archive should succeed");
+
+        assert!(archived.is_empty());
+    }
+
+    #[test]
+    fn selected_open_task_is_refused_and_nothing_moves() {
+        let mut repo = InMemoryTaskRepository::new();
+        let done = new_task("shipped")
+            .mark_done()
+            .expect("done should succeed");
+        let open = new_task("pending");
+        repo.save(done.clone()).expect("save should succeed");
+        repo.save(open.clone()).expect("save should succeed");
+        let mut service = ArchiveTasksService::new(repo, FixedClock::at(Utc::now()));
+
+        let result = service
+            .execute(ArchiveTasksCommand::new().with_ids(vec![done.task_id(), open.task_id()]));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::ArchiveOpenTask { id }) if id == open.task_id()
+        ));
+        assert!(
+            service
+                .repo
+                .list_archived()
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+}
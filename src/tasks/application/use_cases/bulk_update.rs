@@ -0,0 +1,396 @@
+use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::application::staging::{Checkpoint, StagingRepository};
+use crate::tasks::application::subtasks::SubtaskDonePolicy;
+use crate::tasks::application::use_cases::delete_task::{
+    DeleteTaskCommand, DeleteTaskService, DeleteTaskUseCase,
+};
+use crate::tasks::application::use_cases::edit_task::{
+    EditFields, EditTaskCommand, EditTaskService, EditTaskUseCase,
+};
+use crate::tasks::application::use_cases::list_tasks::{
+    FilterTask, ListTasksCommand, ListTasksService, ListTasksUseCase,
+};
+use crate::tasks::application::use_cases::mark_task_done::{
+    MarkTaskDoneCommand, MarkTaskDoneService, MarkTaskDoneUseCase,
+};
+use crate::tasks::application::use_cases::mark_task_todo::{
+    MarkTaskTodoCommand, MarkTaskTodoService, MarkTaskTodoUseCase,
+};
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::{Task, TaskStatus};
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::clock::Clock;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use uuid::Uuid;
+
+/// What to do to every selected task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkAction {
+    Done(SubtaskDonePolicy),
+    Todo,
+    Delete,
+    /// The same fields are edited on each task in turn.
+    Edit(Box<EditFields>),
+}
+
+/// Applies one action to the given tasks and the tasks matching a filter
+/// expression, all or nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkUpdateCommand {
+    action: BulkAction,
+    ids: Vec<Uuid>,
+    filter: Option<String>,
+    dry_run: bool,
+}
+impl BulkUpdateCommand {
+    pub fn new(action: BulkAction) -> Self {
+        Self {
+            action,
+            ids: Vec::new(),
+            filter: None,
+            dry_run: false,
+        }
+    }
+
+    pub fn with_ids(self, ids: Vec<Uuid>) -> Self {
+        Self { ids, ..self }
+    }
+
+    pub fn with_where(self, filter: String) -> Self {
+        Self {
+            filter: Some(filter),
+            ..self
+        }
+    }
+
+    /// Checks every task but writes nothing.
+    pub fn with_dry_run(self) -> Self {
+        Self {
+            dry_run: true,
+            ..self
+        }
+    }
+}
+
+/// How the action went for one selected task.
+#[derive(Debug)]
+pub struct BulkOutcome {
+    /// The task as it was selected.
+    pub task: Task,
+    /// The task as the action left it, or why it could not be changed.
+    pub result: ApplicationResult<Task>,
+}
+
+#[derive(Debug)]
+pub struct BulkReport {
+    pub outcomes: Vec<BulkOutcome>,
+    pub dry_run: bool,
+    /// Whether the changes were written: only when every task succeeded and
+    /// it was not a dry run.
+    pub applied: bool,
+}
+
+impl BulkReport {
+    pub fn failed(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.result.is_err())
+            .count()
+    }
+}
+
+pub trait BulkUpdateUseCase {
+    fn execute(&mut self, cmd: BulkUpdateCommand) -> ApplicationResult<BulkReport>;
+}
+
+pub struct BulkUpdateService<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + Clone,
+    C: Clock + Clone,
+> {
+    repo: R,
+    clock: C,
+}
+
+impl<R: TaskRepository + ProjectRepository + ArchiveRepository + Clone, C: Clock + Clone>
+    BulkUpdateService<R, C>
+{
+    pub fn new(repo: R, clock: C) -> Self {
+        Self { repo, clock }
+    }
+
+    /// The given tasks first, then those matching the filter in list order,
+    /// each once.
+    fn select(&self, cmd: &BulkUpdateCommand) -> ApplicationResult<Vec<Task>> {
+        let mut selected: Vec<Task> = Vec::new();
+        for id in &cmd.ids {
+            let task: Task = self
+                .repo
+                .find_by_id(*id)?
+                .ok_or(DomainError::TaskNotFound { id: *id })?;
+            selected.push(task);
+        }
+        if let Some(filter) = &cmd.filter {
+            let service: ListTasksService<R, C> =
+                ListTasksService::new(self.repo.clone(), self.clock.clone());
+            selected.extend(
+                service
+                    .execute(ListTasksCommand::new(FilterTask::All).with_where(filter.clone()))?,
+            );
+        }
+        let mut seen: Vec<Uuid> = Vec::new();
+        selected.retain(|task| {
+            let first: bool = !seen.contains(&task.task_id());
+            seen.push(task.task_id());
+            first
+        });
+        Ok(selected)
+    }
+
+    fn apply(
+        &self,
+        staging: &StagingRepository<R>,
+        action: &BulkAction,
+        id: Uuid,
+    ) -> ApplicationResult<Task> {
+        // Subtasks completed or trashed along with an earlier selected task
+        // are already where the action would take them.
+        if let Some(task) = staging.staged_task(id) {
+            match action {
+                BulkAction::Done(_) if task.status() == TaskStatus::Done => return Ok(task),
+                BulkAction::Delete if task.is_trashed() => return Ok(task),
+                _ => {}
+            }
+        }
+        match action {
+            BulkAction::Done(policy) => {
+                let mut service: MarkTaskDoneService<StagingRepository<R>, C> =
+                    MarkTaskDoneService::new(staging.clone(), self.clock.clone());
+                let command = MarkTaskDoneCommand::new(id).with_subtask_policy(*policy);
+                Ok(service.execute(command)?.task)
+            }
+            BulkAction::Todo => {
+                let mut service: MarkTaskTodoService<StagingRepository<R>> =
+                    MarkTaskTodoService::new(staging.clone());
+                service.execute(MarkTaskTodoCommand::new(id))
+            }
+            BulkAction::Delete => {
+                let mut service: DeleteTaskService<StagingRepository<R>, C> =
+                    DeleteTaskService::new(staging.clone(), self.clock.clone());
+                service.execute(DeleteTaskCommand::new(id))?;
+                staging
+                    .staged_task(id)
+                    .ok_or_else(|| DomainError::TaskNotFound { id }.into())
+            }
+            BulkAction::Edit(fields) => {
+                let mut service: EditTaskService<StagingRepository<R>> =
+                    EditTaskService::new(staging.clone());
+                service.execute(EditTaskCommand::new(id, fields.as_ref().clone()))
+            }
+        }
+    }
+}
+
+impl<R: TaskRepository + ProjectRepository + ArchiveRepository + Clone, C: Clock + Clone>
+    BulkUpdateUseCase for BulkUpdateService<R, C>
+{
+    fn execute(&mut self, cmd: BulkUpdateCommand) -> ApplicationResult<BulkReport> {
+        // Every task is changed in a staging area on top of what earlier
+        // tasks changed, so that later ones are checked against the result;
+        // a failing task leaves no trace there.
+        let staging: StagingRepository<R> = StagingRepository::new(self.repo.clone());
+        let mut outcomes: Vec<BulkOutcome> = Vec::new();
+        for task in self.select(&cmd)? {
+            let checkpoint: Checkpoint = staging.checkpoint();
+            let result: ApplicationResult<Task> =
+                match self.apply(&staging, &cmd.action, task.task_id()) {
+                    Err(ApplicationError::Repository(error)) => return Err(error.into()),
+                    result => result,
+                };
+            if result.is_err() {
+                staging.rollback(checkpoint);
+            }
+            outcomes.push(BulkOutcome { task, result });
+        }
+        let applied: bool = !cmd.dry_run && outcomes.iter().all(|outcome| outcome.result.is_ok());
+        if applied {
+            staging.apply_to(&mut self.repo)?;
+        }
+        Ok(BulkReport {
+            outcomes,
+            dry_run: cmd.dry_run,
+            applied,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BulkAction, BulkUpdateCommand, BulkUpdateService, BulkUpdateUseCase};
+    use crate::tasks::adapters::clock::system_clock::SystemClock;
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::application::subtasks::SubtaskDonePolicy;
+    use crate::tasks::application::use_cases::edit_task::EditFields;
+    use crate::tasks::domain::priority::Priority;
+    use crate::tasks::domain::tag::Tag;
+    use crate::tasks::domain::task::{Task, TaskStatus};
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use uuid::Uuid;
+
+    fn saved(repo: &mut InMemoryTaskRepository, task: Task) -> Uuid {
+        let id = task.task_id();
+        repo.save(task).expect("save should succeed");
+        id
+    }
+
+    fn status(
+        service: &BulkUpdateService<InMemoryTaskRepository, SystemClock>,
+        id: Uuid,
+    ) -> TaskStatus {
+        service
+            .repo
+            .find_any(id)
+            .expect("find should succeed")
+            .expect("task should exist")
+            .status()
+    }
+
+    #[test]
+    fn every_selected_task_is_changed() {
+        let mut repo = InMemoryTaskRepository::new();
+        let first = saved(
+            &mut repo,
+            Task::new("a".to_string()).expect("task should be created"),
+        );
+        let second = saved(
+            &mut repo,
+            Task::new("b".to_string()).expect("task should be created"),
+        );
+        let mut service = BulkUpdateService::new(repo, SystemClock);
+
+        let report = service
+            .execute(
+                BulkUpdateCommand::new(BulkAction::Done(SubtaskDonePolicy::Block))
+                    .with_ids(vec![first, second]),
+            )
+            .expect("bulk update should succeed");
+
+        assert!(report.applied);
+        assert_eq!(report.failed(), 0);
+        assert_eq!(status(&service, first), TaskStatus::Done);
+        assert_eq!(status(&service, second), TaskStatus::Done);
+    }
+
+    #[test]
+    fn one_failure_leaves_every_task_unchanged() {
+        let mut repo = InMemoryTaskRepository::new();
+        let done = Task::new("a".to_string())
+            .and_then(Task::mark_done)
+            .expect("task should be created");
+        let done = saved(&mut repo, done);
+        let open = saved(
+            &mut repo,
+            Task::new("b".to_string()).expect("task should be created"),
+        );
+        let mut service = BulkUpdateService::new(repo, SystemClock);
+
+        let report = service
+            .execute(BulkUpdateCommand::new(BulkAction::Todo).with_ids(vec![done, open]))
+            .expect("bulk update should succeed");
+
+        assert!(!report.applied);
+        assert_eq!(report.failed(), 1);
+        assert!(report.outcomes[0].result.is_ok());
+        assert!(report.outcomes[1].result.is_err());
+        assert_eq!(status(&service, done), TaskStatus::Done);
+    }
+
+    #[test]
+    fn dry_run_reports_without_writing() {
+        let mut repo = InMemoryTaskRepository::new();
+        let id = saved(
+            &mut repo,
+            Task::new("a".to_string()).expect("task should be created"),
+        );
+        let mut service = BulkUpdateService::new(repo, SystemClock);
+
+        let report = service
+            .execute(
+                BulkUpdateCommand::new(BulkAction::Delete)
+                    .with_ids(vec![id])
+                    .with_dry_run(),
+            )
+            .expect("bulk update should succeed");
+
+        assert!(!report.applied);
+        let outcome = report.outcomes[0]
+            .result
+            .as_ref()
+            .expect("delete should pass");
+        assert!(outcome.is_trashed());
+        assert!(
+            service
+                .repo
+                .list(TaskQuery::Trashed)
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn where_selects_matching_tasks_for_an_edit() {
+        let mut repo = InMemoryTaskRepository::new();
+        let sprint = Tag::parse("sprint-12").expect("tag should parse");
+        let tagged = Task::new("a".to_string())
+            .and_then(|task| task.add_tag(sprint.clone()))
+            .expect("task should be created");
+        let tagged = saved(&mut repo, tagged);
+        let other = saved(
+            &mut repo,
+            Task::new("b".to_string()).expect("task should be created"),
+        );
+        let mut service = BulkUpdateService::new(repo, SystemClock);
+
+        let edit = EditFields::new().with_priority(Priority::High);
+        let report = service
+            .execute(
+                BulkUpdateCommand::new(BulkAction::Edit(Box::new(edit)))
+                    .with_where("tag:sprint-12".to_string()),
+            )
+            .expect("bulk update should succeed");
+
+        assert_eq!(report.outcomes.len(), 1);
+        let priority = |id| {
+            service
+                .repo
+                .find_any(id)
+                .expect("find should succeed")
+                .expect("task should exist")
+                .priority()
+        };
+        assert_eq!(priority(tagged), Priority::High);
+        assert_eq!(priority(other), Priority::None);
+    }
+
+    #[test]
+    fn subtasks_completed_with_their_parent_count_as_done() {
+        let mut repo = InMemoryTaskRepository::new();
+        let parent = Task::new("release".to_string()).expect("task should be created");
+        let child = Task::new("docs".to_string())
+            .and_then(|task| task.set_parent(Some(parent.task_id())))
+            .expect("task should be created");
+        let parent = saved(&mut repo, parent);
+        let child = saved(&mut repo, child);
+        let mut service = BulkUpdateService::new(repo, SystemClock);
+
+        let report = service
+            .execute(
+                BulkUpdateCommand::new(BulkAction::Done(SubtaskDonePolicy::Cascade))
+                    .with_ids(vec![parent, child]),
+            )
+            .expect("bulk update should succeed");
+
+        assert!(report.applied);
+        assert_eq!(status(&service, child), TaskStatus::Done);
+    }
+}
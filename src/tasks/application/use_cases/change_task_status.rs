@@ -0,0 +1,126 @@
+use crate::tasks::application::dependencies::ensure_unblocked;
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::application::subtasks::{SubtaskDonePolicy, complete_subtasks};
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::{Task, TaskStatus};
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use uuid::Uuid;
+
+/// Moves a task to any status the domain transition table allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeTaskStatusCommand {
+    task_id: Uuid,
+    status: TaskStatus,
+    /// Only used when blocking.
+    reason: Option<String>,
+    expected_version: Option<u64>,
+}
+impl ChangeTaskStatusCommand {
+    pub fn new(task_id: Uuid, status: TaskStatus) -> Self {
+        Self {
+            task_id,
+            status,
+            reason: None,
+            expected_version: None,
+        }
+    }
+
+    pub fn with_reason(self, reason: String) -> Self {
+        Self {
+            reason: Some(reason),
+            ..self
+        }
+    }
+
+    /// Refuses the change with a conflict unless the task is still at
+    /// `version`, the one the caller last saw.
+    pub fn with_expected_version(self, version: u64) -> Self {
+        Self {
+            expected_version: Some(version),
+            ..self
+        }
+    }
+}
+pub trait ChangeTaskStatusUseCase {
+    fn execute(&mut self, cmd: ChangeTaskStatusCommand) -> ApplicationResult<Task>;
+}
+pub struct ChangeTaskStatusService<R: TaskRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository> ChangeTaskStatusService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository> ChangeTaskStatusUseCase for ChangeTaskStatusService<R> {
+    fn execute(&mut self, cmd: ChangeTaskStatusCommand) -> ApplicationResult<Task> {
+        let task_id: Uuid = cmd.task_id;
+        let task: Task = self
+            .repo
+            .find_at_version(task_id, cmd.expected_version)?
+            .ok_or(DomainError::TaskNotFound { id: task_id })?;
+        if matches!(cmd.status, TaskStatus::InProgress | TaskStatus::Done) {
+            ensure_unblocked(&self.repo, &task)?;
+        }
+        if cmd.status == TaskStatus::Done {
+            complete_subtasks(&self.repo, task_id, SubtaskDonePolicy::Block)?;
+        }
+        let task: Task = match cmd.status {
+            TaskStatus::Blocked => task.block(cmd.reason)?,
+            status => task.mark_as(status)?,
+        };
+        Ok(self.repo.save(task)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChangeTaskStatusCommand, ChangeTaskStatusService, ChangeTaskStatusUseCase};
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::domain::errors::DomainError;
+    use crate::tasks::domain::task::{Task, TaskStatus};
+    use crate::tasks::ports::outputs::task_repository::TaskRepository;
+
+    #[test]
+    fn blocking_stores_the_reason() {
+        let mut repo = InMemoryTaskRepository::new();
+        let task = Task::new("deploy".to_string()).expect("task should be created");
+        repo.save(task.clone()).expect("save should succeed");
+        let mut service = ChangeTaskStatusService::new(repo);
+
+        let blocked = service
+            .execute(
+                ChangeTaskStatusCommand::new(task.task_id(), TaskStatus::Blocked)
+                    .with_reason("waiting on QA".to_string()),
+            )
+            .expect("block should succeed");
+
+        assert_eq!(blocked.status(), TaskStatus::Blocked);
+        assert_eq!(blocked.blocked_reason(), Some("waiting on QA"));
+    }
+
+    #[test]
+    fn cancelled_task_cannot_be_started() {
+        let mut repo = InMemoryTaskRepository::new();
+        let task = Task::new("deploy".to_string())
+            .and_then(Task::cancel)
+            .expect("task should be cancelled");
+        repo.save(task.clone()).expect("save should succeed");
+        let mut service = ChangeTaskStatusService::new(repo);
+
+        let result = service.execute(ChangeTaskStatusCommand::new(
+            task.task_id(),
+            TaskStatus::InProgress,
+        ));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(
+                DomainError::InvalidStatusTransition { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,22 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::ports::outputs::backup_repository::{Backup, BackupRepository};
+
+pub trait CreateBackupUseCase {
+    /// Backs the task store up now, whenever the last backup was taken.
+    fn execute(&mut self) -> ApplicationResult<Backup>;
+}
+pub struct CreateBackupService<R: BackupRepository> {
+    repo: R,
+}
+
+impl<R: BackupRepository> CreateBackupService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: BackupRepository> CreateBackupUseCase for CreateBackupService<R> {
+    fn execute(&mut self) -> ApplicationResult<Backup> {
+        Ok(self.repo.create_backup()?)
+    }
+}
@@ -0,0 +1,276 @@
+use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::application::project_lookup::resolve_project_id;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::project::Project;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::clock::Clock;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use uuid::Uuid;
+
+/// What happens to the tasks of a deleted project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeleteProjectPolicy {
+    /// Move the tasks to another project (by name; `inbox` is allowed).
+    MoveTo(String),
+    /// Delete the tasks together with the project.
+    Cascade,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteProjectCommand {
+    name: String,
+    policy: DeleteProjectPolicy,
+}
+impl DeleteProjectCommand {
+    pub fn new(name: String, policy: DeleteProjectPolicy) -> Self {
+        Self { name, policy }
+    }
+}
+
+/// Outcome of a project deletion: how many tasks were moved or deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedProject {
+    pub project: Project,
+    pub moved_tasks: usize,
+    pub deleted_tasks: usize,
+}
+
+pub trait DeleteProjectUseCase {
+    fn execute(&mut self, cmd: DeleteProjectCommand) -> ApplicationResult<DeletedProject>;
+}
+pub struct DeleteProjectService<R: TaskRepository + ProjectRepository, C: Clock> {
+    repo: R,
+    clock: C,
+}
+
+impl<R: TaskRepository + ProjectRepository, C: Clock> DeleteProjectService<R, C> {
+    pub fn new(repo: R, clock: C) -> Self {
+        Self { repo, clock }
+    }
+}
+
+impl<R: TaskRepository + ProjectRepository, C: Clock> DeleteProjectUseCase
+    for DeleteProjectService<R, C>
+{
+    fn execute(&mut self, cmd: DeleteProjectCommand) -> ApplicationResult<DeletedProject> {
+        let project: Project = self.repo.find_project_by_name(&cmd.name)?.ok_or_else(|| {
+            DomainError::ProjectNotFound {
+                name: cmd.name.trim().to_string(),
+            }
+        })?;
+        let project_id: Uuid = project.project_id();
+        let tasks: Vec<Task> = self.repo.list(TaskQuery::ByProject(Some(project_id)))?;
+
+        // Every task is changed in memory and written in one go, so a failure
+        // leaves the project and all of its tasks as they were.
+        let (changed, moved_tasks, deleted_tasks): (Vec<Task>, usize, usize) = match cmd.policy {
+            DeleteProjectPolicy::MoveTo(target) => {
+                let target_id: Option<Uuid> = resolve_project_id(&self.repo, &target)?;
+                if target_id == Some(project_id) {
+                    return Err(ApplicationError::MoveIntoDeletedProject { name: target });
+                }
+                let moved: Vec<Task> = tasks
+                    .into_iter()
+                    .map(|task| task.move_to_project(target_id))
+                    .collect::<Result<Vec<Task>, _>>()?;
+                let count: usize = moved.len();
+                (moved, count, 0)
+            }
+            DeleteProjectPolicy::Cascade => {
+                let now = self.clock.now();
+                let trashed: Vec<Task> = tasks
+                    .into_iter()
+                    .map(|task| task.trash(now))
+                    .collect::<Result<Vec<Task>, _>>()?;
+                let count: usize = trashed.len();
+                (trashed, 0, count)
+            }
+        };
+        self.repo.save_all(changed)?;
+        self.repo.delete_project(project_id)?;
+        Ok(DeletedProject {
+            project,
+            moved_tasks,
+            deleted_tasks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DeleteProjectCommand, DeleteProjectPolicy, DeleteProjectService, DeleteProjectUseCase,
+    };
+    use crate::tasks::adapters::clock::system_clock::SystemClock;
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::domain::project::Project;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::errors::{RepoError, RepoResult};
+    use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use uuid::Uuid;
+
+    /// Lists the task titled `b` one version behind the store, as if
+    /// another process saved it right after it was read.
+    struct StaleList(InMemoryTaskRepository);
+
+    impl TaskRepository for StaleList {
+        fn save(&mut self, task: Task) -> RepoResult<Task> {
+            self.0.save(task)
+        }
+
+        fn save_all(&mut self, tasks: Vec<Task>) -> RepoResult<Vec<Task>> {
+            self.0.save_all(tasks)
+        }
+
+        fn list(&self, query: TaskQuery) -> RepoResult<Vec<Task>> {
+            Ok(self
+                .0
+                .list(query)?
+                .into_iter()
+                .map(|task| match task.title() {
+                    "b" => task.clone().with_version(task.version() - 1),
+                    _ => task,
+                })
+                .collect())
+        }
+
+        fn find_any(&self, id: Uuid) -> RepoResult<Option<Task>> {
+            self.0.find_any(id)
+        }
+
+        fn delete(&mut self, id: Uuid) -> RepoResult<bool> {
+            self.0.delete(id)
+        }
+    }
+
+    impl ProjectRepository for StaleList {
+        fn save_project(&mut self, project: Project) -> RepoResult<()> {
+            self.0.save_project(project)
+        }
+
+        fn list_projects(&self) -> RepoResult<Vec<Project>> {
+            self.0.list_projects()
+        }
+
+        fn find_project_by_id(&self, id: Uuid) -> RepoResult<Option<Project>> {
+            self.0.find_project_by_id(id)
+        }
+
+        fn find_project_by_name(&self, name: &str) -> RepoResult<Option<Project>> {
+            self.0.find_project_by_name(name)
+        }
+
+        fn delete_project(&mut self, id: Uuid) -> RepoResult<bool> {
+            self.0.delete_project(id)
+        }
+    }
+
+    fn repo_with_project_tasks() -> (InMemoryTaskRepository, Project) {
+        let mut repo = InMemoryTaskRepository::new();
+        let project = Project::new("work".to_string()).expect("project should be created");
+        repo.save_project(project.clone())
+            .expect("save should succeed");
+        for title in ["a", "b"] {
+            let task = Task::new(title.to_string())
+                .and_then(|task| task.move_to_project(Some(project.project_id())))
+                .expect("task should be created");
+            repo.save(task).expect("save should succeed");
+        }
+        repo.save(Task::new("inbox task".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        (repo, project)
+    }
+
+    #[test]
+    fn move_policy_reassigns_tasks_to_target() {
+        let (repo, project) = repo_with_project_tasks();
+        let mut service = DeleteProjectService::new(repo.clone(), SystemClock);
+
+        let deleted = service
+            .execute(DeleteProjectCommand::new(
+                "work".to_string(),
+                DeleteProjectPolicy::MoveTo("inbox".to_string()),
+            ))
+            .expect("delete should succeed");
+
+        assert_eq!(deleted.moved_tasks, 2);
+        let inbox = service
+            .repo
+            .list(TaskQuery::ByProject(None))
+            .expect("list should succeed");
+        assert_eq!(inbox.len(), 3);
+        let found = service
+            .repo
+            .find_project_by_id(project.project_id())
+            .expect("find should succeed");
+        assert!(found.is_none());
+    }
+
+    #[test]
+    fn cascade_policy_deletes_tasks() {
+        let (repo, _) = repo_with_project_tasks();
+        let mut service = DeleteProjectService::new(repo, SystemClock);
+
+        let deleted = service
+            .execute(DeleteProjectCommand::new(
+                "WORK".to_string(),
+                DeleteProjectPolicy::Cascade,
+            ))
+            .expect("delete should succeed");
+
+        assert_eq!(deleted.deleted_tasks, 2);
+        let all = service
+            .repo
+            .list(TaskQuery::All)
+            .expect("list should succeed");
+        assert_eq!(all.len(), 1);
+    }
+
+    #[test]
+    fn unknown_move_target_keeps_project_and_tasks() {
+        let (repo, project) = repo_with_project_tasks();
+        let mut service = DeleteProjectService::new(repo, SystemClock);
+
+        let result = service.execute(DeleteProjectCommand::new(
+            "work".to_string(),
+            DeleteProjectPolicy::MoveTo("missing".to_string()),
+        ));
+
+        assert!(result.is_err());
+        let found = service
+            .repo
+            .find_project_by_id(project.project_id())
+            .expect("find should succeed");
+        assert!(found.is_some());
+    }
+
+    #[test]
+    fn a_failed_write_leaves_the_project_and_its_tasks_alone() {
+        let (repo, project) = repo_with_project_tasks();
+        let mut before = repo.list(TaskQuery::All).expect("list should succeed");
+        let mut service = DeleteProjectService::new(StaleList(repo), SystemClock);
+
+        let result = service.execute(DeleteProjectCommand::new(
+            "work".to_string(),
+            DeleteProjectPolicy::Cascade,
+        ));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Repository(RepoError::Conflict { .. }))
+        ));
+        let repo = service.repo.0;
+        let mut after = repo.list(TaskQuery::All).expect("list should succeed");
+        before.sort_by_key(Task::task_id);
+        after.sort_by_key(Task::task_id);
+        assert_eq!(after, before);
+        assert!(
+            repo.find_project_by_id(project.project_id())
+                .expect("find should succeed")
+                .is_some()
+        );
+    }
+}
@@ -1,32 +1,119 @@
 use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::ports::outputs::clock::Clock;
 use crate::tasks::ports::outputs::task_repository::TaskRepository;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DeleteTaskCommand {
     task_id: Uuid,
+    expected_version: Option<u64>,
 }
 impl DeleteTaskCommand {
     pub fn new(task_id: Uuid) -> Self {
-        Self { task_id }
+        Self {
+            task_id,
+            expected_version: None,
+        }
+    }
+
+    /// Refuses the change with a conflict unless the task is still at
+    /// `version`, the one the caller last saw.
+    pub fn with_expected_version(self, version: u64) -> Self {
+        Self {
+            expected_version: Some(version),
+            ..self
+        }
     }
 }
 pub trait DeleteTaskUseCase {
+    /// Moves the task to the trash; `false` when there is no such task.
     fn execute(&mut self, cmd: DeleteTaskCommand) -> ApplicationResult<bool>;
 }
-pub struct DeleteTaskService<R: TaskRepository> {
+pub struct DeleteTaskService<R: TaskRepository, C: Clock> {
     repo: R,
+    clock: C,
 }
 
-impl<R: TaskRepository> DeleteTaskService<R> {
-    pub fn new(repo: R) -> Self {
-        Self { repo }
+impl<R: TaskRepository, C: Clock> DeleteTaskService<R, C> {
+    pub fn new(repo: R, clock: C) -> Self {
+        Self { repo, clock }
     }
 }
 
-impl<R: TaskRepository> DeleteTaskUseCase for DeleteTaskService<R> {
+impl<R: TaskRepository, C: Clock> DeleteTaskUseCase for DeleteTaskService<R, C> {
     fn execute(&mut self, cmd: DeleteTaskCommand) -> ApplicationResult<bool> {
-        let task_id: Uuid = cmd.task_id;
-        Ok(self.repo.delete(task_id)?)
+        // Subtasks go to the trash with their parent, stamped with the same
+        // time so that restoring the parent brings them back too. Dependents
+        // keep their link; they are only unlinked when the trash is emptied.
+        let Some(task) = self
+            .repo
+            .find_at_version(cmd.task_id, cmd.expected_version)?
+        else {
+            return Ok(false);
+        };
+        let now = self.clock.now();
+        for subtask in self.repo.descendants(cmd.task_id)? {
+            self.repo.save(subtask.trash(now)?)?;
+        }
+        self.repo.save(task.trash(now)?)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeleteTaskCommand, DeleteTaskService, DeleteTaskUseCase};
+    use crate::tasks::adapters::clock::fixed_clock::FixedClock;
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn delete_moves_the_task_and_its_subtasks_to_the_trash() {
+        let mut repo = InMemoryTaskRepository::new();
+        let parent = Task::new("release".to_string()).expect("task should be created");
+        let child = Task::new("docs".to_string())
+            .expect("task should be created")
+            .set_parent(Some(parent.task_id()))
+            .expect("parent should be set");
+        repo.save(parent.clone()).expect("save should succeed");
+        repo.save(child).expect("save should succeed");
+        let now = Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap();
+        let mut service = DeleteTaskService::new(repo, FixedClock::at(now));
+
+        let deleted = service
+            .execute(DeleteTaskCommand::new(parent.task_id()))
+            .expect("delete should succeed");
+
+        assert!(deleted);
+        let all = service
+            .repo
+            .list(TaskQuery::All)
+            .expect("list should succeed");
+        let trashed = service
+            .repo
+            .list(TaskQuery::Trashed)
+            .expect("list should succeed");
+        assert!(all.is_empty());
+        assert_eq!(trashed.len(), 2);
+        assert!(trashed.iter().all(|task| task.deleted_at() == Some(now)));
+    }
+
+    #[test]
+    fn deleting_a_trashed_task_again_reports_not_found() {
+        let mut repo = InMemoryTaskRepository::new();
+        let task = Task::new("old".to_string()).expect("task should be created");
+        repo.save(task.clone()).expect("save should succeed");
+        let mut service = DeleteTaskService::new(repo, FixedClock::at(Utc::now()));
+
+        service
+            .execute(DeleteTaskCommand::new(task.task_id()))
+            .expect("delete should succeed");
+        let again = service
+            .execute(DeleteTaskCommand::new(task.task_id()))
+            .expect("delete should succeed");
+
+        assert!(!again);
     }
 }
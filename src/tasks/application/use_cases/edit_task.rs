@@ -1,18 +1,137 @@
 use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::application::project_lookup::resolve_project_id;
+use crate::tasks::application::subtasks::ensure_valid_parent;
+use crate::tasks::domain::due_date::DueDate;
 use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::priority::Priority;
+use crate::tasks::domain::recurrence::Recurrence;
+use crate::tasks::domain::tag::Tag;
 use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
 use crate::tasks::ports::outputs::task_repository::TaskRepository;
 use uuid::Uuid;
 
+/// The fields an edit changes, whichever task it is applied to: only the
+/// fields that are set are applied.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EditFields {
+    new_title: Option<String>,
+    new_priority: Option<Priority>,
+    /// `Some(None)` clears the due date.
+    new_due: Option<Option<DueDate>>,
+    /// `Some(None)` stops the task from recurring.
+    new_recurrence: Option<Option<Recurrence>>,
+    added_tags: Vec<String>,
+    removed_tags: Vec<String>,
+    /// `Some(None)` clears the description.
+    new_description: Option<Option<String>>,
+    /// `Some(None)` turns a subtask back into a top-level task.
+    new_parent: Option<Option<Uuid>>,
+    new_project: Option<String>,
+}
+
+impl EditFields {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_title(self, new_title: String) -> Self {
+        Self {
+            new_title: Some(new_title),
+            ..self
+        }
+    }
+
+    pub fn with_priority(self, new_priority: Priority) -> Self {
+        Self {
+            new_priority: Some(new_priority),
+            ..self
+        }
+    }
+
+    pub fn with_due(self, new_due: Option<DueDate>) -> Self {
+        Self {
+            new_due: Some(new_due),
+            ..self
+        }
+    }
+
+    pub fn with_recurrence(self, new_recurrence: Option<Recurrence>) -> Self {
+        Self {
+            new_recurrence: Some(new_recurrence),
+            ..self
+        }
+    }
+
+    pub fn with_added_tags(self, added_tags: Vec<String>) -> Self {
+        Self { added_tags, ..self }
+    }
+
+    pub fn with_removed_tags(self, removed_tags: Vec<String>) -> Self {
+        Self {
+            removed_tags,
+            ..self
+        }
+    }
+
+    pub fn with_description(self, new_description: Option<String>) -> Self {
+        Self {
+            new_description: Some(new_description),
+            ..self
+        }
+    }
+
+    pub fn with_parent(self, new_parent: Option<Uuid>) -> Self {
+        Self {
+            new_parent: Some(new_parent),
+            ..self
+        }
+    }
+
+    pub fn with_project(self, new_project: String) -> Self {
+        Self {
+            new_project: Some(new_project),
+            ..self
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.new_title.is_none()
+            && self.new_priority.is_none()
+            && self.new_due.is_none()
+            && self.new_recurrence.is_none()
+            && self.added_tags.is_empty()
+            && self.removed_tags.is_empty()
+            && self.new_description.is_none()
+            && self.new_parent.is_none()
+            && self.new_project.is_none()
+    }
+}
+
+/// Partial update of one task.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EditTaskCommand {
     task_id: Uuid,
-    new_title: String,
+    fields: EditFields,
+    expected_version: Option<u64>,
 }
 
 impl EditTaskCommand {
-    pub fn new(task_id: Uuid, new_title: String) -> Self {
-        Self { task_id, new_title }
+    pub fn new(task_id: Uuid, fields: EditFields) -> Self {
+        Self {
+            task_id,
+            fields,
+            expected_version: None,
+        }
+    }
+
+    /// Refuses the change with a conflict unless the task is still at
+    /// `version`, the one the caller last saw.
+    pub fn with_expected_version(self, version: u64) -> Self {
+        Self {
+            expected_version: Some(version),
+            ..self
+        }
     }
 }
 
@@ -20,27 +139,156 @@ pub trait EditTaskUseCase {
     fn execute(&mut self, cmd: EditTaskCommand) -> ApplicationResult<Task>;
 }
 
-pub struct EditTaskService<R: TaskRepository> {
+pub struct EditTaskService<R: TaskRepository + ProjectRepository> {
     repo: R,
 }
 
-impl<R: TaskRepository> EditTaskService<R> {
+impl<R: TaskRepository + ProjectRepository> EditTaskService<R> {
     pub fn new(repo: R) -> Self {
         Self { repo }
     }
 }
 
-impl<R: TaskRepository> EditTaskUseCase for EditTaskService<R> {
+impl<R: TaskRepository + ProjectRepository> EditTaskUseCase for EditTaskService<R> {
     fn execute(&mut self, cmd: EditTaskCommand) -> ApplicationResult<Task> {
-        let task_id: Uuid = cmd.task_id;
-        let task: Option<Task> = self.repo.find_by_id(task_id)?;
-        let task: Task = match task {
+        let EditTaskCommand {
+            task_id,
+            fields,
+            expected_version,
+        } = cmd;
+        if fields.is_empty() {
+            return Err(ApplicationError::NothingToEdit { id: task_id });
+        }
+        let task: Option<Task> = self.repo.find_at_version(task_id, expected_version)?;
+        let mut task: Task = match task {
             None => Err(ApplicationError::Domain(DomainError::TaskNotFound {
                 id: task_id,
             })),
-            Some(task) => Ok(task.edit_title(cmd.new_title)?),
+            Some(task) => Ok(task),
         }?;
-        self.repo.save(task.clone())?;
-        Ok(task)
+        if let Some(new_title) = fields.new_title {
+            task = task.edit_title(new_title)?;
+        }
+        if let Some(new_priority) = fields.new_priority {
+            task = task.set_priority(new_priority)?;
+        }
+        if let Some(new_due) = fields.new_due {
+            task = task.set_due(new_due)?;
+        }
+        if let Some(new_recurrence) = fields.new_recurrence {
+            task = task.set_recurrence(new_recurrence)?;
+        }
+        for tag in fields.added_tags {
+            task = task.add_tag(Tag::parse(&tag)?)?;
+        }
+        for tag in fields.removed_tags {
+            task = task.remove_tag(&Tag::parse(&tag)?)?;
+        }
+        if let Some(new_description) = fields.new_description {
+            task = task.set_description(new_description)?;
+        }
+        if let Some(new_parent) = fields.new_parent {
+            if let Some(parent) = new_parent {
+                ensure_valid_parent(&self.repo, task_id, parent)?;
+            }
+            task = task.set_parent(new_parent)?;
+        }
+        if let Some(new_project) = fields.new_project {
+            task = task.move_to_project(resolve_project_id(&self.repo, &new_project)?)?;
+        }
+        Ok(self.repo.save(task)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EditFields, EditTaskCommand, EditTaskService, EditTaskUseCase};
+    use crate::tasks::adapters::persistence::json_file_task_repository::JsonFileTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::domain::errors::DomainError;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::errors::RepoError;
+    use crate::tasks::ports::outputs::task_repository::TaskRepository;
+    use tempfile::tempdir;
+    use uuid::Uuid;
+
+    #[test]
+    fn reparenting_under_a_descendant_is_a_cycle() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let parent = Task::new("release".to_string()).expect("task should be created");
+        let child = Task::new("changelog".to_string())
+            .and_then(|task| task.set_parent(Some(parent.task_id())))
+            .expect("task should be created");
+        repo.save(parent.clone()).expect("save should succeed");
+        repo.save(child.clone()).expect("save should succeed");
+        let mut service = EditTaskService::new(repo);
+
+        let result = service.execute(EditTaskCommand::new(
+            parent.task_id(),
+            EditFields::new().with_parent(Some(child.task_id())),
+        ));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DomainError::SubtaskCycle { .. }))
+        ));
+    }
+
+    #[test]
+    fn unknown_parent_is_rejected() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let task = Task::new("changelog".to_string()).expect("task should be created");
+        repo.save(task.clone()).expect("save should succeed");
+        let mut service = EditTaskService::new(repo);
+
+        let result = service.execute(EditTaskCommand::new(
+            task.task_id(),
+            EditFields::new().with_parent(Some(Uuid::new_v4())),
+        ));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DomainError::ParentNotFound { .. }))
+        ));
+    }
+
+    #[test]
+    fn an_edit_expecting_an_older_version_is_refused() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let seen = repo
+            .save(Task::new("deploy".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let mut service = EditTaskService::new(repo.clone());
+        service
+            .execute(EditTaskCommand::new(
+                seen.task_id(),
+                EditFields::new().with_title("deploy v2".to_string()),
+            ))
+            .expect("edit should succeed");
+
+        let result = service.execute(
+            EditTaskCommand::new(
+                seen.task_id(),
+                EditFields::new().with_title("ship".to_string()),
+            )
+            .with_expected_version(seen.version()),
+        );
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Repository(RepoError::Conflict {
+                expected: 1,
+                actual: 2,
+                ..
+            }))
+        ));
+        let stored = repo
+            .find_by_id(seen.task_id())
+            .expect("find should succeed")
+            .expect("task should exist");
+        assert_eq!(stored.title(), "deploy v2");
     }
 }
@@ -0,0 +1,209 @@
+use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::clock::Clock;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmptyTrashCommand {
+    older_than_days: Option<u32>,
+}
+impl EmptyTrashCommand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only purges tasks that have been in the trash for at least `days` days.
+    pub fn with_older_than(self, days: u32) -> Self {
+        Self {
+            older_than_days: Some(days),
+        }
+    }
+}
+pub trait EmptyTrashUseCase {
+    /// Deletes trashed tasks for good and returns them.
+    fn execute(&mut self, cmd: EmptyTrashCommand) -> ApplicationResult<Vec<Task>>;
+}
+pub struct EmptyTrashService<R: TaskRepository, C: Clock> {
+    repo: R,
+    clock: C,
+}
+
+impl<R: TaskRepository, C: Clock> EmptyTrashService<R, C> {
+    pub fn new(repo: R, clock: C) -> Self {
+        Self { repo, clock }
+    }
+
+    fn all_tasks(&self) -> ApplicationResult<Vec<Task>> {
+        let mut tasks: Vec<Task> = self.repo.list(TaskQuery::All)?;
+        tasks.extend(self.repo.list(TaskQuery::Trashed)?);
+        Ok(tasks)
+    }
+
+    /// Moves the purged task's subtasks up to its parent and drops it from
+    /// every `blocked_by`, trashed tasks included, so nothing dangles.
+    fn unlink(&mut self, task_id: Uuid) -> ApplicationResult<()> {
+        let parent: Option<Uuid> = self.repo.find_any(task_id)?.and_then(|task| task.parent());
+        for task in self.all_tasks()? {
+            let mut updated: Task = task.clone();
+            if updated.parent() == Some(task_id) {
+                updated = updated.set_parent(parent)?;
+            }
+            if updated.blocked_by().contains(&task_id) {
+                updated = updated.remove_blocker(task_id)?;
+            }
+            if updated != task {
+                self.repo.save(updated)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: TaskRepository, C: Clock> EmptyTrashUseCase for EmptyTrashService<R, C> {
+    fn execute(&mut self, cmd: EmptyTrashCommand) -> ApplicationResult<Vec<Task>> {
+        let cutoff: Option<DateTime<Utc>> = cmd
+            .older_than_days
+            .map(|days| {
+                Duration::try_days(i64::from(days))
+                    .and_then(|age| self.clock.now().checked_sub_signed(age))
+                    .ok_or(ApplicationError::AgeOutOfRange { days })
+            })
+            .transpose()?;
+        let purged: Vec<Task> = self
+            .repo
+            .list(TaskQuery::Trashed)?
+            .into_iter()
+            .filter(|task| match (cutoff, task.deleted_at()) {
+                (Some(cutoff), Some(deleted_at)) => deleted_at <= cutoff,
+                _ => true,
+            })
+            .collect();
+        for task in &purged {
+            self.unlink(task.task_id())?;
+            self.repo.delete(task.task_id())?;
+        }
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EmptyTrashCommand, EmptyTrashService, EmptyTrashUseCase};
+    use crate::tasks::adapters::clock::fixed_clock::FixedClock;
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn new_task(title: &str) -> Task {
+        Task::new(title.to_string()).expect("task should be created")
+    }
+
+    #[test]
+    fn older_than_only_purges_tasks_trashed_long_enough_ago() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap();
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(
+            new_task("ancient")
+                .trash(now - Duration::days(40))
+                .expect("trash should succeed"),
+        )
+        .expect("save should succeed");
+        repo.save(
+            new_task("recent")
+                .trash(now - Duration::days(2))
+                .expect("trash should succeed"),
+        )
+        .expect("save should succeed");
+        let mut service = EmptyTrashService::new(repo, FixedClock::at(now));
+
+        let purged = service
+            .execute(EmptyTrashCommand::new().with_older_than(30))
+            .expect("empty should succeed");
+
+        assert_eq!(purged.len(), 1);
+        assert_eq!(purged[0].title(), "ancient");
+        let trashed = service
+            .repo
+            .list(TaskQuery::Trashed)
+            .expect("list should succeed");
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].title(), "recent");
+    }
+
+    #[test]
+    fn an_age_before_the_earliest_date_is_rejected() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap();
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(
+            new_task("ancient")
+                .trash(now - Duration::days(40))
+                .expect("trash should succeed"),
+        )
+        .expect("save should succeed");
+        let mut service = EmptyTrashService::new(repo, FixedClock::at(now));
+
+        let result = service.execute(EmptyTrashCommand::new().with_older_than(4_000_000_000));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::AgeOutOfRange {
+                days: 4_000_000_000
+            })
+        ));
+        let trashed = service
+            .repo
+            .list(TaskQuery::Trashed)
+            .expect("list should succeed");
+        assert_eq!(trashed.len(), 1);
+    }
+
+    #[test]
+    fn purging_unlinks_subtasks_and_dependents() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap();
+        let mut repo = InMemoryTaskRepository::new();
+        let root = new_task("release");
+        let purged = new_task("draft")
+            .set_parent(Some(root.task_id()))
+            .expect("parent should be set");
+        let child = new_task("outline")
+            .set_parent(Some(purged.task_id()))
+            .expect("parent should be set");
+        let dependent = new_task("publish")
+            .add_blocker(purged.task_id())
+            .expect("blocker should be added");
+        repo.save(root.clone()).expect("save should succeed");
+        repo.save(purged.trash(now).expect("trash should succeed"))
+            .expect("save should succeed");
+        repo.save(child.clone()).expect("save should succeed");
+        repo.save(dependent.clone()).expect("save should succeed");
+        let mut service = EmptyTrashService::new(repo, FixedClock::at(now));
+
+        service
+            .execute(EmptyTrashCommand::new())
+            .expect("empty should succeed");
+
+        let child = service
+            .repo
+            .find_by_id(child.task_id())
+            .expect("find should succeed")
+            .expect("child should exist");
+        let dependent = service
+            .repo
+            .find_by_id(dependent.task_id())
+            .expect("find should succeed")
+            .expect("dependent should exist");
+        assert_eq!(child.parent(), Some(root.task_id()));
+        assert!(dependent.blocked_by().is_empty());
+        assert!(
+            service
+                .repo
+                .list(TaskQuery::Trashed)
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GetTaskCommand {
+    task_id: Uuid,
+}
+impl GetTaskCommand {
+    pub fn new(task_id: Uuid) -> Self {
+        Self { task_id }
+    }
+}
+pub trait GetTaskUseCase {
+    fn execute(&self, cmd: GetTaskCommand) -> ApplicationResult<Task>;
+}
+pub struct GetTaskService<R: TaskRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository> GetTaskService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository> GetTaskUseCase for GetTaskService<R> {
+    fn execute(&self, cmd: GetTaskCommand) -> ApplicationResult<Task> {
+        let task_id: Uuid = cmd.task_id;
+        let task: Option<Task> = self.repo.find_by_id(task_id)?;
+        Ok(task.ok_or(DomainError::TaskNotFound { id: task_id })?)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::ports::outputs::backup_repository::{Backup, BackupRepository};
+use std::cmp::Reverse;
+
+pub trait ListBackupsUseCase {
+    /// Backups of the task store, newest first.
+    fn execute(&self) -> ApplicationResult<Vec<Backup>>;
+}
+pub struct ListBackupsService<R: BackupRepository> {
+    repo: R,
+}
+
+impl<R: BackupRepository> ListBackupsService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: BackupRepository> ListBackupsUseCase for ListBackupsService<R> {
+    fn execute(&self) -> ApplicationResult<Vec<Backup>> {
+        let mut backups: Vec<Backup> = self.repo.list_backups()?;
+        backups.sort_by_key(|backup| Reverse(backup.created_at));
+        Ok(backups)
+    }
+}
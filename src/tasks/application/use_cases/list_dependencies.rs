@@ -0,0 +1,44 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListDependenciesCommand {
+    task_id: Uuid,
+}
+impl ListDependenciesCommand {
+    pub fn new(task_id: Uuid) -> Self {
+        Self { task_id }
+    }
+}
+pub trait ListDependenciesUseCase {
+    /// The tasks blocking the given one, whatever their status.
+    fn execute(&self, cmd: ListDependenciesCommand) -> ApplicationResult<Vec<Task>>;
+}
+pub struct ListDependenciesService<R: TaskRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository> ListDependenciesService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository> ListDependenciesUseCase for ListDependenciesService<R> {
+    fn execute(&self, cmd: ListDependenciesCommand) -> ApplicationResult<Vec<Task>> {
+        let task: Task = self
+            .repo
+            .find_by_id(cmd.task_id)?
+            .ok_or(DomainError::TaskNotFound { id: cmd.task_id })?;
+        let mut blockers: Vec<Task> = Vec::new();
+        for id in task.blocked_by() {
+            if let Some(blocker) = self.repo.find_by_id(*id)? {
+                blockers.push(blocker);
+            }
+        }
+        Ok(blockers)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::project::Project;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+
+/// A project with its task counts; `project` is `None` for the inbox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectSummary {
+    pub project: Option<Project>,
+    pub open_tasks: usize,
+    pub total_tasks: usize,
+}
+
+pub trait ListProjectsUseCase {
+    fn execute(&self) -> ApplicationResult<Vec<ProjectSummary>>;
+}
+pub struct ListProjectsService<R: TaskRepository + ProjectRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository + ProjectRepository> ListProjectsService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository + ProjectRepository> ListProjectsUseCase for ListProjectsService<R> {
+    fn execute(&self) -> ApplicationResult<Vec<ProjectSummary>> {
+        let tasks = self.repo.list(TaskQuery::All)?;
+        let mut projects: Vec<Project> = self.repo.list_projects()?;
+        projects.sort_by_key(|project| project.name().to_lowercase());
+
+        let summarize = |project: Option<Project>| {
+            let id = project.as_ref().map(Project::project_id);
+            let in_project = tasks.iter().filter(|task| task.project() == id);
+            let (open_tasks, total_tasks) = in_project.fold((0, 0), |(open, total), task| {
+                (open + usize::from(task.status().is_open()), total + 1)
+            });
+            ProjectSummary {
+                project,
+                open_tasks,
+                total_tasks,
+            }
+        };
+
+        Ok(std::iter::once(None)
+            .chain(projects.into_iter().map(Some))
+            .map(summarize)
+            .collect())
+    }
+}
@@ -1,47 +1,640 @@
+use crate::tasks::application::dependencies::open_task_ids;
 use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::application::project_lookup::resolve_project_id;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::filter::FilterExpr;
+use crate::tasks::domain::priority::Priority;
+use crate::tasks::domain::tag::Tag;
 use crate::tasks::domain::task::{Task, TaskStatus};
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::clock::Clock;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
 use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ListTasksCommand {
     filter_task: FilterTask,
+    priority: Option<Priority>,
+    due: Option<DueFilter>,
+    all_tags: Vec<String>,
+    any_tags: Vec<String>,
+    project: Option<String>,
+    ready: bool,
+    archived: bool,
+    filter: Option<String>,
+    sort: TaskSort,
+    offset: usize,
+    limit: Option<usize>,
 }
 impl ListTasksCommand {
     pub fn new(filter_task: FilterTask) -> Self {
-        Self { filter_task }
+        Self {
+            filter_task,
+            priority: None,
+            due: None,
+            all_tags: Vec::new(),
+            any_tags: Vec::new(),
+            project: None,
+            ready: false,
+            archived: false,
+            filter: None,
+            sort: TaskSort::default(),
+            offset: 0,
+            limit: None,
+        }
+    }
+
+    pub fn with_priority(self, priority: Priority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+
+    pub fn with_due(self, due: DueFilter) -> Self {
+        Self {
+            due: Some(due),
+            ..self
+        }
+    }
+
+    /// Keeps tasks carrying every one of `tags`.
+    pub fn with_all_tags(self, tags: Vec<String>) -> Self {
+        Self {
+            all_tags: tags,
+            ..self
+        }
+    }
+
+    /// Keeps tasks carrying at least one of `tags`.
+    pub fn with_any_tags(self, tags: Vec<String>) -> Self {
+        Self {
+            any_tags: tags,
+            ..self
+        }
+    }
+
+    pub fn with_project(self, project: String) -> Self {
+        Self {
+            project: Some(project),
+            ..self
+        }
+    }
+
+    /// Keeps open tasks whose blockers are all closed.
+    pub fn with_ready(self) -> Self {
+        Self {
+            ready: true,
+            ..self
+        }
+    }
+
+    /// Lists archived tasks instead of the task store.
+    pub fn with_archived(self) -> Self {
+        Self {
+            archived: true,
+            ..self
+        }
+    }
+
+    /// Keeps tasks matching a filter expression such as
+    /// `status:todo and (tag:backend or priority>=high)`.
+    pub fn with_where(self, filter: String) -> Self {
+        Self {
+            filter: Some(filter),
+            ..self
+        }
+    }
+
+    /// Orders the tasks by `sort`; the default is oldest first.
+    pub fn with_sort(self, sort: TaskSort) -> Self {
+        Self { sort, ..self }
+    }
+
+    /// Skips the first `offset` tasks of the sorted list.
+    pub fn with_offset(self, offset: usize) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Returns at most `limit` tasks, after the offset.
+    pub fn with_limit(self, limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..self
+        }
+    }
+
+    /// Every criterion of the command as a repository query, apart from
+    /// those needing the repository to resolve.
+    fn queries(&self, now: DateTime<Utc>) -> ApplicationResult<Vec<TaskQuery>> {
+        let mut queries: Vec<TaskQuery> = vec![filter_task_to_query(self.filter_task)];
+        if let Some(priority) = self.priority {
+            queries.push(TaskQuery::ByPriority(priority));
+        }
+        if let Some(due) = self.due {
+            queries.push(due_filter_to_query(due, now));
+        }
+        let all_tags: Vec<Tag> = parse_tags(&self.all_tags)?;
+        match all_tags.len() {
+            0 => {}
+            1 => queries.push(TaskQuery::HasTag(all_tags[0].clone())),
+            _ => queries.push(TaskQuery::HasAllTags(all_tags)),
+        }
+        let any_tags: Vec<Tag> = parse_tags(&self.any_tags)?;
+        if !any_tags.is_empty() {
+            queries.push(TaskQuery::HasAnyTag(any_tags));
+        }
+        Ok(queries)
     }
 }
 pub trait ListTasksUseCase {
     fn execute(&self, cmd: ListTasksCommand) -> ApplicationResult<Vec<Task>>;
 }
-pub struct ListTasksService<R: TaskRepository + Clone> {
+pub struct ListTasksService<
+    R: TaskRepository + ProjectRepository + ArchiveRepository + Clone,
+    C: Clock,
+> {
     repo: R,
+    clock: C,
 }
 
-impl<R: TaskRepository + Clone> ListTasksService<R> {
-    pub fn new(repo: R) -> Self {
-        Self { repo }
+impl<R: TaskRepository + ProjectRepository + ArchiveRepository + Clone, C: Clock>
+    ListTasksService<R, C>
+{
+    pub fn new(repo: R, clock: C) -> Self {
+        Self { repo, clock }
     }
 }
 
-impl<R: TaskRepository + Clone> ListTasksUseCase for ListTasksService<R> {
+impl<R: TaskRepository + ProjectRepository + ArchiveRepository + Clone, C: Clock> ListTasksUseCase
+    for ListTasksService<R, C>
+{
     fn execute(&self, cmd: ListTasksCommand) -> ApplicationResult<Vec<Task>> {
-        let task_query: TaskQuery = filter_task_to_query(cmd.filter_task);
-        Ok(self.repo.list(task_query)?)
+        let mut queries: Vec<TaskQuery> = cmd.queries(self.clock.now())?;
+        if let Some(filter) = &cmd.filter {
+            let filter: FilterExpr = FilterExpr::parse(filter)
+                .map_err(DomainError::from)?
+                .resolve_projects(&mut |name: &str| resolve_project_id(&self.repo, name))?;
+            queries.push(TaskQuery::Where {
+                filter,
+                now: self.clock.now(),
+            });
+        }
+        if let Some(project) = &cmd.project {
+            queries.push(TaskQuery::ByProject(resolve_project_id(
+                &self.repo, project,
+            )?));
+        }
+        if cmd.ready {
+            queries.push(TaskQuery::Ready(open_task_ids(&self.repo)?));
+        }
+        let mut tasks: Vec<Task> = if cmd.archived {
+            self.repo
+                .list_archived()?
+                .into_iter()
+                .filter(|task| queries.iter().all(|query| query.matches(task)))
+                .collect()
+        } else {
+            self.repo.list_matching(&queries)?
+        };
+        sort_tasks(&mut tasks, cmd.sort);
+        Ok(tasks
+            .into_iter()
+            .skip(cmd.offset)
+            .take(cmd.limit.unwrap_or(usize::MAX))
+            .collect())
     }
 }
 
 pub fn filter_task_to_query(filter_task: FilterTask) -> TaskQuery {
     match filter_task {
         FilterTask::All => TaskQuery::All,
-        FilterTask::Done => TaskQuery::ByStatus(TaskStatus::Done),
         FilterTask::Todo => TaskQuery::ByStatus(TaskStatus::Todo),
+        FilterTask::InProgress => TaskQuery::ByStatus(TaskStatus::InProgress),
+        FilterTask::Blocked => TaskQuery::ByStatus(TaskStatus::Blocked),
+        FilterTask::Done => TaskQuery::ByStatus(TaskStatus::Done),
+        FilterTask::Cancelled => TaskQuery::ByStatus(TaskStatus::Cancelled),
+    }
+}
+
+fn parse_tags(tags: &[String]) -> ApplicationResult<Vec<Tag>> {
+    Ok(tags
+        .iter()
+        .map(|tag| Tag::parse(tag))
+        .collect::<Result<Vec<Tag>, _>>()?)
+}
+
+pub fn due_filter_to_query(due_filter: DueFilter, now: DateTime<Utc>) -> TaskQuery {
+    match due_filter {
+        DueFilter::Overdue => TaskQuery::Overdue(now),
+        DueFilter::Today => TaskQuery::DueOn(now.date_naive()),
+        DueFilter::Within(days) => TaskQuery::DueWithin { now, days },
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FilterTask {
     All,
-    Done,
     Todo,
+    InProgress,
+    Blocked,
+    Done,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueFilter {
+    Overdue,
+    Today,
+    Within(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    #[default]
+    Created,
+    Modified,
+    /// Case-insensitive.
+    Title,
+    /// In lifecycle order: todo, in progress, blocked, done, cancelled.
+    Status,
+    /// From no priority up to urgent.
+    Priority,
+    /// Earliest deadline first; tasks without a due date always come last.
+    Due,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 6] = [
+        SortKey::Created,
+        SortKey::Modified,
+        SortKey::Title,
+        SortKey::Status,
+        SortKey::Priority,
+        SortKey::Due,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::Created => "created",
+            SortKey::Modified => "modified",
+            SortKey::Title => "title",
+            SortKey::Status => "status",
+            SortKey::Priority => "priority",
+            SortKey::Due => "due",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TaskSort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl TaskSort {
+    pub fn new(key: SortKey) -> Self {
+        Self {
+            key,
+            descending: false,
+        }
+    }
+
+    pub fn reversed(self) -> Self {
+        Self {
+            descending: !self.descending,
+            ..self
+        }
+    }
+}
+
+/// Sorts by the key in the requested direction. Ties are broken by creation
+/// time and then id, always ascending, so the order is the same on every run
+/// whatever order the repository returned.
+pub fn sort_tasks(tasks: &mut [Task], sort: TaskSort) {
+    tasks.sort_by(|a, b| {
+        let by_key: Ordering = match sort.key {
+            SortKey::Created => a.created_at().cmp(&b.created_at()),
+            SortKey::Modified => a.modified_at().cmp(&b.modified_at()),
+            SortKey::Title => a.title().to_lowercase().cmp(&b.title().to_lowercase()),
+            SortKey::Status => status_rank(a.status()).cmp(&status_rank(b.status())),
+            SortKey::Priority => a.priority().cmp(&b.priority()),
+            SortKey::Due => match (a.due(), b.due()) {
+                (Some(a), Some(b)) => a.deadline().cmp(&b.deadline()),
+                (a, b) => {
+                    // Undated tasks go last in either direction.
+                    let undated: Ordering = a.is_none().cmp(&b.is_none());
+                    if sort.descending {
+                        undated.reverse()
+                    } else {
+                        undated
+                    }
+                }
+            },
+        };
+        let by_key: Ordering = if sort.descending {
+            by_key.reverse()
+        } else {
+            by_key
+        };
+        by_key
+            .then_with(|| a.created_at().cmp(&b.created_at()))
+            .then_with(|| a.task_id().cmp(&b.task_id()))
+    });
+}
+
+fn status_rank(status: TaskStatus) -> usize {
+    TaskStatus::ALL
+        .iter()
+        .position(|candidate| *candidate == status)
+        .unwrap_or(TaskStatus::ALL.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DueFilter, FilterTask, ListTasksCommand, ListTasksService, ListTasksUseCase, SortKey,
+        TaskSort,
+    };
+    use crate::tasks::adapters::clock::fixed_clock::FixedClock;
+    use crate::tasks::adapters::clock::system_clock::SystemClock;
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::domain::due_date::DueDate;
+    use crate::tasks::domain::errors::DomainError;
+    use crate::tasks::domain::priority::Priority;
+    use crate::tasks::domain::project::Project;
+    use crate::tasks::domain::tag::Tag;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+    use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+    use crate::tasks::ports::outputs::task_repository::TaskRepository;
+    use chrono::{TimeZone, Utc};
+
+    fn new_task(title: &str, priority: Priority) -> Task {
+        Task::new(title.to_string())
+            .and_then(|task| task.set_priority(priority))
+            .expect("task should be created")
+    }
+
+    #[test]
+    fn combines_status_and_priority_filters() {
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(new_task("urgent todo", Priority::Urgent))
+            .expect("save should succeed");
+        repo.save(new_task("low todo", Priority::Low))
+            .expect("save should succeed");
+        repo.save(
+            new_task("urgent done", Priority::Urgent)
+                .mark_done()
+                .expect("status transition should succeed"),
+        )
+        .expect("save should succeed");
+
+        let service = ListTasksService::new(repo, SystemClock);
+        let tasks = service
+            .execute(ListTasksCommand::new(FilterTask::Todo).with_priority(Priority::Urgent))
+            .expect("list should succeed");
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title(), "urgent todo");
+    }
+
+    fn due_task(title: &str, due: &str) -> Task {
+        Task::new(title.to_string())
+            .and_then(|task| task.set_due(Some(DueDate::parse(due)?)))
+            .expect("task should be created")
+    }
+
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        let mut titles: Vec<&str> = tasks.iter().map(|task| task.title()).collect();
+        titles.sort();
+        titles
+    }
+
+    #[test]
+    fn due_filters_are_evaluated_against_the_clock() {
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(due_task("yesterday", "2026-03-20"))
+            .expect("save should succeed");
+        repo.save(due_task("today", "2026-03-21"))
+            .expect("save should succeed");
+        repo.save(due_task("next week", "2026-03-27"))
+            .expect("save should succeed");
+        repo.save(due_task("next month", "2026-04-21"))
+            .expect("save should succeed");
+        repo.save(new_task("no due date", Priority::None))
+            .expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 12, 0, 0).unwrap());
+        let service = ListTasksService::new(repo, clock);
+
+        let overdue = service
+            .execute(ListTasksCommand::new(FilterTask::All).with_due(DueFilter::Overdue))
+            .expect("list should succeed");
+        let today = service
+            .execute(ListTasksCommand::new(FilterTask::All).with_due(DueFilter::Today))
+            .expect("list should succeed");
+        let within_week = service
+            .execute(ListTasksCommand::new(FilterTask::All).with_due(DueFilter::Within(7)))
+            .expect("list should succeed");
+
+        assert_eq!(titles(&overdue), ["yesterday"]);
+        assert_eq!(titles(&today), ["today"]);
+        assert_eq!(titles(&within_week), ["next week", "today"]);
+    }
+
+    fn tagged_task(title: &str, tags: &[&str]) -> Task {
+        tags.iter()
+            .try_fold(new_task(title, Priority::None), |task, tag| {
+                task.add_tag(Tag::parse(tag)?)
+            })
+            .expect("task should be created")
+    }
+
+    #[test]
+    fn tag_filters_support_all_and_any_semantics() {
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(tagged_task("api", &["backend"]))
+            .expect("save should succeed");
+        repo.save(tagged_task("hotfix", &["backend", "urgent"]))
+            .expect("save should succeed");
+        repo.save(tagged_task("css", &["frontend"]))
+            .expect("save should succeed");
+        let service = ListTasksService::new(repo, SystemClock);
+
+        let backend = service
+            .execute(ListTasksCommand::new(FilterTask::All).with_all_tags(vec!["+Backend".into()]))
+            .expect("list should succeed");
+        let backend_and_urgent = service
+            .execute(
+                ListTasksCommand::new(FilterTask::All)
+                    .with_all_tags(vec!["backend".into(), "urgent".into()]),
+            )
+            .expect("list should succeed");
+        let urgent_or_frontend = service
+            .execute(
+                ListTasksCommand::new(FilterTask::All)
+                    .with_any_tags(vec!["urgent".into(), "frontend".into()]),
+            )
+            .expect("list should succeed");
+
+        assert_eq!(titles(&backend), ["api", "hotfix"]);
+        assert_eq!(titles(&backend_and_urgent), ["hotfix"]);
+        assert_eq!(titles(&urgent_or_frontend), ["css", "hotfix"]);
+    }
+
+    #[test]
+    fn invalid_tag_filter_is_rejected() {
+        let service = ListTasksService::new(InMemoryTaskRepository::new(), SystemClock);
+
+        let result = service.execute(
+            ListTasksCommand::new(FilterTask::All).with_all_tags(vec!["no spaces".into()]),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn ready_keeps_open_tasks_without_open_blockers() {
+        let mut repo = InMemoryTaskRepository::new();
+        let open_blocker = Task::new("design".to_string()).expect("task should be created");
+        let done_blocker = Task::new("research".to_string())
+            .and_then(Task::mark_done)
+            .expect("task should be created");
+        let waiting = Task::new("build".to_string())
+            .and_then(|task| task.add_blocker(open_blocker.task_id()))
+            .expect("task should be created");
+        let unblocked = Task::new("write".to_string())
+            .and_then(|task| task.add_blocker(done_blocker.task_id()))
+            .expect("task should be created");
+        for task in [open_blocker, done_blocker, waiting, unblocked] {
+            repo.save(task).expect("save should succeed");
+        }
+        let service = ListTasksService::new(repo, SystemClock);
+
+        let tasks = service
+            .execute(ListTasksCommand::new(FilterTask::All).with_ready())
+            .expect("list should succeed");
+
+        assert_eq!(titles(&tasks), ["design", "write"]);
+    }
+
+    #[test]
+    fn archived_lists_the_archive_with_the_same_filters() {
+        let mut repo = InMemoryTaskRepository::new();
+        let low = new_task("old low", Priority::Low)
+            .mark_done()
+            .expect("done should succeed");
+        let high = new_task("old high", Priority::High)
+            .mark_done()
+            .expect("done should succeed");
+        repo.archive(vec![low, high.clone()])
+            .expect("archive should succeed");
+        repo.save(new_task("live high", Priority::High))
+            .expect("save should succeed");
+        let service = ListTasksService::new(repo, SystemClock);
+
+        let tasks = service
+            .execute(
+                ListTasksCommand::new(FilterTask::All)
+                    .with_priority(Priority::High)
+                    .with_archived(),
+            )
+            .expect("list should succeed");
+
+        assert_eq!(tasks, vec![high]);
+    }
+
+    #[test]
+    fn where_filters_resolve_projects_and_combine_with_other_filters() {
+        let mut repo = InMemoryTaskRepository::new();
+        let work = Project::new("work".to_string()).expect("project should be created");
+        repo.save_project(work.clone())
+            .expect("save should succeed");
+        let deploy = tagged_task("Deploy API", &["backend"])
+            .move_to_project(Some(work.project_id()))
+            .expect("project should be set");
+        repo.save(deploy).expect("save should succeed");
+        repo.save(tagged_task("deploy docs", &["docs"]))
+            .expect("save should succeed");
+        repo.save(new_task("urgent fix", Priority::Urgent))
+            .expect("save should succeed");
+        let service = ListTasksService::new(repo, SystemClock);
+
+        let in_work = service
+            .execute(
+                ListTasksCommand::new(FilterTask::Todo)
+                    .with_where("project:work and title~deploy".to_string()),
+            )
+            .expect("list should succeed");
+        let either = service
+            .execute(
+                ListTasksCommand::new(FilterTask::All)
+                    .with_where("tag:backend or priority>=high".to_string()),
+            )
+            .expect("list should succeed");
+        let unknown_project = service
+            .execute(ListTasksCommand::new(FilterTask::All).with_where("project:home".to_string()));
+        let invalid = service
+            .execute(ListTasksCommand::new(FilterTask::All).with_where("priority>".to_string()));
+
+        assert_eq!(titles(&in_work), ["Deploy API"]);
+        assert_eq!(titles(&either), ["Deploy API", "urgent fix"]);
+        assert!(unknown_project.is_err());
+        assert!(matches!(
+            invalid,
+            Err(ApplicationError::Domain(DomainError::InvalidFilter(error))) if error.column == 10
+        ));
+    }
+
+    #[test]
+    fn sorts_with_a_stable_tiebreak_and_paginates() {
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(new_task("beta", Priority::High))
+            .expect("save should succeed");
+        repo.save(new_task("Alpha", Priority::Low))
+            .expect("save should succeed");
+        repo.save(new_task("gamma", Priority::High))
+            .expect("save should succeed");
+        repo.save(due_task("delta", "2026-03-27"))
+            .expect("save should succeed");
+        let service = ListTasksService::new(repo, SystemClock);
+        let list = |sort: TaskSort| {
+            service
+                .execute(ListTasksCommand::new(FilterTask::All).with_sort(sort))
+                .expect("list should succeed")
+                .iter()
+                .map(|task| task.title().to_string())
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(
+            list(TaskSort::new(SortKey::Title)),
+            ["Alpha", "beta", "delta", "gamma"]
+        );
+        assert_eq!(
+            list(TaskSort::new(SortKey::Title).reversed()),
+            ["gamma", "delta", "beta", "Alpha"]
+        );
+        let by_priority: Vec<String> = list(TaskSort::new(SortKey::Priority).reversed());
+        assert_eq!(by_priority[2..], ["Alpha", "delta"]);
+        assert_eq!(
+            by_priority,
+            list(TaskSort::new(SortKey::Priority).reversed())
+        );
+        assert_eq!(list(TaskSort::new(SortKey::Due))[0], "delta");
+        assert_eq!(list(TaskSort::new(SortKey::Due).reversed())[0], "delta");
+
+        let page = service
+            .execute(
+                ListTasksCommand::new(FilterTask::All)
+                    .with_sort(TaskSort::new(SortKey::Title))
+                    .with_offset(1)
+                    .with_limit(2),
+            )
+            .expect("list should succeed");
+        assert_eq!(titles(&page), ["beta", "delta"]);
+    }
 }
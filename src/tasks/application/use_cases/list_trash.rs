@@ -0,0 +1,26 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use std::cmp::Reverse;
+
+pub trait ListTrashUseCase {
+    /// Trashed tasks, most recently deleted first.
+    fn execute(&self) -> ApplicationResult<Vec<Task>>;
+}
+pub struct ListTrashService<R: TaskRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository> ListTrashService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository> ListTrashUseCase for ListTrashService<R> {
+    fn execute(&self) -> ApplicationResult<Vec<Task>> {
+        let mut tasks: Vec<Task> = self.repo.list(TaskQuery::Trashed)?;
+        tasks.sort_by_key(|task| Reverse(task.deleted_at()));
+        Ok(tasks)
+    }
+}
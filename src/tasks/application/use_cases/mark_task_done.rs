@@ -1,42 +1,369 @@
+use crate::tasks::application::dependencies::ensure_unblocked;
 use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::application::subtasks::{SubtaskDonePolicy, complete_subtasks};
 use crate::tasks::domain::errors::DomainError;
 use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::clock::Clock;
+use crate::tasks::ports::outputs::errors::RepoError;
 use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use chrono::NaiveDate;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MarkTaskDoneCommand {
     task_id: Uuid,
+    subtask_policy: SubtaskDonePolicy,
+    expected_version: Option<u64>,
 }
 impl MarkTaskDoneCommand {
     pub fn new(task_id: Uuid) -> Self {
-        Self { task_id }
+        Self {
+            task_id,
+            subtask_policy: SubtaskDonePolicy::default(),
+            expected_version: None,
+        }
+    }
+
+    pub fn with_subtask_policy(self, subtask_policy: SubtaskDonePolicy) -> Self {
+        Self {
+            subtask_policy,
+            ..self
+        }
+    }
+
+    /// Refuses the change with a conflict unless the task is still at
+    /// `version`, the one the caller last saw.
+    pub fn with_expected_version(self, version: u64) -> Self {
+        Self {
+            expected_version: Some(version),
+            ..self
+        }
     }
 }
+/// A completed task and, for recurring tasks, the occurrence spawned in its
+/// place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletedTask {
+    pub task: Task,
+    pub next_occurrence: Option<Task>,
+}
+
 pub trait MarkTaskDoneUseCase {
-    fn execute(&mut self, cmd: MarkTaskDoneCommand) -> ApplicationResult<Task>;
+    fn execute(&mut self, cmd: MarkTaskDoneCommand) -> ApplicationResult<CompletedTask>;
 }
-pub struct MarkTaskDoneService<R: TaskRepository> {
+pub struct MarkTaskDoneService<R: TaskRepository, C: Clock> {
     repo: R,
+    clock: C,
 }
 
-impl<R: TaskRepository> MarkTaskDoneService<R> {
-    pub fn new(repo: R) -> Self {
-        Self { repo }
+impl<R: TaskRepository, C: Clock> MarkTaskDoneService<R, C> {
+    pub fn new(repo: R, clock: C) -> Self {
+        Self { repo, clock }
     }
 }
 
-impl<R: TaskRepository> MarkTaskDoneUseCase for MarkTaskDoneService<R> {
-    fn execute(&mut self, cmd: MarkTaskDoneCommand) -> ApplicationResult<Task> {
+/// A completed task and, when it recurs, its next occurrence. The rule
+/// moves to the new occurrence so that reopening and completing the old one
+/// again does not spawn a second copy.
+fn complete(task: Task, today: NaiveDate) -> ApplicationResult<(Task, Option<Task>)> {
+    match task.next_occurrence(today) {
+        None => Ok((task, None)),
+        Some(next) => Ok((task.set_recurrence(None)?, Some(next))),
+    }
+}
+
+impl<R: TaskRepository, C: Clock> MarkTaskDoneUseCase for MarkTaskDoneService<R, C> {
+    fn execute(&mut self, cmd: MarkTaskDoneCommand) -> ApplicationResult<CompletedTask> {
         let task_id: Uuid = cmd.task_id;
-        let task: Option<Task> = self.repo.find_by_id(task_id)?;
+        let task: Option<Task> = self.repo.find_at_version(task_id, cmd.expected_version)?;
         let task: Task = match task {
             None => Err(ApplicationError::Domain(DomainError::TaskNotFound {
                 id: task_id,
             })),
-            Some(task) => Ok(task.mark_done()?),
+            Some(task) => {
+                ensure_unblocked(&self.repo, &task)?;
+                Ok(task.mark_done()?)
+            }
         }?;
-        self.repo.save(task.clone())?;
-        Ok(task)
+        // Subtasks, the task and every next occurrence are written together,
+        // so a conflict on any of them changes nothing.
+        let today: NaiveDate = self.clock.now().date_naive();
+        let mut batch: Vec<Task> = Vec::new();
+        for subtask in complete_subtasks(&self.repo, task_id, cmd.subtask_policy)? {
+            let (subtask, next) = complete(subtask, today)?;
+            batch.push(subtask);
+            batch.extend(next);
+        }
+        let (task, next) = complete(task, today)?;
+        let recurs: bool = next.is_some();
+        batch.push(task);
+        batch.extend(next);
+        let mut saved: Vec<Task> = self.repo.save_all(batch)?;
+        let next_occurrence: Option<Task> = if recurs { saved.pop() } else { None };
+        let task: Task = saved.pop().ok_or_else(|| RepoError::InternalError {
+            error: "save_all did not return every task it was given".to_string(),
+        })?;
+        Ok(CompletedTask {
+            task,
+            next_occurrence,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MarkTaskDoneCommand, MarkTaskDoneService, MarkTaskDoneUseCase};
+    use crate::tasks::adapters::clock::fixed_clock::FixedClock;
+    use crate::tasks::adapters::clock::system_clock::SystemClock;
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::adapters::persistence::json_file_task_repository::JsonFileTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::application::subtasks::SubtaskDonePolicy;
+    use crate::tasks::domain::due_date::DueDate;
+    use crate::tasks::domain::errors::DomainError;
+    use crate::tasks::domain::recurrence::Recurrence;
+    use crate::tasks::domain::task::{Task, TaskStatus};
+    use crate::tasks::ports::outputs::errors::{RepoError, RepoResult};
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use chrono::{TimeZone, Utc};
+    use tempfile::tempdir;
+    use uuid::Uuid;
+
+    /// Hands out tasks one version behind the store, as if another process
+    /// saved them right after they were read.
+    #[derive(Clone)]
+    struct StaleReads(InMemoryTaskRepository);
+
+    impl TaskRepository for StaleReads {
+        fn save(&mut self, task: Task) -> RepoResult<Task> {
+            self.0.save(task)
+        }
+
+        fn save_all(&mut self, tasks: Vec<Task>) -> RepoResult<Vec<Task>> {
+            self.0.save_all(tasks)
+        }
+
+        fn list(&self, query: TaskQuery) -> RepoResult<Vec<Task>> {
+            self.0.list(query)
+        }
+
+        fn find_any(&self, id: Uuid) -> RepoResult<Option<Task>> {
+            Ok(self
+                .0
+                .find_any(id)?
+                .map(|task| task.clone().with_version(task.version() - 1)))
+        }
+
+        fn delete(&mut self, id: Uuid) -> RepoResult<bool> {
+            self.0.delete(id)
+        }
+    }
+
+    fn family(repo: &mut JsonFileTaskRepository) -> (Task, Task, Task) {
+        let parent = Task::new("release".to_string()).expect("task should be created");
+        let child = Task::new("changelog".to_string())
+            .and_then(|task| task.set_parent(Some(parent.task_id())))
+            .expect("task should be created");
+        let grandchild = Task::new("draft".to_string())
+            .and_then(|task| task.set_parent(Some(child.task_id())))
+            .expect("task should be created");
+        for task in [&parent, &child, &grandchild] {
+            repo.save(task.clone()).expect("save should succeed");
+        }
+        (parent, child, grandchild)
+    }
+
+    #[test]
+    fn block_policy_refuses_parent_with_open_subtasks() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let (parent, _, _) = family(&mut repo);
+        let mut service = MarkTaskDoneService::new(repo.clone(), SystemClock);
+
+        let result = service.execute(MarkTaskDoneCommand::new(parent.task_id()));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DomainError::OpenSubtasks {
+                open: 2,
+                ..
+            }))
+        ));
+        let found = repo
+            .find_by_id(parent.task_id())
+            .expect("find should succeed")
+            .expect("task should exist");
+        assert_eq!(found.status(), TaskStatus::Todo);
+    }
+
+    #[test]
+    fn cascade_policy_completes_all_descendants() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let (parent, child, grandchild) = family(&mut repo);
+        let mut service = MarkTaskDoneService::new(repo.clone(), SystemClock);
+
+        service
+            .execute(
+                MarkTaskDoneCommand::new(parent.task_id())
+                    .with_subtask_policy(SubtaskDonePolicy::Cascade),
+            )
+            .expect("done should succeed");
+
+        for task in [parent, child, grandchild] {
+            let found = repo
+                .find_by_id(task.task_id())
+                .expect("find should succeed")
+                .expect("task should exist");
+            assert_eq!(found.status(), TaskStatus::Done);
+        }
+    }
+
+    #[test]
+    fn open_blockers_are_listed_when_completing() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let blocker = Task::new("design".to_string()).expect("task should be created");
+        let finished = Task::new("research".to_string())
+            .and_then(Task::mark_done)
+            .expect("task should be created");
+        let task = Task::new("build".to_string())
+            .and_then(|task| task.add_blocker(blocker.task_id()))
+            .and_then(|task| task.add_blocker(finished.task_id()))
+            .expect("task should be created");
+        for task in [&blocker, &finished, &task] {
+            repo.save(task.clone()).expect("save should succeed");
+        }
+        let mut service = MarkTaskDoneService::new(repo.clone(), SystemClock);
+
+        let result = service.execute(MarkTaskDoneCommand::new(task.task_id()));
+
+        match result {
+            Err(ApplicationError::Domain(DomainError::OpenBlockers { id, blockers })) => {
+                assert_eq!(id, task.task_id());
+                assert_eq!(blockers, vec![blocker.task_id()]);
+            }
+            other => panic!("expected open blockers error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn completing_a_recurring_task_spawns_the_next_occurrence() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let report = Task::new("weekly report".to_string())
+            .and_then(|task| task.set_due(Some(DueDate::parse("2026-03-20")?)))
+            .and_then(|task| task.set_recurrence(Some(Recurrence::parse("weekly")?)))
+            .expect("task should be created");
+        repo.save(report.clone()).expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut service = MarkTaskDoneService::new(repo.clone(), clock);
+
+        let completed = service
+            .execute(MarkTaskDoneCommand::new(report.task_id()))
+            .expect("done should succeed");
+
+        let next = completed.next_occurrence.expect("task should recur");
+        assert_eq!(
+            next.due(),
+            Some(DueDate::parse("2026-03-27").expect("should parse"))
+        );
+        assert_eq!(next.status(), TaskStatus::Todo);
+        assert!(completed.task.recurrence().is_none());
+        assert_eq!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .len(),
+            2
+        );
+
+        let reopened = completed.task.mark_todo().expect("task should reopen");
+        repo.save(reopened).expect("save should succeed");
+        let again = service
+            .execute(MarkTaskDoneCommand::new(report.task_id()))
+            .expect("done should succeed");
+        assert!(again.next_occurrence.is_none());
+    }
+
+    #[test]
+    fn finished_series_do_not_spawn() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let invoice = Task::new("invoice".to_string())
+            .and_then(|task| task.set_due(Some(DueDate::parse("2026-03-31")?)))
+            .and_then(|task| {
+                task.set_recurrence(Some(Recurrence::parse("FREQ=MONTHLY;UNTIL=20260415")?))
+            })
+            .expect("task should be created");
+        repo.save(invoice.clone()).expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 31, 9, 0, 0).unwrap());
+        let mut service = MarkTaskDoneService::new(repo.clone(), clock);
+
+        let completed = service
+            .execute(MarkTaskDoneCommand::new(invoice.task_id()))
+            .expect("done should succeed");
+
+        assert!(completed.next_occurrence.is_none());
+        assert_eq!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn a_conflict_on_the_completed_task_spawns_no_occurrence() {
+        let mut repo = InMemoryTaskRepository::new();
+        let report = Task::new("weekly report".to_string())
+            .and_then(|task| task.set_due(Some(DueDate::parse("2026-03-20")?)))
+            .and_then(|task| task.set_recurrence(Some(Recurrence::parse("weekly")?)))
+            .expect("task should be created");
+        let report = repo.save(report).expect("save should succeed");
+        let clock = FixedClock::at(Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap());
+        let mut service = MarkTaskDoneService::new(StaleReads(repo), clock);
+
+        let result = service.execute(MarkTaskDoneCommand::new(report.task_id()));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Repository(RepoError::Conflict { .. }))
+        ));
+        let stored = service
+            .repo
+            .0
+            .list(TaskQuery::All)
+            .expect("list should succeed");
+        assert_eq!(stored, vec![report]);
+    }
+
+    #[test]
+    fn a_conflict_on_the_parent_leaves_every_subtask_open() {
+        let mut repo = InMemoryTaskRepository::new();
+        let parent = repo
+            .save(Task::new("release".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let child = Task::new("changelog".to_string())
+            .and_then(|task| task.set_parent(Some(parent.task_id())))
+            .expect("task should be created");
+        let child = repo.save(child).expect("save should succeed");
+        let mut service = MarkTaskDoneService::new(StaleReads(repo), SystemClock);
+
+        let result = service.execute(
+            MarkTaskDoneCommand::new(parent.task_id())
+                .with_subtask_policy(SubtaskDonePolicy::Cascade),
+        );
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Repository(RepoError::Conflict { .. }))
+        ));
+        let stored = service
+            .repo
+            .0
+            .find_by_id(child.task_id())
+            .expect("find should succeed")
+            .expect("task should exist");
+        assert_eq!(stored, child);
     }
 }
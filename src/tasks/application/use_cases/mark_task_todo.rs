@@ -36,7 +36,6 @@ impl<R: TaskRepository> MarkTaskTodoUseCase for MarkTaskTodoService<R> {
             })),
             Some(task) => Ok(task.mark_todo()?),
         }?;
-        self.repo.save(task.clone())?;
-        Ok(task)
+        Ok(self.repo.save(task)?)
     }
 }
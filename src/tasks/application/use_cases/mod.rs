@@ -1,6 +1,28 @@
+pub mod add_dependency;
+pub mod add_project;
 pub mod add_task;
+pub mod archive_tasks;
+pub mod bulk_update;
+pub mod change_task_status;
+pub mod create_backup;
+pub mod delete_project;
 pub mod delete_task;
 pub mod edit_task;
+pub mod empty_trash;
+pub mod get_task;
+pub mod list_backups;
+pub mod list_dependencies;
+pub mod list_projects;
 pub mod list_tasks;
+pub mod list_trash;
 pub mod mark_task_done;
 pub mod mark_task_todo;
+pub mod redo;
+pub mod remove_dependency;
+pub mod rename_project;
+pub mod resolve_task_id;
+pub mod restore_backup;
+pub mod restore_task;
+pub mod search_tasks;
+pub mod unarchive_task;
+pub mod undo;
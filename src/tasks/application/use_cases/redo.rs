@@ -0,0 +1,43 @@
+use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::application::journal::{reapply, revert};
+use crate::tasks::domain::journal::{Journal, JournalEntry};
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+
+pub trait RedoUseCase {
+    /// Applies the latest undone operation again and returns it.
+    fn execute(&mut self) -> ApplicationResult<JournalEntry>;
+}
+pub struct RedoService<R: TaskRepository + ArchiveRepository + JournalRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository + ArchiveRepository + JournalRepository> RedoService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository + ArchiveRepository + JournalRepository> RedoUseCase for RedoService<R> {
+    /// As with undo, a task changed since the undo is a conflict, and the
+    /// entry only moves back to the undo stack once it has been reapplied.
+    /// If it is no longer the next one to redo, the redo is reverted and
+    /// fails with [`ApplicationError::JournalChanged`].
+    fn execute(&mut self) -> ApplicationResult<JournalEntry> {
+        let journal: Journal = self.repo.load_journal()?;
+        let entry: JournalEntry = journal
+            .next_redo()
+            .cloned()
+            .ok_or(ApplicationError::NothingToRedo)?;
+        let redone: JournalEntry = reapply(&mut self.repo, &entry)?;
+        let moved: bool = self
+            .repo
+            .update_journal(|journal| journal.mark_redone(&entry, redone.clone()))?;
+        if !moved {
+            revert(&mut self.repo, &redone)?;
+            return Err(ApplicationError::JournalChanged);
+        }
+        Ok(entry)
+    }
+}
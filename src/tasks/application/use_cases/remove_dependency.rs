@@ -0,0 +1,42 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoveDependencyCommand {
+    task_id: Uuid,
+    blocker_id: Uuid,
+}
+impl RemoveDependencyCommand {
+    pub fn new(task_id: Uuid, blocker_id: Uuid) -> Self {
+        Self {
+            task_id,
+            blocker_id,
+        }
+    }
+}
+pub trait RemoveDependencyUseCase {
+    fn execute(&mut self, cmd: RemoveDependencyCommand) -> ApplicationResult<Task>;
+}
+pub struct RemoveDependencyService<R: TaskRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository> RemoveDependencyService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository> RemoveDependencyUseCase for RemoveDependencyService<R> {
+    fn execute(&mut self, cmd: RemoveDependencyCommand) -> ApplicationResult<Task> {
+        let task: Task = self
+            .repo
+            .find_by_id(cmd.task_id)?
+            .ok_or(DomainError::TaskNotFound { id: cmd.task_id })?;
+        let task: Task = task.remove_blocker(cmd.blocker_id)?;
+        Ok(self.repo.save(task)?)
+    }
+}
@@ -0,0 +1,48 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::project::Project;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameProjectCommand {
+    name: String,
+    new_name: String,
+}
+impl RenameProjectCommand {
+    pub fn new(name: String, new_name: String) -> Self {
+        Self { name, new_name }
+    }
+}
+pub trait RenameProjectUseCase {
+    fn execute(&mut self, cmd: RenameProjectCommand) -> ApplicationResult<Project>;
+}
+pub struct RenameProjectService<R: ProjectRepository> {
+    repo: R,
+}
+
+impl<R: ProjectRepository> RenameProjectService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: ProjectRepository> RenameProjectUseCase for RenameProjectService<R> {
+    fn execute(&mut self, cmd: RenameProjectCommand) -> ApplicationResult<Project> {
+        let project: Project = self.repo.find_project_by_name(&cmd.name)?.ok_or_else(|| {
+            DomainError::ProjectNotFound {
+                name: cmd.name.trim().to_string(),
+            }
+        })?;
+        let renamed: Project = project.rename(cmd.new_name)?;
+        if let Some(existing) = self.repo.find_project_by_name(renamed.name())?
+            && existing.project_id() != renamed.project_id()
+        {
+            return Err(DomainError::DuplicateProjectName {
+                name: renamed.name().to_string(),
+            }
+            .into());
+        }
+        self.repo.save_project(renamed.clone())?;
+        Ok(renamed)
+    }
+}
@@ -0,0 +1,382 @@
+use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::application::use_cases::list_tasks::{TaskSort, sort_tasks};
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Shortest prefix shown for a task id, even when fewer characters would be
+/// unique.
+pub const MIN_PREFIX_LENGTH: usize = 4;
+
+/// How a task is referred to on the command line: its full id, a unique
+/// prefix of it, or its 1-based number in the list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskRef {
+    Id(Uuid),
+    /// All digits without a leading zero. Read as an id prefix only when no
+    /// task has that number; [`ShortIds`] only hands out prefixes
+    /// containing a letter or a dash, so the prefixes it shows never clash.
+    Index(usize),
+    /// Lowercase hex digits and dashes.
+    Prefix(String),
+}
+
+impl TaskRef {
+    pub fn parse(value: &str) -> ApplicationResult<Self> {
+        let value: String = value.trim().to_lowercase();
+        if let Ok(id) = Uuid::parse_str(&value) {
+            return Ok(TaskRef::Id(id));
+        }
+        let digits: bool = !value.is_empty() && value.chars().all(|c| c.is_ascii_digit());
+        if digits
+            && !value.starts_with('0')
+            && let Ok(index) = value.parse::<usize>()
+        {
+            return Ok(TaskRef::Index(index));
+        }
+        if !value.is_empty() && value.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+            return Ok(TaskRef::Prefix(value));
+        }
+        Err(ApplicationError::InvalidTaskRef { value })
+    }
+}
+
+impl FromStr for TaskRef {
+    type Err = ApplicationError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        TaskRef::parse(value)
+    }
+}
+
+impl std::fmt::Display for TaskRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskRef::Id(id) => write!(f, "{id}"),
+            TaskRef::Index(index) => write!(f, "{index}"),
+            TaskRef::Prefix(prefix) => write!(f, "{prefix}"),
+        }
+    }
+}
+
+/// Which tasks a reference is looked up among, and in which order they are
+/// numbered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdScope {
+    /// The task list, oldest first, as `todo list` shows it by default.
+    #[default]
+    Tasks,
+    /// The trash, most recently deleted first.
+    Trash,
+    /// The archive, oldest first.
+    Archive,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveTaskIdCommand {
+    reference: TaskRef,
+    scope: IdScope,
+}
+impl ResolveTaskIdCommand {
+    pub fn new(reference: TaskRef) -> Self {
+        Self {
+            reference,
+            scope: IdScope::default(),
+        }
+    }
+
+    pub fn with_scope(self, scope: IdScope) -> Self {
+        Self { scope, ..self }
+    }
+}
+pub trait ResolveTaskIdUseCase {
+    /// A full id is returned as given, so that the command using it reports
+    /// a missing task the usual way.
+    fn execute(&self, cmd: ResolveTaskIdCommand) -> ApplicationResult<Uuid>;
+}
+pub struct ResolveTaskIdService<R: TaskRepository + ArchiveRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository + ArchiveRepository> ResolveTaskIdService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository + ArchiveRepository> ResolveTaskIdUseCase for ResolveTaskIdService<R> {
+    fn execute(&self, cmd: ResolveTaskIdCommand) -> ApplicationResult<Uuid> {
+        match &cmd.reference {
+            TaskRef::Id(id) => Ok(*id),
+            TaskRef::Index(index) => {
+                let tasks: Vec<Task> = scope_tasks(&self.repo, cmd.scope)?;
+                match index
+                    .checked_sub(1)
+                    .and_then(|position| tasks.get(position))
+                {
+                    Some(task) => Ok(task.task_id()),
+                    // Digits copied from the start of a full id.
+                    None => match_prefix(&tasks, &index.to_string()),
+                }
+            }
+            TaskRef::Prefix(prefix) => match_prefix(&scope_tasks(&self.repo, cmd.scope)?, prefix),
+        }
+    }
+}
+
+fn match_prefix(tasks: &[Task], prefix: &str) -> ApplicationResult<Uuid> {
+    let mut candidates: Vec<Uuid> = tasks
+        .iter()
+        .map(Task::task_id)
+        .filter(|id| id.to_string().starts_with(prefix))
+        .collect();
+    match candidates.len() {
+        0 => Err(ApplicationError::TaskRefNotFound {
+            reference: prefix.to_string(),
+        }),
+        1 => Ok(candidates[0]),
+        _ => {
+            candidates.sort();
+            Err(ApplicationError::AmbiguousTaskRef {
+                reference: prefix.to_string(),
+                candidates,
+            })
+        }
+    }
+}
+
+fn scope_tasks<R: TaskRepository + ArchiveRepository>(
+    repo: &R,
+    scope: IdScope,
+) -> ApplicationResult<Vec<Task>> {
+    let mut tasks: Vec<Task> = match scope {
+        IdScope::Tasks => repo.list(TaskQuery::All)?,
+        IdScope::Trash => repo.list(TaskQuery::Trashed)?,
+        IdScope::Archive => repo.list_archived()?,
+    };
+    sort_tasks(&mut tasks, TaskSort::default());
+    if scope == IdScope::Trash {
+        tasks.sort_by_key(|task| Reverse(task.deleted_at()));
+    }
+    Ok(tasks)
+}
+
+/// Short forms of task ids for display: the shortest prefix telling each id
+/// apart from every other task, trashed or archived, and the number of each
+/// task in the task list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShortIds {
+    prefixes: HashMap<Uuid, String>,
+    indexes: HashMap<Uuid, usize>,
+}
+
+impl ShortIds {
+    pub fn new(ids: &[Uuid], numbered: &[Uuid]) -> Self {
+        let mut sorted: Vec<(String, Uuid)> = ids.iter().map(|id| (id.to_string(), *id)).collect();
+        sorted.sort();
+        sorted.dedup();
+        let common =
+            |a: &str, b: &str| a.chars().zip(b.chars()).take_while(|(x, y)| x == y).count();
+        let prefixes: HashMap<Uuid, String> = sorted
+            .iter()
+            .enumerate()
+            .map(|(position, (id, uuid))| {
+                let before: usize = position
+                    .checked_sub(1)
+                    .map_or(0, |previous| common(id, &sorted[previous].0));
+                let after: usize = sorted
+                    .get(position + 1)
+                    .map_or(0, |(next, _)| common(id, next));
+                let mut length: usize = (before.max(after) + 1).max(MIN_PREFIX_LENGTH);
+                // An all-digit prefix would be read as a list number.
+                while id[..length].chars().all(|c| c.is_ascii_digit()) {
+                    length += 1;
+                }
+                (*uuid, id[..length].to_string())
+            })
+            .collect();
+        let indexes: HashMap<Uuid, usize> = numbered
+            .iter()
+            .enumerate()
+            .map(|(position, id)| (*id, position + 1))
+            .collect();
+        Self { prefixes, indexes }
+    }
+
+    /// The full id when the id is not known.
+    pub fn prefix(&self, id: Uuid) -> String {
+        self.prefixes
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    /// Number of the task in the task list, if it is in it.
+    pub fn index(&self, id: Uuid) -> Option<usize> {
+        self.indexes.get(&id).copied()
+    }
+}
+
+/// Short forms of every task id in the store, the trash and the archive.
+pub fn short_ids<R: TaskRepository + ArchiveRepository>(repo: &R) -> ApplicationResult<ShortIds> {
+    let numbered: Vec<Uuid> = scope_tasks(repo, IdScope::Tasks)?
+        .iter()
+        .map(Task::task_id)
+        .collect();
+    let mut ids: Vec<Uuid> = numbered.clone();
+    for scope in [IdScope::Trash, IdScope::Archive] {
+        ids.extend(scope_tasks(repo, scope)?.iter().map(Task::task_id));
+    }
+    Ok(ShortIds::new(&ids, &numbered))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        IdScope, ResolveTaskIdCommand, ResolveTaskIdService, ResolveTaskIdUseCase, ShortIds,
+        TaskRef, short_ids,
+    };
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::task_repository::TaskRepository;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn id(text: &str) -> Uuid {
+        Uuid::parse_str(text).expect("test id should parse")
+    }
+
+    /// Tasks with chosen ids, to control which prefixes they share.
+    fn task_with_id(id: Uuid, title: &str) -> Task {
+        let task = Task::new(title.to_string()).expect("task should be created");
+        let mut value = serde_json::to_value(task).expect("task should serialize");
+        value["id"] = serde_json::Value::String(id.to_string());
+        serde_json::from_value(value).expect("task should deserialize")
+    }
+
+    #[test]
+    fn parses_ids_numbers_and_prefixes() {
+        let full = Uuid::new_v4();
+
+        assert_eq!(
+            TaskRef::parse(&full.to_string()).ok(),
+            Some(TaskRef::Id(full))
+        );
+        assert_eq!(TaskRef::parse("12").ok(), Some(TaskRef::Index(12)));
+        assert_eq!(
+            TaskRef::parse("0123").ok(),
+            Some(TaskRef::Prefix("0123".to_string()))
+        );
+        assert_eq!(
+            TaskRef::parse("A1B2").ok(),
+            Some(TaskRef::Prefix("a1b2".to_string()))
+        );
+        assert!(TaskRef::parse("deploy").is_err());
+        assert!(TaskRef::parse("").is_err());
+    }
+
+    #[test]
+    fn resolves_unique_prefixes_and_reports_ambiguous_ones() {
+        let first = id("abcd1111-0000-4000-8000-000000000000");
+        let second = id("abcd2222-0000-4000-8000-000000000000");
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(task_with_id(first, "first"))
+            .expect("save should succeed");
+        repo.save(task_with_id(second, "second"))
+            .expect("save should succeed");
+        let service = ResolveTaskIdService::new(repo);
+        let resolve = |reference: &str| {
+            service.execute(ResolveTaskIdCommand::new(
+                TaskRef::parse(reference).expect("reference should parse"),
+            ))
+        };
+
+        assert_eq!(resolve("abcd1").ok(), Some(first));
+        assert!(matches!(
+            resolve("abcd"),
+            Err(ApplicationError::AmbiguousTaskRef { candidates, .. }) if candidates == vec![first, second]
+        ));
+        assert!(matches!(
+            resolve("ffff"),
+            Err(ApplicationError::TaskRefNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn numbers_follow_the_list_order_of_the_scope() {
+        let mut repo = InMemoryTaskRepository::new();
+        let older = Task::new("older".to_string()).expect("task should be created");
+        let newer = Task::new("newer".to_string()).expect("task should be created");
+        let trashed = Task::new("trashed".to_string())
+            .and_then(|task| task.trash(Utc::now()))
+            .expect("task should be trashed");
+        for task in [older.clone(), newer.clone(), trashed.clone()] {
+            repo.save(task).expect("save should succeed");
+        }
+        let service = ResolveTaskIdService::new(repo);
+
+        assert_eq!(
+            service
+                .execute(ResolveTaskIdCommand::new(TaskRef::Index(2)))
+                .ok(),
+            Some(newer.task_id())
+        );
+        assert_eq!(
+            service
+                .execute(ResolveTaskIdCommand::new(TaskRef::Index(1)).with_scope(IdScope::Trash))
+                .ok(),
+            Some(trashed.task_id())
+        );
+        // Nine digits run past the first dash of any id, so the prefix
+        // fallback cannot match either.
+        assert!(
+            service
+                .execute(ResolveTaskIdCommand::new(TaskRef::Index(123_456_789)))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn a_number_no_task_has_is_matched_as_an_id_prefix() {
+        let numeric = id("12345678-0000-4000-8000-000000000000");
+        let mut repo = InMemoryTaskRepository::new();
+        repo.save(task_with_id(numeric, "numeric"))
+            .expect("save should succeed");
+        let service = ResolveTaskIdService::new(repo);
+        let resolve = |reference: &str| {
+            service.execute(ResolveTaskIdCommand::new(
+                TaskRef::parse(reference).expect("reference should parse"),
+            ))
+        };
+
+        assert_eq!(resolve("1").ok(), Some(numeric));
+        assert_eq!(resolve("12345678").ok(), Some(numeric));
+        assert!(matches!(
+            resolve("999"),
+            Err(ApplicationError::TaskRefNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn short_ids_are_the_shortest_unique_non_numeric_prefixes() {
+        let apart = id("abcd1111-0000-4000-8000-000000000000");
+        let close = id("abcd1112-0000-4000-8000-000000000000");
+        let numeric = id("12345678-0000-4000-8000-000000000000");
+
+        let short = ShortIds::new(&[apart, close, numeric], &[numeric]);
+
+        assert_eq!(short.prefix(apart), "abcd1111");
+        assert_eq!(short.prefix(close), "abcd1112");
+        assert_eq!(short.prefix(numeric), "12345678-");
+        assert_eq!(short.index(numeric), Some(1));
+        assert_eq!(short.index(apart), None);
+
+        let repo = InMemoryTaskRepository::new();
+        assert_eq!(short_ids(&repo).ok(), Some(ShortIds::default()));
+    }
+}
@@ -0,0 +1,32 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::ports::outputs::backup_repository::{Backup, BackupRepository};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreBackupCommand {
+    name: String,
+}
+impl RestoreBackupCommand {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+pub trait RestoreBackupUseCase {
+    /// Puts the named backup in place of the task store. Like archiving,
+    /// this is not journaled: undo keeps working on task snapshots.
+    fn execute(&mut self, cmd: RestoreBackupCommand) -> ApplicationResult<Backup>;
+}
+pub struct RestoreBackupService<R: BackupRepository> {
+    repo: R,
+}
+
+impl<R: BackupRepository> RestoreBackupService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: BackupRepository> RestoreBackupUseCase for RestoreBackupService<R> {
+    fn execute(&mut self, cmd: RestoreBackupCommand) -> ApplicationResult<Backup> {
+        Ok(self.repo.restore_backup(&cmd.name)?)
+    }
+}
@@ -0,0 +1,163 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::project_repository::ProjectRepository;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestoreTaskCommand {
+    task_id: Uuid,
+}
+impl RestoreTaskCommand {
+    pub fn new(task_id: Uuid) -> Self {
+        Self { task_id }
+    }
+}
+pub trait RestoreTaskUseCase {
+    /// Takes a task, and the subtasks trashed along with it, out of the trash.
+    fn execute(&mut self, cmd: RestoreTaskCommand) -> ApplicationResult<Task>;
+}
+pub struct RestoreTaskService<R: TaskRepository + ProjectRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository + ProjectRepository> RestoreTaskService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+
+    /// A parent that is still trashed or a project deleted in the meantime
+    /// cannot be pointed at, so the task goes to the top level / the inbox.
+    fn reattach(&self, task: Task) -> ApplicationResult<Task> {
+        let mut task: Task = task.restore()?;
+        if let Some(parent) = task.parent()
+            && self.repo.find_by_id(parent)?.is_none()
+        {
+            task = task.set_parent(None)?;
+        }
+        if let Some(project) = task.project()
+            && self.repo.find_project_by_id(project)?.is_none()
+        {
+            task = task.move_to_project(None)?;
+        }
+        Ok(task)
+    }
+}
+
+impl<R: TaskRepository + ProjectRepository> RestoreTaskUseCase for RestoreTaskService<R> {
+    fn execute(&mut self, cmd: RestoreTaskCommand) -> ApplicationResult<Task> {
+        let task_id: Uuid = cmd.task_id;
+        let task: Task = self
+            .repo
+            .find_any(task_id)?
+            .ok_or(DomainError::TaskNotFound { id: task_id })?;
+        let deleted_at = task.deleted_at();
+        let restored: Task = self.reattach(task)?;
+        let restored: Task = self.repo.save(restored)?;
+
+        let trashed: Vec<Task> = self.repo.list(TaskQuery::Trashed)?;
+        let mut pending: Vec<Uuid> = vec![task_id];
+        while let Some(parent) = pending.pop() {
+            for subtask in trashed.iter().filter(|subtask| {
+                subtask.parent() == Some(parent) && subtask.deleted_at() == deleted_at
+            }) {
+                pending.push(subtask.task_id());
+                let subtask: Task = self.reattach(subtask.clone())?;
+                self.repo.save(subtask)?;
+            }
+        }
+        Ok(restored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RestoreTaskCommand, RestoreTaskService, RestoreTaskUseCase};
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::domain::errors::DomainError;
+    use crate::tasks::domain::project::Project;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use chrono::{Duration, Utc};
+
+    fn new_task(title: &str) -> Task {
+        Task::new(title.to_string()).expect("task should be created")
+    }
+
+    #[test]
+    fn restore_brings_back_subtasks_trashed_with_the_task() {
+        let mut repo = InMemoryTaskRepository::new();
+        let now = Utc::now();
+        let parent = new_task("release");
+        let child = new_task("docs")
+            .set_parent(Some(parent.task_id()))
+            .expect("parent should be set");
+        let earlier = new_task("notes")
+            .set_parent(Some(parent.task_id()))
+            .expect("parent should be set");
+        repo.save(parent.clone().trash(now).expect("trash should succeed"))
+            .expect("save should succeed");
+        repo.save(child.trash(now).expect("trash should succeed"))
+            .expect("save should succeed");
+        repo.save(
+            earlier
+                .trash(now - Duration::days(1))
+                .expect("trash should succeed"),
+        )
+        .expect("save should succeed");
+        let mut service = RestoreTaskService::new(repo);
+
+        let restored = service
+            .execute(RestoreTaskCommand::new(parent.task_id()))
+            .expect("restore should succeed");
+
+        assert!(!restored.is_trashed());
+        let all = service
+            .repo
+            .list(TaskQuery::All)
+            .expect("list should succeed");
+        let trashed = service
+            .repo
+            .list(TaskQuery::Trashed)
+            .expect("list should succeed");
+        assert_eq!(all.len(), 2);
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].title(), "notes");
+    }
+
+    #[test]
+    fn restore_moves_a_task_of_a_deleted_project_to_the_inbox() {
+        let mut repo = InMemoryTaskRepository::new();
+        let project = Project::new("gone".to_string()).expect("project should be created");
+        let task = new_task("orphan")
+            .move_to_project(Some(project.project_id()))
+            .expect("move should succeed")
+            .trash(Utc::now())
+            .expect("trash should succeed");
+        repo.save(task.clone()).expect("save should succeed");
+        let mut service = RestoreTaskService::new(repo);
+
+        let restored = service
+            .execute(RestoreTaskCommand::new(task.task_id()))
+            .expect("restore should succeed");
+
+        assert_eq!(restored.project(), None);
+    }
+
+    #[test]
+    fn restoring_a_live_task_fails() {
+        let mut repo = InMemoryTaskRepository::new();
+        let task = new_task("live");
+        repo.save(task.clone()).expect("save should succeed");
+        let mut service = RestoreTaskService::new(repo);
+
+        let result = service.execute(RestoreTaskCommand::new(task.task_id()));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DomainError::TaskNotInTrash { .. }))
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::search::SearchQuery;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+use std::cmp::Reverse;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchTasksCommand {
+    query: SearchQuery,
+}
+impl SearchTasksCommand {
+    pub fn new(query: SearchQuery) -> Self {
+        Self { query }
+    }
+}
+
+/// A matching task with its relevance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub task: Task,
+    pub score: u32,
+}
+
+pub trait SearchTasksUseCase {
+    /// Matching tasks, most relevant first; ties go to the most recently
+    /// modified task.
+    fn execute(&self, cmd: SearchTasksCommand) -> ApplicationResult<Vec<SearchHit>>;
+}
+pub struct SearchTasksService<R: TaskRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository> SearchTasksService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository> SearchTasksUseCase for SearchTasksService<R> {
+    fn execute(&self, cmd: SearchTasksCommand) -> ApplicationResult<Vec<SearchHit>> {
+        let query: SearchQuery = cmd.query;
+        let mut hits: Vec<SearchHit> = self
+            .repo
+            .list(TaskQuery::Text(query.clone()))?
+            .into_iter()
+            .filter_map(|task| {
+                let score: u32 = query.score(&task)?;
+                Some(SearchHit { task, score })
+            })
+            .collect();
+        hits.sort_by_key(|hit| (Reverse(hit.score), Reverse(hit.task.modified_at())));
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchTasksCommand, SearchTasksService, SearchTasksUseCase};
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::domain::search::SearchQuery;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::task_repository::TaskRepository;
+    use chrono::Utc;
+
+    fn search(repo: InMemoryTaskRepository, text: &str) -> Vec<String> {
+        let query = SearchQuery::parse(text).expect("query should parse");
+        SearchTasksService::new(repo)
+            .execute(SearchTasksCommand::new(query))
+            .expect("search should succeed")
+            .into_iter()
+            .map(|hit| hit.task.title().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn results_are_ranked_by_relevance() {
+        let mut repo = InMemoryTaskRepository::new();
+        for title in ["Report expenses", "Write reports", "Prepare taxes"] {
+            repo.save(Task::new(title.to_string()).expect("task should be created"))
+                .expect("save should succeed");
+        }
+
+        assert_eq!(
+            search(repo, "report"),
+            vec!["Report expenses", "Write reports"]
+        );
+    }
+
+    #[test]
+    fn trashed_tasks_are_not_found() {
+        let mut repo = InMemoryTaskRepository::new();
+        let task = Task::new("Old report".to_string())
+            .and_then(|task| task.trash(Utc::now()))
+            .expect("task should be trashed");
+        repo.save(task).expect("save should succeed");
+
+        assert!(search(repo, "report").is_empty());
+    }
+}
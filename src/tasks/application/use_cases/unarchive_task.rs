@@ -0,0 +1,101 @@
+use crate::tasks::application::errors::ApplicationResult;
+use crate::tasks::domain::errors::DomainError;
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnarchiveTaskCommand {
+    task_id: Uuid,
+}
+impl UnarchiveTaskCommand {
+    pub fn new(task_id: Uuid) -> Self {
+        Self { task_id }
+    }
+}
+pub trait UnarchiveTaskUseCase {
+    /// Moves an archived task back to the task store.
+    fn execute(&mut self, cmd: UnarchiveTaskCommand) -> ApplicationResult<Task>;
+}
+pub struct UnarchiveTaskService<R: TaskRepository + ArchiveRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository + ArchiveRepository> UnarchiveTaskService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository + ArchiveRepository> UnarchiveTaskUseCase for UnarchiveTaskService<R> {
+    fn execute(&mut self, cmd: UnarchiveTaskCommand) -> ApplicationResult<Task> {
+        let task_id: Uuid = cmd.task_id;
+        let task: Task = self
+            .repo
+            .list_archived()?
+            .into_iter()
+            .find(|task| task.task_id() == task_id)
+            .ok_or(DomainError::TaskNotFound { id: task_id })?;
+        let task: Task = self.repo.save(task)?;
+        self.repo.delete_archived(task_id)?;
+        Ok(task)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UnarchiveTaskCommand, UnarchiveTaskService, UnarchiveTaskUseCase};
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::domain::errors::DomainError;
+    use crate::tasks::domain::task::Task;
+    use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+    use crate::tasks::ports::outputs::task_repository::TaskRepository;
+
+    #[test]
+    fn unarchive_moves_the_task_back() {
+        let mut repo = InMemoryTaskRepository::new();
+        let task = Task::new("shipped".to_string())
+            .and_then(Task::mark_done)
+            .expect("task should be created");
+        repo.archive(vec![task.clone()])
+            .expect("archive should succeed");
+        let mut service = UnarchiveTaskService::new(repo);
+
+        let restored = service
+            .execute(UnarchiveTaskCommand::new(task.task_id()))
+            .expect("unarchive should succeed");
+
+        assert_eq!(restored, task.clone().with_version(task.version() + 1));
+        assert!(
+            service
+                .repo
+                .find_by_id(task.task_id())
+                .expect("find should succeed")
+                .is_some()
+        );
+        assert!(
+            service
+                .repo
+                .list_archived()
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn unarchive_of_a_task_not_in_the_archive_fails() {
+        let mut repo = InMemoryTaskRepository::new();
+        let task = Task::new("live".to_string()).expect("task should be created");
+        repo.save(task.clone()).expect("save should succeed");
+        let mut service = UnarchiveTaskService::new(repo);
+
+        let result = service.execute(UnarchiveTaskCommand::new(task.task_id()));
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DomainError::TaskNotFound { .. }))
+        ));
+    }
+}
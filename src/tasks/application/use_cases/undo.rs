@@ -0,0 +1,380 @@
+use crate::tasks::application::errors::{ApplicationError, ApplicationResult};
+use crate::tasks::application::journal::{reapply, revert};
+use crate::tasks::domain::journal::{Journal, JournalEntry};
+use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+use crate::tasks::ports::outputs::task_repository::TaskRepository;
+
+pub trait UndoUseCase {
+    /// Reverts the latest recorded operation and returns it.
+    fn execute(&mut self) -> ApplicationResult<JournalEntry>;
+}
+pub struct UndoService<R: TaskRepository + ArchiveRepository + JournalRepository> {
+    repo: R,
+}
+
+impl<R: TaskRepository + ArchiveRepository + JournalRepository> UndoService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: TaskRepository + ArchiveRepository + JournalRepository> UndoUseCase for UndoService<R> {
+    /// Fails with a conflict, changing nothing, if a task was changed since
+    /// the operation. The entry only moves to the redo stack, with the
+    /// versions undo left, once it has been reverted. If another process
+    /// recorded or undid an entry meanwhile, the revert is applied again
+    /// and the undo fails with [`ApplicationError::JournalChanged`].
+    fn execute(&mut self) -> ApplicationResult<JournalEntry> {
+        let journal: Journal = self.repo.load_journal()?;
+        let entry: JournalEntry = journal
+            .next_undo()
+            .cloned()
+            .ok_or(ApplicationError::NothingToUndo)?;
+        let undone: JournalEntry = revert(&mut self.repo, &entry)?;
+        let moved: bool = self
+            .repo
+            .update_journal(|journal| journal.mark_undone(&entry, undone.clone()))?;
+        if !moved {
+            reapply(&mut self.repo, &undone)?;
+            return Err(ApplicationError::JournalChanged);
+        }
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UndoService, UndoUseCase};
+    use crate::tasks::adapters::clock::system_clock::SystemClock;
+    use crate::tasks::adapters::persistence::in_memory_task_repository::InMemoryTaskRepository;
+    use crate::tasks::adapters::persistence::json_file_task_repository::JsonFileTaskRepository;
+    use crate::tasks::application::errors::ApplicationError;
+    use crate::tasks::application::journal::{JournalingRepository, run_journaled};
+    use crate::tasks::application::use_cases::add_task::{
+        AddTaskCommand, AddTaskService, AddTaskUseCase,
+    };
+    use crate::tasks::application::use_cases::archive_tasks::{
+        ArchiveTasksCommand, ArchiveTasksService, ArchiveTasksUseCase,
+    };
+    use crate::tasks::application::use_cases::delete_task::{
+        DeleteTaskCommand, DeleteTaskService, DeleteTaskUseCase,
+    };
+    use crate::tasks::application::use_cases::edit_task::{
+        EditFields, EditTaskCommand, EditTaskService, EditTaskUseCase,
+    };
+    use crate::tasks::application::use_cases::mark_task_done::{
+        MarkTaskDoneCommand, MarkTaskDoneService, MarkTaskDoneUseCase,
+    };
+    use crate::tasks::application::use_cases::redo::{RedoService, RedoUseCase};
+    use crate::tasks::domain::journal::{Journal, JournalEntry, TaskChange};
+    use crate::tasks::domain::task::{Task, TaskStatus};
+    use crate::tasks::ports::outputs::archive_repository::ArchiveRepository;
+    use crate::tasks::ports::outputs::errors::{RepoError, RepoResult};
+    use crate::tasks::ports::outputs::journal_repository::JournalRepository;
+    use crate::tasks::ports::outputs::task_repository::{TaskQuery, TaskRepository};
+    use chrono::Utc;
+    use std::cell::Cell;
+    use tempfile::tempdir;
+    use uuid::Uuid;
+
+    /// Records another entry in the journal the second time it is read, as
+    /// if another process ran a command between undo's revert and its
+    /// journal update.
+    struct BusyJournal {
+        inner: InMemoryTaskRepository,
+        loads: Cell<u32>,
+    }
+
+    impl TaskRepository for BusyJournal {
+        fn save(&mut self, task: Task) -> RepoResult<Task> {
+            self.inner.save(task)
+        }
+
+        fn list(&self, query: TaskQuery) -> RepoResult<Vec<Task>> {
+            self.inner.list(query)
+        }
+
+        fn find_any(&self, id: Uuid) -> RepoResult<Option<Task>> {
+            self.inner.find_any(id)
+        }
+
+        fn delete(&mut self, id: Uuid) -> RepoResult<bool> {
+            self.inner.delete(id)
+        }
+    }
+
+    impl ArchiveRepository for BusyJournal {
+        fn list_archived(&self) -> RepoResult<Vec<Task>> {
+            self.inner.list_archived()
+        }
+
+        fn archive(&mut self, tasks: Vec<Task>) -> RepoResult<()> {
+            self.inner.archive(tasks)
+        }
+
+        fn delete_archived(&mut self, id: Uuid) -> RepoResult<bool> {
+            self.inner.delete_archived(id)
+        }
+    }
+
+    impl JournalRepository for BusyJournal {
+        fn load_journal(&self) -> RepoResult<Journal> {
+            let mut journal: Journal = self.inner.load_journal()?;
+            self.loads.set(self.loads.get() + 1);
+            if self.loads.get() == 2 {
+                let other = Task::new("Other".to_string()).expect("task should be created");
+                journal.record(JournalEntry::new(
+                    "add".to_string(),
+                    Utc::now(),
+                    vec![TaskChange::new(None, Some(other))],
+                ));
+            }
+            Ok(journal)
+        }
+
+        fn save_journal(&mut self, journal: Journal) -> RepoResult<()> {
+            self.inner.save_journal(journal)
+        }
+    }
+
+    #[test]
+    fn delete_can_be_undone_and_redone_across_restarts() {
+        let temp = tempdir().expect("temp dir should be created");
+        let path = temp.path().join("tasks.json");
+        let journaling = JournalingRepository::new(JsonFileTaskRepository::using(path.clone()));
+        let task = AddTaskService::new(journaling.clone())
+            .execute(AddTaskCommand::new("Buy milk".to_string()))
+            .expect("add should succeed");
+        journaling
+            .commit("add", Utc::now())
+            .expect("commit should succeed");
+        let journaling = JournalingRepository::new(JsonFileTaskRepository::using(path.clone()));
+        DeleteTaskService::new(journaling.clone(), SystemClock)
+            .execute(DeleteTaskCommand::new(task.task_id()))
+            .expect("delete should succeed");
+        journaling
+            .commit("delete", Utc::now())
+            .expect("commit should succeed");
+
+        let repo = JsonFileTaskRepository::using(path.clone());
+        let undone = UndoService::new(repo.clone())
+            .execute()
+            .expect("undo should succeed");
+        assert_eq!(undone.summary(), "delete \"Buy milk\"");
+        assert!(
+            repo.find_by_id(task.task_id())
+                .expect("find should succeed")
+                .is_some()
+        );
+
+        RedoService::new(JsonFileTaskRepository::using(path))
+            .execute()
+            .expect("redo should succeed");
+        assert!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn undoing_an_add_removes_the_task_and_then_runs_out() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let journaling = JournalingRepository::new(repo.clone());
+        AddTaskService::new(journaling.clone())
+            .execute(AddTaskCommand::new("Buy milk".to_string()))
+            .expect("add should succeed");
+        journaling
+            .commit("add", Utc::now())
+            .expect("commit should succeed");
+        let mut service = UndoService::new(repo.clone());
+
+        service.execute().expect("undo should succeed");
+        let second = service.execute();
+
+        assert!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .is_empty()
+        );
+        assert!(matches!(second, Err(ApplicationError::NothingToUndo)));
+    }
+
+    #[test]
+    fn undo_leaves_tasks_archived_since_in_the_archive() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let journaling = JournalingRepository::new(repo.clone());
+        let task = AddTaskService::new(journaling.clone())
+            .execute(AddTaskCommand::new("Buy milk".to_string()))
+            .expect("add should succeed");
+        journaling
+            .commit("add", Utc::now())
+            .expect("commit should succeed");
+        let journaling = JournalingRepository::new(repo.clone());
+        MarkTaskDoneService::new(journaling.clone(), SystemClock)
+            .execute(MarkTaskDoneCommand::new(task.task_id()))
+            .expect("done should succeed");
+        journaling
+            .commit("done", Utc::now())
+            .expect("commit should succeed");
+        ArchiveTasksService::new(repo.clone(), SystemClock)
+            .execute(ArchiveTasksCommand::new().with_ids(vec![task.task_id()]))
+            .expect("archive should succeed");
+        let mut service = UndoService::new(repo.clone());
+
+        service.execute().expect("undo should succeed");
+        service.execute().expect("undo should succeed");
+
+        assert!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .is_empty()
+        );
+        let archived = repo.list_archived().expect("list should succeed");
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].status(), TaskStatus::Done);
+    }
+
+    #[test]
+    fn undo_refuses_to_overwrite_a_change_made_since() {
+        let temp = tempdir().expect("temp dir should be created");
+        let mut repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let journaling = JournalingRepository::new(repo.clone());
+        let task = AddTaskService::new(journaling.clone())
+            .execute(AddTaskCommand::new("Buy milk".to_string()))
+            .expect("add should succeed");
+        journaling
+            .commit("add", Utc::now())
+            .expect("commit should succeed");
+        let renamed = task
+            .clone()
+            .edit_title("Buy oat milk".to_string())
+            .expect("title should be valid");
+        repo.save(renamed).expect("save should succeed");
+
+        let result = UndoService::new(repo.clone()).execute();
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Repository(RepoError::Conflict { .. }))
+        ));
+        let stored = repo
+            .find_by_id(task.task_id())
+            .expect("find should succeed")
+            .expect("task should still exist");
+        assert_eq!(stored.title(), "Buy oat milk");
+        assert!(repo.load_journal().expect("load should succeed").can_undo());
+    }
+
+    #[test]
+    fn undo_puts_the_tasks_back_when_the_journal_changed_meanwhile() {
+        let mut inner = InMemoryTaskRepository::new();
+        let task = inner
+            .save(Task::new("Buy milk".to_string()).expect("task should be created"))
+            .expect("save should succeed");
+        let renamed = inner
+            .save(
+                task.clone()
+                    .edit_title("Buy oat milk".to_string())
+                    .expect("title should be valid"),
+            )
+            .expect("save should succeed");
+        inner
+            .update_journal(|journal| {
+                journal.record(JournalEntry::new(
+                    "edit".to_string(),
+                    Utc::now(),
+                    vec![TaskChange::new(Some(task.clone()), Some(renamed))],
+                ))
+            })
+            .expect("journal should be saved");
+        let mut service = UndoService::new(BusyJournal {
+            inner,
+            loads: Cell::new(0),
+        });
+
+        let result = service.execute();
+
+        assert!(matches!(result, Err(ApplicationError::JournalChanged)));
+        let repo = service.repo;
+        let stored = repo
+            .find_by_id(task.task_id())
+            .expect("find should succeed")
+            .expect("task should exist");
+        assert_eq!(stored.title(), "Buy oat milk");
+        let journal = repo.inner.load_journal().expect("load should succeed");
+        assert_eq!(journal.next_undo().map(JournalEntry::label), Some("add"));
+        assert!(!journal.can_redo());
+    }
+
+    #[test]
+    fn undo_and_redo_can_alternate_on_a_task_changed_twice_in_one_entry() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+        let journaling = JournalingRepository::new(repo.clone());
+        let task = AddTaskService::new(journaling.clone())
+            .execute(AddTaskCommand::new("Buy milk".to_string()))
+            .expect("add should succeed");
+        EditTaskService::new(journaling.clone())
+            .execute(EditTaskCommand::new(
+                task.task_id(),
+                EditFields::new().with_title("Buy oat milk".to_string()),
+            ))
+            .expect("edit should succeed");
+        journaling
+            .commit("add", Utc::now())
+            .expect("commit should succeed");
+
+        for _ in 0..2 {
+            UndoService::new(repo.clone())
+                .execute()
+                .expect("undo should succeed");
+            RedoService::new(repo.clone())
+                .execute()
+                .expect("redo should succeed");
+        }
+
+        let stored = repo
+            .find_by_id(task.task_id())
+            .expect("find should succeed")
+            .expect("task should exist");
+        assert_eq!(stored.title(), "Buy oat milk");
+        UndoService::new(repo.clone())
+            .execute()
+            .expect("undo should succeed");
+        assert!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn saves_made_before_an_operation_failed_can_be_undone() {
+        let temp = tempdir().expect("temp dir should be created");
+        let repo = JsonFileTaskRepository::using(temp.path().join("tasks.json"));
+
+        let result: Result<(), ApplicationError> =
+            run_journaled(repo.clone(), "bulk", Utc::now(), |journaling| {
+                let task = AddTaskService::new(journaling)
+                    .execute(AddTaskCommand::new("Buy milk".to_string()))?;
+                Err(ApplicationError::NothingToEdit { id: task.task_id() })
+            });
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::NothingToEdit { .. })
+        ));
+        UndoService::new(repo.clone())
+            .execute()
+            .expect("undo should succeed");
+        assert!(
+            repo.list(TaskQuery::All)
+                .expect("list should succeed")
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use crate::tasks::domain::errors::{DomainError, DomainResult};
+use chrono::{DateTime, Days, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// When a task is due: either a whole (UTC) day or an exact instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DueDate {
+    Date(NaiveDate),
+    DateTime(DateTime<Utc>),
+}
+
+impl DueDate {
+    /// Accepts `YYYY-MM-DD`, `YYYY-MM-DDTHH:MM[:SS]` (UTC) or RFC 3339.
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        let value = value.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Ok(DueDate::Date(date));
+        }
+        if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+            return Ok(DueDate::DateTime(date_time.with_timezone(&Utc)));
+        }
+        ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+            .map(|date_time| DueDate::DateTime(date_time.and_utc()))
+            .ok_or_else(|| DomainError::InvalidDueDate {
+                value: value.to_string(),
+            })
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            DueDate::Date(date) => *date,
+            DueDate::DateTime(date_time) => date_time.date_naive(),
+        }
+    }
+
+    /// First instant at which the task counts as late. A date-only due date
+    /// stays on time for the whole day.
+    pub fn deadline(&self) -> DateTime<Utc> {
+        match self {
+            DueDate::Date(date) => date
+                .checked_add_days(Days::new(1))
+                .unwrap_or(*date)
+                .and_time(NaiveTime::MIN)
+                .and_utc(),
+            DueDate::DateTime(date_time) => *date_time,
+        }
+    }
+
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        now >= self.deadline()
+    }
+
+    pub fn is_due_on(&self, day: NaiveDate) -> bool {
+        self.date() == day
+    }
+
+    /// Not overdue yet and due no later than `days` days after `now`'s date.
+    pub fn is_due_within(&self, now: DateTime<Utc>, days: u32) -> bool {
+        let last_day = now
+            .date_naive()
+            .checked_add_days(Days::new(u64::from(days)))
+            .unwrap_or(NaiveDate::MAX);
+        !self.is_overdue(now) && self.date() <= last_day
+    }
+}
+
+impl FromStr for DueDate {
+    type Err = DomainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        DueDate::parse(value)
+    }
+}
+
+impl fmt::Display for DueDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DueDate::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            DueDate::DateTime(date_time) => write!(f, "{}", date_time.format("%Y-%m-%d %H:%M")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DueDate;
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).expect("date should be valid")
+    }
+
+    #[test]
+    fn parse_accepts_dates_and_datetimes() {
+        assert_eq!(
+            DueDate::parse("2026-03-21").expect("should parse"),
+            DueDate::Date(date(2026, 3, 21))
+        );
+        assert_eq!(
+            DueDate::parse("2026-03-21T14:30").expect("should parse"),
+            DueDate::DateTime(Utc.with_ymd_and_hms(2026, 3, 21, 14, 30, 0).unwrap())
+        );
+        assert_eq!(
+            DueDate::parse("2026-03-21T14:30:00+02:00").expect("should parse"),
+            DueDate::DateTime(Utc.with_ymd_and_hms(2026, 3, 21, 12, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert!(DueDate::parse("next tuesday").is_err());
+        assert!(DueDate::parse("2026-02-30").is_err());
+    }
+
+    #[test]
+    fn date_only_due_dates_are_overdue_the_day_after() {
+        let due = DueDate::Date(date(2026, 3, 21));
+
+        assert!(!due.is_overdue(Utc.with_ymd_and_hms(2026, 3, 21, 23, 59, 59).unwrap()));
+        assert!(due.is_overdue(Utc.with_ymd_and_hms(2026, 3, 22, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn datetime_due_dates_are_overdue_at_the_instant() {
+        let due = DueDate::DateTime(Utc.with_ymd_and_hms(2026, 3, 21, 9, 0, 0).unwrap());
+
+        assert!(!due.is_overdue(Utc.with_ymd_and_hms(2026, 3, 21, 8, 59, 0).unwrap()));
+        assert!(due.is_overdue(Utc.with_ymd_and_hms(2026, 3, 21, 9, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn due_within_excludes_overdue_and_far_future() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 21, 12, 0, 0).unwrap();
+
+        assert!(DueDate::Date(date(2026, 3, 21)).is_due_within(now, 0));
+        assert!(DueDate::Date(date(2026, 3, 28)).is_due_within(now, 7));
+        assert!(!DueDate::Date(date(2026, 3, 29)).is_due_within(now, 7));
+        assert!(!DueDate::Date(date(2026, 3, 20)).is_due_within(now, 7));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::tasks::domain::filter::FilterError;
 use crate::tasks::domain::task::TaskStatus;
 use thiserror::Error;
 use uuid::Uuid;
@@ -8,14 +9,59 @@ pub type DomainResult<T> = Result<T, DomainError>;
 pub enum DomainError {
     #[error("task title cannot be empty")]
     EmptyTitle,
-    #[error("task title exceeds max length ({max})")]
+    #[error("task title exceeds max length ({max} characters); put longer text in the description")]
     TitleTooLong { max: usize },
+    #[error("blocked reason exceeds max length ({max} characters)")]
+    BlockedReasonTooLong { max: usize },
+    #[error("task description exceeds max length ({max} characters)")]
+    DescriptionTooLong { max: usize },
     #[error("task with id {id} was not found")]
     TaskNotFound { id: Uuid },
-    #[error("invalid status transition for task {id}: {from:?} => {to:?}")]
+    #[error("task {id} is not in the trash")]
+    TaskNotInTrash { id: Uuid },
+    #[error("invalid status transition for task {id}: {from} => {to}")]
     InvalidStatusTransition {
         id: Uuid,
         from: TaskStatus,
         to: TaskStatus,
     },
+    #[error("parent task {id} was not found")]
+    ParentNotFound { id: Uuid },
+    #[error("task {parent} cannot be the parent of task {id}: that would create a cycle")]
+    SubtaskCycle { id: Uuid, parent: Uuid },
+    #[error("task {id} still has {open} open subtask(s)")]
+    OpenSubtasks { id: Uuid, open: usize },
+    #[error("blocking task {id} was not found")]
+    BlockerNotFound { id: Uuid },
+    #[error("task {blocker} cannot block task {id}: that would create a dependency cycle")]
+    DependencyCycle { id: Uuid, blocker: Uuid },
+    #[error("task {id} is blocked by open task(s): {}", join_ids(.blockers))]
+    OpenBlockers { id: Uuid, blockers: Vec<Uuid> },
+    #[error("invalid priority '{value}' (expected none, low, medium, high or urgent)")]
+    InvalidPriority { value: String },
+    #[error("invalid due date '{value}' (expected YYYY-MM-DD or YYYY-MM-DDTHH:MM)")]
+    InvalidDueDate { value: String },
+    #[error(
+        "invalid tag '{value}' (use 1-32 lowercase letters, digits, '-' or '_', optionally prefixed with '+')"
+    )]
+    InvalidTag { value: String },
+    #[error("invalid recurrence '{value}': {reason}")]
+    InvalidRecurrence { value: String, reason: String },
+    #[error("search text cannot be empty")]
+    EmptySearch,
+    #[error(transparent)]
+    InvalidFilter(#[from] FilterError),
+    #[error("invalid project name '{name}' (1-64 characters, 'inbox' is reserved)")]
+    InvalidProjectName { name: String },
+    #[error("project '{name}' was not found")]
+    ProjectNotFound { name: String },
+    #[error("project '{name}' already exists")]
+    DuplicateProjectName { name: String },
+}
+
+fn join_ids(ids: &[Uuid]) -> String {
+    ids.iter()
+        .map(Uuid::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
 }
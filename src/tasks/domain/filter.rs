@@ -0,0 +1,797 @@
+use crate::tasks::domain::priority::Priority;
+use crate::tasks::domain::tag::Tag;
+use crate::tasks::domain::task::{Task, TaskStatus};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use uuid::Uuid;
+
+const FIELDS: &str = "status, priority, tag, project, due, created, modified, title or notes";
+
+/// A filter expression that could not be parsed, with the 1-based character
+/// column the problem was found at.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid filter at column {column}: {kind}")]
+pub struct FilterError {
+    pub column: usize,
+    pub kind: FilterErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FilterErrorKind {
+    #[error("the expression is empty")]
+    Empty,
+    #[error("unexpected character '{0}'")]
+    UnexpectedChar(char),
+    #[error("quoted value is never closed")]
+    UnterminatedQuote,
+    #[error("expected a field ({FIELDS}), found {0}")]
+    ExpectedField(String),
+    #[error("unknown field '{0}' (expected {FIELDS})")]
+    UnknownField(String),
+    #[error("expected an operator (:, =, !=, <, <=, >, >= or ~) after '{0}'")]
+    ExpectedOperator(String),
+    #[error("'{field}' cannot be compared with '{operator}'")]
+    UnsupportedOperator { field: String, operator: Operator },
+    #[error("expected a value after '{0}'")]
+    ExpectedValue(Operator),
+    #[error("invalid {field} '{value}' (expected {expected})")]
+    InvalidValue {
+        field: String,
+        value: String,
+        expected: &'static str,
+    },
+    #[error("the '(' at column {0} is never closed")]
+    UnclosedParen(usize),
+    #[error("expected 'and', 'or' or the end of the expression, found {0}")]
+    UnexpectedToken(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    /// `:`, equality for most fields and "contains" for text.
+    Colon,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// `~`, case-insensitive "contains".
+    Tilde,
+}
+
+impl Operator {
+    fn compare<T: Ord>(self, actual: T, expected: T) -> bool {
+        match self {
+            Operator::Colon | Operator::Eq => actual == expected,
+            Operator::Ne => actual != expected,
+            Operator::Lt => actual < expected,
+            Operator::Le => actual <= expected,
+            Operator::Gt => actual > expected,
+            Operator::Ge => actual >= expected,
+            Operator::Tilde => false,
+        }
+    }
+
+    fn is_equality(self) -> bool {
+        matches!(self, Operator::Colon | Operator::Eq | Operator::Ne)
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Operator::Colon => ":",
+            Operator::Eq => "=",
+            Operator::Ne => "!=",
+            Operator::Lt => "<",
+            Operator::Le => "<=",
+            Operator::Gt => ">",
+            Operator::Ge => ">=",
+            Operator::Tilde => "~",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusValue {
+    Is(TaskStatus),
+    /// Neither done nor cancelled.
+    Open,
+    Closed,
+}
+
+/// A day given absolutely or relative to the time the filter is evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateValue {
+    /// Only for `due`: the task has no due date.
+    None,
+    Today,
+    /// `7d` is a week from today, `-7d` a week ago.
+    DaysFromToday(i64),
+    Date(NaiveDate),
+}
+
+impl DateValue {
+    fn day(self, now: DateTime<Utc>) -> Option<NaiveDate> {
+        match self {
+            DateValue::None => None,
+            DateValue::Today => Some(now.date_naive()),
+            // The parser only accepts spans that fit between the first and
+            // last representable days, so only the end can be out of reach.
+            DateValue::DaysFromToday(days) => Some(
+                Duration::try_days(days)
+                    .and_then(|span| now.checked_add_signed(span))
+                    .map_or(
+                        if days < 0 {
+                            NaiveDate::MIN
+                        } else {
+                            NaiveDate::MAX
+                        },
+                        |day| day.date_naive(),
+                    ),
+            ),
+            DateValue::Date(date) => Some(date),
+        }
+    }
+}
+
+/// Projects are written by name and have to be resolved to the id stored on
+/// tasks (`None` for the inbox) before the filter can match them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectRef {
+    Named(String),
+    Resolved(Option<Uuid>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    Status(Operator, StatusValue),
+    Priority(Operator, Priority),
+    Tag(Operator, Tag),
+    Project(Operator, ProjectRef),
+    Due(Operator, DateValue),
+    Created(Operator, DateValue),
+    Modified(Operator, DateValue),
+    Title(Operator, String),
+    Notes(Operator, String),
+}
+
+impl Condition {
+    /// Tasks without a due date only match `due:none` and `due!=<day>`.
+    pub fn matches(&self, task: &Task, now: DateTime<Utc>) -> bool {
+        match self {
+            Condition::Status(operator, value) => {
+                let is = match value {
+                    StatusValue::Is(status) => task.status() == *status,
+                    StatusValue::Open => task.status().is_open(),
+                    StatusValue::Closed => !task.status().is_open(),
+                };
+                is == (*operator != Operator::Ne)
+            }
+            Condition::Priority(operator, priority) => operator.compare(task.priority(), *priority),
+            Condition::Tag(operator, tag) => task.has_tag(tag) == (*operator != Operator::Ne),
+            Condition::Project(operator, ProjectRef::Resolved(project)) => {
+                operator.compare(task.project(), *project)
+            }
+            Condition::Project(_, ProjectRef::Named(_)) => false,
+            Condition::Due(operator, value) => {
+                let due: Option<NaiveDate> = task.due().map(|due| due.date());
+                match (due, value.day(now)) {
+                    (Some(due), Some(day)) => operator.compare(due, day),
+                    (None, Some(_)) => *operator == Operator::Ne,
+                    (due, None) => due.is_none() == (*operator != Operator::Ne),
+                }
+            }
+            Condition::Created(operator, value) => value
+                .day(now)
+                .is_some_and(|day| operator.compare(task.created_at().date_naive(), day)),
+            Condition::Modified(operator, value) => value
+                .day(now)
+                .is_some_and(|day| operator.compare(task.modified_at().date_naive(), day)),
+            Condition::Title(operator, text) => text_matches(*operator, task.title(), text),
+            Condition::Notes(operator, text) => {
+                text_matches(*operator, task.description().unwrap_or_default(), text)
+            }
+        }
+    }
+
+    fn parse(field: &Token, operator: &Token, value: &Token) -> Result<Self, FilterError> {
+        let name: String = field.text().to_lowercase();
+        let TokenKind::Op(op) = operator.kind else {
+            unreachable!("the parser only passes operator tokens")
+        };
+        let allowed: &[Operator] = match name.as_str() {
+            "status" | "tag" | "project" => &[Operator::Colon, Operator::Eq, Operator::Ne],
+            "title" | "notes" => &[Operator::Colon, Operator::Eq, Operator::Ne, Operator::Tilde],
+            "priority" | "due" | "created" | "modified" => &[
+                Operator::Colon,
+                Operator::Eq,
+                Operator::Ne,
+                Operator::Lt,
+                Operator::Le,
+                Operator::Gt,
+                Operator::Ge,
+            ],
+            _ => return Err(field.error(FilterErrorKind::UnknownField(field.text()))),
+        };
+        if !allowed.contains(&op) {
+            return Err(operator.error(FilterErrorKind::UnsupportedOperator {
+                field: name,
+                operator: op,
+            }));
+        }
+
+        let text: String = value.text();
+        let invalid = |expected: &'static str| {
+            value.error(FilterErrorKind::InvalidValue {
+                field: name.clone(),
+                value: text.clone(),
+                expected,
+            })
+        };
+        let lower: String = text.to_lowercase();
+        Ok(match name.as_str() {
+            "status" => Condition::Status(
+                op,
+                match lower.as_str() {
+                    "open" => StatusValue::Open,
+                    "closed" => StatusValue::Closed,
+                    label => TaskStatus::ALL
+                        .into_iter()
+                        .find(|status| status.label() == label)
+                        .map(StatusValue::Is)
+                        .ok_or_else(|| {
+                            invalid("todo, in-progress, blocked, done, cancelled, open or closed")
+                        })?,
+                },
+            ),
+            "priority" => Condition::Priority(
+                op,
+                Priority::parse(&text).map_err(|_| invalid("none, low, medium, high or urgent"))?,
+            ),
+            "tag" => Condition::Tag(
+                op,
+                Tag::parse(&text).map_err(|_| invalid("a tag such as backend"))?,
+            ),
+            "project" => Condition::Project(op, ProjectRef::Named(text.clone())),
+            "title" => Condition::Title(op, text.clone()),
+            "notes" => Condition::Notes(op, text.clone()),
+            date_field => {
+                let date: DateValue = parse_date_value(&lower)
+                    .filter(|date| {
+                        *date != DateValue::None || (date_field == "due" && op.is_equality())
+                    })
+                    .ok_or_else(|| {
+                        invalid(if date_field == "due" {
+                            "none, today, <N>d, -<N>d or YYYY-MM-DD"
+                        } else {
+                            "today, <N>d, -<N>d or YYYY-MM-DD"
+                        })
+                    })?;
+                match date_field {
+                    "due" => Condition::Due(op, date),
+                    "created" => Condition::Created(op, date),
+                    _ => Condition::Modified(op, date),
+                }
+            }
+        })
+    }
+}
+
+fn parse_date_value(value: &str) -> Option<DateValue> {
+    match value {
+        "none" => Some(DateValue::None),
+        "today" => Some(DateValue::Today),
+        other => match other.strip_suffix('d') {
+            Some(days) => days
+                .parse::<i64>()
+                .ok()
+                .filter(|days| is_representable_span(*days))
+                .map(DateValue::DaysFromToday),
+            None => NaiveDate::parse_from_str(other, "%Y-%m-%d")
+                .ok()
+                .map(DateValue::Date),
+        },
+    }
+}
+
+/// Whether `days` fits between the first and last days chrono can represent.
+fn is_representable_span(days: i64) -> bool {
+    Duration::try_days(days)
+        .and_then(|span| DateTime::<Utc>::MIN_UTC.checked_add_signed(span.abs()))
+        .is_some()
+}
+
+/// `:` and `~` look for the text anywhere, `=` and `!=` compare it whole;
+/// both ignore case.
+fn text_matches(operator: Operator, actual: &str, expected: &str) -> bool {
+    let actual: String = actual.to_lowercase();
+    let expected: String = expected.to_lowercase();
+    match operator {
+        Operator::Colon | Operator::Tilde => actual.contains(&expected),
+        Operator::Eq => actual == expected,
+        Operator::Ne => actual != expected,
+        _ => false,
+    }
+}
+
+/// Parsed filter expression, e.g.
+/// `status:todo and (tag:backend or priority>=high) and due<7d and title~"deploy"`.
+/// `not` binds tightest, then `and`, then `or`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterExpr {
+    Condition(Condition),
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+impl FilterExpr {
+    pub fn parse(text: &str) -> Result<Self, FilterError> {
+        let tokens: Vec<Token> = tokenize(text)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            end_column: text.chars().count() + 1,
+        };
+        if parser.tokens.is_empty() {
+            return Err(FilterError {
+                column: 1,
+                kind: FilterErrorKind::Empty,
+            });
+        }
+        let expr: FilterExpr = parser.parse_or()?;
+        match parser.peek() {
+            Some(token) => Err(token.error(FilterErrorKind::UnexpectedToken(token.describe()))),
+            None => Ok(expr),
+        }
+    }
+
+    pub fn matches(&self, task: &Task, now: DateTime<Utc>) -> bool {
+        match self {
+            FilterExpr::Condition(condition) => condition.matches(task, now),
+            FilterExpr::Not(inner) => !inner.matches(task, now),
+            FilterExpr::And(left, right) => left.matches(task, now) && right.matches(task, now),
+            FilterExpr::Or(left, right) => left.matches(task, now) || right.matches(task, now),
+        }
+    }
+
+    /// Conditions every matching task has to meet, i.e. those joined to the
+    /// rest of the expression by `and` only.
+    pub fn conjuncts(&self) -> Vec<&Condition> {
+        match self {
+            FilterExpr::Condition(condition) => vec![condition],
+            FilterExpr::And(left, right) => {
+                let mut conditions: Vec<&Condition> = left.conjuncts();
+                conditions.extend(right.conjuncts());
+                conditions
+            }
+            FilterExpr::Not(_) | FilterExpr::Or(_, _) => Vec::new(),
+        }
+    }
+
+    /// Replaces every project name with the id `resolve` returns for it.
+    pub fn resolve_projects<E>(
+        self,
+        resolve: &mut impl FnMut(&str) -> Result<Option<Uuid>, E>,
+    ) -> Result<Self, E> {
+        Ok(match self {
+            FilterExpr::Condition(Condition::Project(operator, ProjectRef::Named(name))) => {
+                FilterExpr::Condition(Condition::Project(
+                    operator,
+                    ProjectRef::Resolved(resolve(&name)?),
+                ))
+            }
+            FilterExpr::Condition(condition) => FilterExpr::Condition(condition),
+            FilterExpr::Not(inner) => FilterExpr::Not(Box::new(inner.resolve_projects(resolve)?)),
+            FilterExpr::And(left, right) => FilterExpr::And(
+                Box::new(left.resolve_projects(resolve)?),
+                Box::new(right.resolve_projects(resolve)?),
+            ),
+            FilterExpr::Or(left, right) => FilterExpr::Or(
+                Box::new(left.resolve_projects(resolve)?),
+                Box::new(right.resolve_projects(resolve)?),
+            ),
+        })
+    }
+}
+
+impl FromStr for FilterExpr {
+    type Err = FilterError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        FilterExpr::parse(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    Op(Operator),
+    Word(String),
+    Quoted(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    kind: TokenKind,
+    column: usize,
+}
+
+impl Token {
+    fn error(&self, kind: FilterErrorKind) -> FilterError {
+        FilterError {
+            column: self.column,
+            kind,
+        }
+    }
+
+    fn text(&self) -> String {
+        match &self.kind {
+            TokenKind::Word(text) | TokenKind::Quoted(text) => text.clone(),
+            TokenKind::LParen => "(".to_string(),
+            TokenKind::RParen => ")".to_string(),
+            TokenKind::Op(operator) => operator.to_string(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match &self.kind {
+            TokenKind::Quoted(text) => format!("\"{text}\""),
+            _ => format!("'{}'", self.text()),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.kind, TokenKind::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | '+' | '.' | '/')
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, FilterError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let column = index + 1;
+        let c = chars[index];
+        let next = chars.get(index + 1).copied();
+        let (kind, length) = match c {
+            _ if c.is_whitespace() => {
+                index += 1;
+                continue;
+            }
+            '(' => (TokenKind::LParen, 1),
+            ')' => (TokenKind::RParen, 1),
+            ':' => (TokenKind::Op(Operator::Colon), 1),
+            '=' => (TokenKind::Op(Operator::Eq), 1),
+            '~' => (TokenKind::Op(Operator::Tilde), 1),
+            '!' if next == Some('=') => (TokenKind::Op(Operator::Ne), 2),
+            '<' if next == Some('=') => (TokenKind::Op(Operator::Le), 2),
+            '<' => (TokenKind::Op(Operator::Lt), 1),
+            '>' if next == Some('=') => (TokenKind::Op(Operator::Ge), 2),
+            '>' => (TokenKind::Op(Operator::Gt), 1),
+            '"' => {
+                let mut value = String::new();
+                let mut end = index + 1;
+                loop {
+                    match chars.get(end) {
+                        None => {
+                            return Err(FilterError {
+                                column,
+                                kind: FilterErrorKind::UnterminatedQuote,
+                            });
+                        }
+                        Some('"') => break,
+                        Some('\\') if chars.get(end + 1).is_some() => {
+                            value.push(chars[end + 1]);
+                            end += 2;
+                        }
+                        Some(other) => {
+                            value.push(*other);
+                            end += 1;
+                        }
+                    }
+                }
+                (TokenKind::Quoted(value), end + 1 - index)
+            }
+            _ if is_word_char(c) => {
+                let word: String = chars[index..]
+                    .iter()
+                    .take_while(|c| is_word_char(**c))
+                    .collect();
+                let length = word.chars().count();
+                (TokenKind::Word(word), length)
+            }
+            other => {
+                return Err(FilterError {
+                    column,
+                    kind: FilterErrorKind::UnexpectedChar(other),
+                });
+            }
+        };
+        tokens.push(Token { kind, column });
+        index += length;
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    end_column: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn at_end(&self, kind: FilterErrorKind) -> FilterError {
+        FilterError {
+            column: self.end_column,
+            kind,
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, FilterError> {
+        let mut expr: FilterExpr = self.parse_and()?;
+        while self.peek().is_some_and(|token| token.is_keyword("or")) {
+            self.position += 1;
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, FilterError> {
+        let mut expr: FilterExpr = self.parse_unary()?;
+        while self.peek().is_some_and(|token| token.is_keyword("and")) {
+            self.position += 1;
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, FilterError> {
+        if self.peek().is_some_and(|token| token.is_keyword("not")) {
+            self.position += 1;
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<FilterExpr, FilterError> {
+        let Some(token) = self.next() else {
+            return Err(self.at_end(FilterErrorKind::ExpectedField(
+                "the end of the expression".to_string(),
+            )));
+        };
+        match &token.kind {
+            TokenKind::LParen => {
+                let expr: FilterExpr = self.parse_or()?;
+                match self.next() {
+                    Some(Token {
+                        kind: TokenKind::RParen,
+                        ..
+                    }) => Ok(expr),
+                    Some(other) => Err(other.error(FilterErrorKind::UnclosedParen(token.column))),
+                    None => Err(self.at_end(FilterErrorKind::UnclosedParen(token.column))),
+                }
+            }
+            TokenKind::Word(_) => self.parse_condition(token),
+            _ => Err(token.error(FilterErrorKind::ExpectedField(token.describe()))),
+        }
+    }
+
+    fn parse_condition(&mut self, field: Token) -> Result<FilterExpr, FilterError> {
+        let operator: Token = match self.next() {
+            Some(
+                token @ Token {
+                    kind: TokenKind::Op(_),
+                    ..
+                },
+            ) => token,
+            Some(other) => {
+                return Err(other.error(FilterErrorKind::ExpectedOperator(field.text())));
+            }
+            None => return Err(self.at_end(FilterErrorKind::ExpectedOperator(field.text()))),
+        };
+        let TokenKind::Op(op) = operator.kind else {
+            unreachable!("matched an operator token above")
+        };
+        let value: Token = match self.next() {
+            Some(
+                token @ Token {
+                    kind: TokenKind::Word(_) | TokenKind::Quoted(_),
+                    ..
+                },
+            ) => token,
+            Some(other) => return Err(other.error(FilterErrorKind::ExpectedValue(op))),
+            None => return Err(self.at_end(FilterErrorKind::ExpectedValue(op))),
+        };
+        Ok(FilterExpr::Condition(Condition::parse(
+            &field, &operator, &value,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Condition, FilterErrorKind, FilterExpr, Operator, ProjectRef};
+    use crate::tasks::domain::due_date::DueDate;
+    use crate::tasks::domain::priority::Priority;
+    use crate::tasks::domain::tag::Tag;
+    use crate::tasks::domain::task::{Task, TaskStatus};
+    use chrono::{TimeZone, Utc};
+    use std::convert::Infallible;
+
+    fn task(title: &str) -> Task {
+        Task::new(title.to_string()).expect("task should be created")
+    }
+
+    fn filter(text: &str) -> FilterExpr {
+        FilterExpr::parse(text).expect("filter should parse")
+    }
+
+    fn error_at(text: &str) -> (usize, FilterErrorKind) {
+        let error = FilterExpr::parse(text).expect_err("filter should be rejected");
+        (error.column, error.kind)
+    }
+
+    #[test]
+    fn example_expression_is_evaluated_against_tasks() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap();
+        let expr = filter(
+            "status:todo and (tag:backend or priority>=high) and due<7d and title~\"deploy\"",
+        );
+        let due_soon = DueDate::parse("2026-03-24").expect("due date should parse");
+        let matching = task("Deploy API")
+            .add_tag(Tag::parse("backend").expect("tag should parse"))
+            .and_then(|task| task.set_due(Some(due_soon)))
+            .expect("task should be updated");
+        let urgent = task("deploy docs")
+            .set_priority(Priority::Urgent)
+            .and_then(|task| task.set_due(Some(due_soon)))
+            .expect("task should be updated");
+        let too_late = matching
+            .clone()
+            .set_due(Some(
+                DueDate::parse("2026-04-30").expect("due date should parse"),
+            ))
+            .expect("task should be updated");
+        let done = matching.clone().mark_done().expect("done should succeed");
+
+        assert!(expr.matches(&matching, now));
+        assert!(expr.matches(&urgent, now));
+        assert!(!expr.matches(&too_late, now));
+        assert!(!expr.matches(&done, now));
+        assert!(!expr.matches(&task("Deploy"), now));
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or_and_not_tighter_than_and() {
+        let now = Utc::now();
+        let done = task("x").mark_done().expect("done should succeed");
+
+        assert!(filter("status:done or status:todo and priority:high").matches(&done, now));
+        assert!(!filter("(status:done or status:todo) and priority:high").matches(&done, now));
+        assert!(filter("not status:todo and status!=blocked").matches(&done, now));
+        assert!(filter("status:closed").matches(&done, now));
+    }
+
+    #[test]
+    fn due_none_and_missing_due_dates() {
+        let now = Utc::now();
+        let undated = task("someday");
+
+        assert!(filter("due:none").matches(&undated, now));
+        assert!(!filter("due<7d").matches(&undated, now));
+        assert!(filter("due!=today").matches(&undated, now));
+        assert!(filter("created>=-1d and modified:today").matches(&undated, now));
+    }
+
+    #[test]
+    fn day_counts_beyond_the_calendar_are_clamped_to_its_ends() {
+        let now = Utc::now();
+        let undated = task("someday");
+
+        assert!(filter("created<150000000d").matches(&undated, now));
+        assert!(!filter("created<-150000000d").matches(&undated, now));
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_column() {
+        assert_eq!(error_at("   ").1, FilterErrorKind::Empty);
+        assert_eq!(
+            error_at("stat:todo"),
+            (1, FilterErrorKind::UnknownField("stat".to_string()))
+        );
+        assert_eq!(
+            error_at("status:todo and"),
+            (
+                16,
+                FilterErrorKind::ExpectedField("the end of the expression".to_string())
+            )
+        );
+        assert_eq!(
+            error_at("tag<backend"),
+            (
+                4,
+                FilterErrorKind::UnsupportedOperator {
+                    field: "tag".to_string(),
+                    operator: Operator::Lt
+                }
+            )
+        );
+        assert!(matches!(
+            error_at("priority>=huge"),
+            (11, FilterErrorKind::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            error_at("due<9999999999999d"),
+            (5, FilterErrorKind::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            error_at("created>-99999999999d"),
+            (9, FilterErrorKind::InvalidValue { .. })
+        ));
+        assert_eq!(
+            error_at("(status:todo or tag:x"),
+            (22, FilterErrorKind::UnclosedParen(1))
+        );
+        assert_eq!(
+            error_at("title~\"deploy"),
+            (7, FilterErrorKind::UnterminatedQuote)
+        );
+        assert_eq!(
+            error_at("status & x"),
+            (8, FilterErrorKind::UnexpectedChar('&'))
+        );
+        assert_eq!(
+            error_at("status:todo tag:x"),
+            (13, FilterErrorKind::UnexpectedToken("'tag'".to_string()))
+        );
+    }
+
+    #[test]
+    fn conjuncts_and_project_resolution() {
+        let expr = filter("status:todo and project:work and (tag:a or tag:b)");
+        let project = uuid::Uuid::new_v4();
+
+        assert_eq!(expr.conjuncts().len(), 2);
+        let resolved = expr
+            .resolve_projects(&mut |name: &str| {
+                assert_eq!(name, "work");
+                Ok::<_, Infallible>(Some(project))
+            })
+            .expect("resolution should succeed");
+        assert!(resolved.conjuncts().contains(&&Condition::Project(
+            Operator::Colon,
+            ProjectRef::Resolved(Some(project))
+        )));
+        assert!(!resolved.matches(
+            &task("x").mark_done().expect("done should succeed"),
+            Utc::now()
+        ));
+        assert_eq!(
+            filter("status=in-progress").conjuncts(),
+            vec![&Condition::Status(
+                Operator::Eq,
+                super::StatusValue::Is(TaskStatus::InProgress)
+            )]
+        );
+    }
+}
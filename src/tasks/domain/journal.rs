@@ -0,0 +1,221 @@
+use crate::tasks::domain::task::Task;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+/// How many operations can be undone before the oldest is forgotten.
+pub const MAX_JOURNAL_DEPTH: usize = 100;
+
+/// One task as it was before and after an operation; `None` means the task
+/// did not exist on that side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskChange {
+    before: Option<Task>,
+    after: Option<Task>,
+}
+
+impl TaskChange {
+    pub fn new(before: Option<Task>, after: Option<Task>) -> Self {
+        Self { before, after }
+    }
+
+    pub fn before(&self) -> Option<&Task> {
+        self.before.as_ref()
+    }
+    pub fn after(&self) -> Option<&Task> {
+        self.after.as_ref()
+    }
+    pub fn task_id(&self) -> Option<Uuid> {
+        self.after
+            .as_ref()
+            .or(self.before.as_ref())
+            .map(Task::task_id)
+    }
+}
+
+/// Every task change made by one operation, undone and redone as a unit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    label: String,
+    recorded_at: DateTime<Utc>,
+    changes: Vec<TaskChange>,
+}
+
+impl JournalEntry {
+    pub fn new(label: String, recorded_at: DateTime<Utc>, changes: Vec<TaskChange>) -> Self {
+        Self {
+            label,
+            recorded_at,
+            changes,
+        }
+    }
+
+    /// The same operation with other snapshots, e.g. brought up to the
+    /// versions undo or redo left in the store.
+    pub fn with_changes(self, changes: Vec<TaskChange>) -> Self {
+        Self { changes, ..self }
+    }
+
+    /// Short description such as `delete "Buy milk"` or
+    /// `done "Release" (+2 more)`.
+    pub fn summary(&self) -> String {
+        let title = self
+            .changes
+            .first()
+            .and_then(|change| change.after().or(change.before()))
+            .map(Task::title)
+            .unwrap_or_default();
+        match self.changes.len() {
+            0 | 1 => format!("{} \"{title}\"", self.label),
+            n => format!("{} \"{title}\" (+{} more)", self.label, n - 1),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+    pub fn recorded_at(&self) -> DateTime<Utc> {
+        self.recorded_at
+    }
+    pub fn changes(&self) -> &[TaskChange] {
+        &self.changes
+    }
+}
+
+/// Undo and redo stacks. Recording a new entry forgets what could be
+/// redone, and only the latest [`MAX_JOURNAL_DEPTH`] entries are kept.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Journal {
+    #[serde(default)]
+    undo: VecDeque<JournalEntry>,
+    #[serde(default)]
+    redo: Vec<JournalEntry>,
+}
+
+impl Journal {
+    pub fn record(&mut self, entry: JournalEntry) {
+        self.redo.clear();
+        self.undo.push_back(entry);
+        while self.undo.len() > MAX_JOURNAL_DEPTH {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Moves the latest entry to the redo stack and returns it.
+    pub fn undo(&mut self) -> Option<JournalEntry> {
+        let entry = self.undo.pop_back()?;
+        self.redo.push(entry.clone());
+        Some(entry)
+    }
+
+    /// Moves the latest undone entry back to the undo stack and returns it.
+    pub fn redo(&mut self) -> Option<JournalEntry> {
+        let entry = self.redo.pop()?;
+        self.undo.push_back(entry.clone());
+        Some(entry)
+    }
+
+    /// Moves `entry` to the redo stack as `undone` if it is still the next
+    /// entry to undo; returns whether it was.
+    pub fn mark_undone(&mut self, entry: &JournalEntry, undone: JournalEntry) -> bool {
+        if self.next_undo() != Some(entry) {
+            return false;
+        }
+        self.undo.pop_back();
+        self.redo.push(undone);
+        true
+    }
+
+    /// Moves `entry` back to the undo stack as `redone` if it is still the
+    /// next entry to redo; returns whether it was.
+    pub fn mark_redone(&mut self, entry: &JournalEntry, redone: JournalEntry) -> bool {
+        if self.next_redo() != Some(entry) {
+            return false;
+        }
+        self.redo.pop();
+        self.undo.push_back(redone);
+        true
+    }
+
+    /// The entry [`Journal::undo`] would return, left in place.
+    pub fn next_undo(&self) -> Option<&JournalEntry> {
+        self.undo.back()
+    }
+
+    /// The entry [`Journal::redo`] would return, left in place.
+    pub fn next_redo(&self) -> Option<&JournalEntry> {
+        self.redo.last()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Journal, JournalEntry, MAX_JOURNAL_DEPTH};
+    use chrono::Utc;
+
+    fn entry(label: &str) -> JournalEntry {
+        JournalEntry::new(label.to_string(), Utc::now(), Vec::new())
+    }
+
+    #[test]
+    fn undo_and_redo_move_entries_between_stacks() {
+        let mut journal = Journal::default();
+        journal.record(entry("add"));
+        journal.record(entry("edit"));
+
+        assert_eq!(
+            journal.undo().map(|e| e.label().to_string()),
+            Some("edit".into())
+        );
+        assert_eq!(
+            journal.redo().map(|e| e.label().to_string()),
+            Some("edit".into())
+        );
+        assert!(journal.redo().is_none());
+
+        journal.undo();
+        journal.record(entry("delete"));
+        assert!(!journal.can_redo());
+    }
+
+    #[test]
+    fn entries_are_only_marked_undone_while_they_are_next() {
+        let mut journal = Journal::default();
+        journal.record(entry("add"));
+        let add = journal
+            .next_undo()
+            .cloned()
+            .expect("add should be recorded");
+        journal.record(entry("edit"));
+
+        assert!(!journal.mark_undone(&add, entry("add again")));
+        journal.undo();
+        assert!(journal.mark_undone(&add, entry("add again")));
+        assert_eq!(
+            journal.next_redo().map(|e| e.label().to_string()),
+            Some("add again".into())
+        );
+    }
+
+    #[test]
+    fn depth_is_bounded() {
+        let mut journal = Journal::default();
+        for i in 0..MAX_JOURNAL_DEPTH + 5 {
+            journal.record(entry(&i.to_string()));
+        }
+
+        let mut undone = 0;
+        while journal.undo().is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, MAX_JOURNAL_DEPTH);
+    }
+}
@@ -1,2 +1,10 @@
+pub mod due_date;
 pub mod errors;
+pub mod filter;
+pub mod journal;
+pub mod priority;
+pub mod project;
+pub mod recurrence;
+pub mod search;
+pub mod tag;
 pub mod task;
@@ -0,0 +1,115 @@
+use crate::tasks::domain::errors::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Priority {
+    #[default]
+    None,
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 5] = [
+        Priority::None,
+        Priority::Low,
+        Priority::Medium,
+        Priority::High,
+        Priority::Urgent,
+    ];
+
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(Priority::None),
+            "low" => Ok(Priority::Low),
+            "medium" => Ok(Priority::Medium),
+            "high" => Ok(Priority::High),
+            "urgent" => Ok(Priority::Urgent),
+            _ => Err(DomainError::InvalidPriority {
+                value: value.to_string(),
+            }),
+        }
+    }
+
+    /// Next priority in ascending order, wrapping from `Urgent` back to `None`.
+    pub fn next(self) -> Self {
+        match self {
+            Priority::None => Priority::Low,
+            Priority::Low => Priority::Medium,
+            Priority::Medium => Priority::High,
+            Priority::High => Priority::Urgent,
+            Priority::Urgent => Priority::None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Priority::None => "none",
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+            Priority::Urgent => "urgent",
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = DomainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Priority::parse(value)
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Priority;
+    use crate::tasks::domain::errors::DomainError;
+
+    #[test]
+    fn parse_accepts_known_labels_case_insensitively() {
+        assert_eq!(
+            Priority::parse("HIGH").expect("should parse"),
+            Priority::High
+        );
+        assert_eq!(
+            Priority::parse(" urgent ").expect("should parse"),
+            Priority::Urgent
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_labels() {
+        let result = Priority::parse("critical");
+
+        assert!(matches!(result, Err(DomainError::InvalidPriority { .. })));
+    }
+
+    #[test]
+    fn priorities_are_ordered_by_urgency() {
+        assert!(Priority::None < Priority::Low);
+        assert!(Priority::High < Priority::Urgent);
+    }
+
+    #[test]
+    fn next_cycles_through_all_priorities() {
+        let mut priority = Priority::None;
+        for _ in Priority::ALL {
+            priority = priority.next();
+        }
+
+        assert_eq!(priority, Priority::None);
+    }
+}
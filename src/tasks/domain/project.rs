@@ -0,0 +1,100 @@
+use crate::tasks::domain::errors::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const MAX_PROJECT_NAME_LENGTH: usize = 64;
+
+/// Named namespace for tasks. Tasks without a project belong to the implicit
+/// [`INBOX`] project, which cannot be renamed or deleted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Project {
+    id: Uuid,
+    name: String,
+    created_at: DateTime<Utc>,
+}
+
+pub const INBOX: &str = "inbox";
+
+impl Project {
+    pub fn new(name: String) -> DomainResult<Self> {
+        Ok(Project {
+            id: Uuid::new_v4(),
+            name: validate_name(name)?,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub fn rename(self, name: String) -> DomainResult<Self> {
+        Ok(Self {
+            name: validate_name(name)?,
+            ..self
+        })
+    }
+
+    pub fn has_name(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name.trim())
+    }
+
+    pub fn project_id(&self) -> Uuid {
+        self.id
+    }
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+pub fn is_inbox(name: &str) -> bool {
+    name.trim().eq_ignore_ascii_case(INBOX)
+}
+
+fn validate_name(name: String) -> DomainResult<String> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_PROJECT_NAME_LENGTH || is_inbox(&name) {
+        Err(DomainError::InvalidProjectName { name })
+    } else {
+        Ok(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Project;
+    use crate::tasks::domain::errors::DomainError;
+
+    #[test]
+    fn new_trims_name() {
+        let project = Project::new("  Work ".to_string()).expect("project should be created");
+
+        assert_eq!(project.name(), "Work");
+        assert!(project.has_name("work"));
+    }
+
+    #[test]
+    fn rejects_blank_and_reserved_names() {
+        for name in ["", "   ", "Inbox", &"x".repeat(65)] {
+            let result = Project::new(name.to_string());
+
+            assert!(
+                matches!(result, Err(DomainError::InvalidProjectName { .. })),
+                "{name:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn rename_keeps_id() {
+        let project = Project::new("work".to_string()).expect("project should be created");
+        let id = project.project_id();
+
+        let renamed = project
+            .rename("job".to_string())
+            .expect("rename should succeed");
+
+        assert_eq!(renamed.project_id(), id);
+        assert_eq!(renamed.name(), "job");
+    }
+}
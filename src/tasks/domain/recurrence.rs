@@ -0,0 +1,418 @@
+use crate::tasks::domain::errors::{DomainError, DomainResult};
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Frequency {
+    fn label(self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        }
+    }
+}
+
+/// How a task repeats: a subset of iCalendar RRULE (`FREQ` of `DAILY`,
+/// `WEEKLY` or `MONTHLY`, plus `INTERVAL`, `BYDAY`, `BYMONTHDAY`, `UNTIL` and
+/// `COUNT`). Stored in its RRULE text form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Recurrence {
+    frequency: Frequency,
+    interval: u32,
+    /// Weekly only; sorted Monday first. Empty repeats on the due weekday.
+    weekdays: Vec<Weekday>,
+    /// Monthly only; defaults to the due day, clamped to short months.
+    month_day: Option<u32>,
+    until: Option<NaiveDate>,
+    /// Occurrences left, the current one included.
+    count: Option<u32>,
+}
+
+impl Recurrence {
+    /// Accepts `daily`, `weekly`, `monthly` or RRULE text such as
+    /// `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;UNTIL=20261231`.
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        let trimmed = value.trim();
+        let invalid = |reason: &str| DomainError::InvalidRecurrence {
+            value: value.to_string(),
+            reason: reason.to_string(),
+        };
+        let alias = match trimmed.to_ascii_lowercase().as_str() {
+            "daily" => Some(Frequency::Daily),
+            "weekly" => Some(Frequency::Weekly),
+            "monthly" => Some(Frequency::Monthly),
+            _ => None,
+        };
+        if let Some(frequency) = alias {
+            return Ok(Self::every(frequency, 1));
+        }
+
+        let rule = trimmed
+            .strip_prefix("RRULE:")
+            .unwrap_or(trimmed)
+            .to_ascii_uppercase();
+        let mut frequency: Option<Frequency> = None;
+        let mut recurrence = Self::every(Frequency::Daily, 1);
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (key, val) = part
+                .split_once('=')
+                .ok_or_else(|| invalid("expected KEY=VALUE parts"))?;
+            match key {
+                "FREQ" => {
+                    frequency = Some(match val {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(invalid("FREQ must be DAILY, WEEKLY or MONTHLY")),
+                    })
+                }
+                "INTERVAL" => {
+                    recurrence.interval = val
+                        .parse()
+                        .ok()
+                        .filter(|interval| *interval > 0)
+                        .ok_or_else(|| invalid("INTERVAL must be a positive number"))?
+                }
+                "BYDAY" => {
+                    recurrence.weekdays = val
+                        .split(',')
+                        .map(parse_weekday)
+                        .collect::<Option<Vec<Weekday>>>()
+                        .ok_or_else(|| invalid("BYDAY takes MO, TU, WE, TH, FR, SA or SU"))?
+                }
+                "BYMONTHDAY" => {
+                    recurrence.month_day = Some(
+                        val.parse()
+                            .ok()
+                            .filter(|day| (1..=31).contains(day))
+                            .ok_or_else(|| invalid("BYMONTHDAY must be between 1 and 31"))?,
+                    )
+                }
+                "UNTIL" => {
+                    recurrence.until = Some(
+                        NaiveDate::parse_from_str(val, "%Y%m%d")
+                            .or_else(|_| NaiveDate::parse_from_str(val, "%Y-%m-%d"))
+                            .map_err(|_| invalid("UNTIL must be a YYYYMMDD date"))?,
+                    )
+                }
+                "COUNT" => {
+                    recurrence.count = Some(
+                        val.parse()
+                            .ok()
+                            .filter(|count| *count > 0)
+                            .ok_or_else(|| invalid("COUNT must be a positive number"))?,
+                    )
+                }
+                _ => return Err(invalid(&format!("unsupported part {key}"))),
+            }
+        }
+
+        recurrence.frequency = frequency.ok_or_else(|| invalid("FREQ is required"))?;
+        if !recurrence.weekdays.is_empty() && recurrence.frequency != Frequency::Weekly {
+            return Err(invalid("BYDAY is only supported with FREQ=WEEKLY"));
+        }
+        if recurrence.month_day.is_some() && recurrence.frequency != Frequency::Monthly {
+            return Err(invalid("BYMONTHDAY is only supported with FREQ=MONTHLY"));
+        }
+        if recurrence.until.is_some() && recurrence.count.is_some() {
+            return Err(invalid("UNTIL and COUNT cannot be combined"));
+        }
+        recurrence
+            .weekdays
+            .sort_by_key(|day| day.num_days_from_monday());
+        recurrence.weekdays.dedup();
+        Ok(recurrence)
+    }
+
+    fn every(frequency: Frequency, interval: u32) -> Self {
+        Self {
+            frequency,
+            interval,
+            weekdays: Vec::new(),
+            month_day: None,
+            until: None,
+            count: None,
+        }
+    }
+
+    /// The first occurrence after `previous` that also falls after `today`
+    /// (missed occurrences are skipped, not piled up), together with the rule
+    /// the new occurrence carries. `None` once the series is over.
+    pub fn next(&self, previous: NaiveDate, today: NaiveDate) -> Option<(NaiveDate, Recurrence)> {
+        let count = match self.count {
+            Some(1) => return None,
+            count => count.map(|count| count - 1),
+        };
+        let month_day = match self.frequency {
+            Frequency::Monthly => Some(self.month_day.unwrap_or(previous.day())),
+            _ => None,
+        };
+        let rule = Self {
+            month_day,
+            count,
+            ..self.clone()
+        };
+
+        let threshold = previous.max(today);
+        let mut date = previous;
+        while date <= threshold {
+            date = rule.step(previous, date)?;
+        }
+        match rule.until {
+            Some(until) if date > until => None,
+            _ => Some((date, rule)),
+        }
+    }
+
+    /// The occurrence following `date`; `anchor` fixes which weeks count
+    /// for weekly rules with an interval.
+    fn step(&self, anchor: NaiveDate, date: NaiveDate) -> Option<NaiveDate> {
+        match self.frequency {
+            Frequency::Daily => date.checked_add_days(Days::new(u64::from(self.interval))),
+            Frequency::Weekly if self.weekdays.is_empty() => {
+                date.checked_add_days(Days::new(7 * u64::from(self.interval)))
+            }
+            Frequency::Weekly => {
+                let anchor_week = anchor.week(Weekday::Mon).first_day();
+                let mut candidate = date;
+                for _ in 0..7 * (self.interval as usize + 1) {
+                    candidate = candidate.succ_opt()?;
+                    let weeks =
+                        (candidate.week(Weekday::Mon).first_day() - anchor_week).num_days() / 7;
+                    if weeks % i64::from(self.interval) == 0
+                        && self.weekdays.contains(&candidate.weekday())
+                    {
+                        return Some(candidate);
+                    }
+                }
+                None
+            }
+            Frequency::Monthly => {
+                let first = date
+                    .with_day(1)?
+                    .checked_add_months(Months::new(self.interval))?;
+                let last_day = first.checked_add_months(Months::new(1))?.pred_opt()?.day();
+                first.with_day(self.month_day.unwrap_or(date.day()).min(last_day))
+            }
+        }
+    }
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value.trim() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = DomainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Recurrence::parse(value)
+    }
+}
+
+impl TryFrom<String> for Recurrence {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Recurrence::parse(&value)
+    }
+}
+
+impl From<Recurrence> for String {
+    fn from(value: Recurrence) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FREQ={}", self.frequency.label())?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.weekdays.is_empty() {
+            let days: Vec<&str> = self.weekdays.iter().copied().map(weekday_code).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(day) = self.month_day {
+            write!(f, ";BYMONTHDAY={day}")?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={count}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Recurrence;
+    use chrono::NaiveDate;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).expect("date should be valid")
+    }
+
+    fn next(rule: &str, previous: NaiveDate, today: NaiveDate) -> Option<NaiveDate> {
+        Recurrence::parse(rule)
+            .expect("rule should parse")
+            .next(previous, today)
+            .map(|(date, _)| date)
+    }
+
+    #[test]
+    fn parse_accepts_aliases_and_round_trips_rrule_text() {
+        assert_eq!(
+            Recurrence::parse("Weekly")
+                .expect("alias should parse")
+                .to_string(),
+            "FREQ=WEEKLY"
+        );
+        let rule = Recurrence::parse("RRULE:freq=weekly;byday=th,mo;interval=2;until=20261231")
+            .expect("rule should parse");
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;UNTIL=20261231"
+        );
+        assert_eq!(
+            Recurrence::parse(&rule.to_string()).expect("display should parse"),
+            rule
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unsupported_rules() {
+        for rule in [
+            "yearly",
+            "FREQ=YEARLY",
+            "INTERVAL=2",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;BYDAY=MO",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=WEEKLY;COUNT=3;UNTIL=20261231",
+            "FREQ=DAILY;BYHOUR=9",
+        ] {
+            assert!(
+                Recurrence::parse(rule).is_err(),
+                "{rule} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn daily_and_weekly_rules_step_from_the_previous_due_date() {
+        let today = date(2026, 3, 1);
+        assert_eq!(
+            next("daily", date(2026, 3, 20), today),
+            Some(date(2026, 3, 21))
+        );
+        assert_eq!(
+            next("FREQ=DAILY;INTERVAL=3", date(2026, 3, 20), today),
+            Some(date(2026, 3, 23))
+        );
+        assert_eq!(
+            next("weekly", date(2026, 3, 20), today),
+            Some(date(2026, 3, 27))
+        );
+    }
+
+    #[test]
+    fn weekly_rules_pick_the_next_listed_weekday() {
+        let today = date(2026, 3, 1);
+        // 2026-03-16 is a Monday.
+        let rule = "FREQ=WEEKLY;BYDAY=MO,TH";
+        assert_eq!(
+            next(rule, date(2026, 3, 16), today),
+            Some(date(2026, 3, 19))
+        );
+        assert_eq!(
+            next(rule, date(2026, 3, 19), today),
+            Some(date(2026, 3, 23))
+        );
+        let biweekly = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH";
+        assert_eq!(
+            next(biweekly, date(2026, 3, 19), today),
+            Some(date(2026, 3, 30))
+        );
+    }
+
+    #[test]
+    fn monthly_rules_clamp_to_short_months_without_drifting() {
+        let today = date(2026, 1, 1);
+        let rule = Recurrence::parse("monthly").expect("rule should parse");
+
+        let (february, rule) = rule
+            .next(date(2026, 1, 31), today)
+            .expect("series should continue");
+        let (march, _) = rule.next(february, today).expect("series should continue");
+
+        assert_eq!(february, date(2026, 2, 28));
+        assert_eq!(march, date(2026, 3, 31));
+    }
+
+    #[test]
+    fn missed_occurrences_are_skipped() {
+        assert_eq!(
+            next("daily", date(2026, 3, 1), date(2026, 3, 21)),
+            Some(date(2026, 3, 22))
+        );
+        assert_eq!(
+            next("weekly", date(2026, 3, 2), date(2026, 3, 21)),
+            Some(date(2026, 3, 23))
+        );
+    }
+
+    #[test]
+    fn until_and_count_end_the_series() {
+        let today = date(2026, 3, 1);
+        assert_eq!(
+            next("FREQ=DAILY;UNTIL=20260321", date(2026, 3, 20), today),
+            Some(date(2026, 3, 21))
+        );
+        assert_eq!(
+            next("FREQ=DAILY;UNTIL=20260321", date(2026, 3, 21), today),
+            None
+        );
+
+        let rule = Recurrence::parse("FREQ=DAILY;COUNT=2").expect("rule should parse");
+        let (_, last) = rule
+            .next(date(2026, 3, 20), today)
+            .expect("series should continue");
+        assert_eq!(last.to_string(), "FREQ=DAILY;COUNT=1");
+        assert!(last.next(date(2026, 3, 21), today).is_none());
+    }
+}
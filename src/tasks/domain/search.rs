@@ -0,0 +1,248 @@
+use crate::tasks::domain::errors::{DomainError, DomainResult};
+use crate::tasks::domain::task::Task;
+use std::fmt;
+use std::ops::Range;
+
+/// Relevance of a term matching a whole word of the title. Weaker kinds of
+/// match score less, and matches in the notes count half.
+pub const EXACT_WORD_SCORE: u32 = 100;
+pub const WORD_PREFIX_SCORE: u32 = 80;
+pub const SUBSTRING_SCORE: u32 = 60;
+pub const TYPO_SCORE: u32 = 40;
+pub const FUZZY_SCORE: u32 = 30;
+/// Shorter terms are not matched with a typo, since nearly every short word
+/// is one edit away from another.
+pub const MIN_TYPO_TERM_LENGTH: usize = 4;
+
+/// Case-insensitive search terms; a task matches when every term matches its
+/// title or notes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    terms: Vec<String>,
+}
+
+impl SearchQuery {
+    /// Splits the text into whitespace-separated terms.
+    pub fn parse(text: &str) -> DomainResult<Self> {
+        let terms: Vec<String> = text
+            .split_whitespace()
+            .map(|term| fold(term).into_iter().collect())
+            .collect();
+        if terms.is_empty() {
+            return Err(DomainError::EmptySearch);
+        }
+        Ok(Self { terms })
+    }
+
+    pub fn terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Relevance of the task, or `None` when a term matches neither its
+    /// title nor its notes. Each term counts its best match; notes are only
+    /// searched for words, substrings and typos, not scattered letters.
+    pub fn score(&self, task: &Task) -> Option<u32> {
+        let title: Vec<char> = fold(task.title());
+        let notes: Option<Vec<char>> = task.description().map(fold);
+        let mut total: u32 = 0;
+        for term in &self.terms {
+            let term: Vec<char> = term.chars().collect();
+            let in_title: Option<u32> = term_score(&term, &title, true);
+            let in_notes: Option<u32> = notes
+                .as_deref()
+                .and_then(|notes| term_score(&term, notes, false))
+                .map(|score| score / 2);
+            total += in_title.max(in_notes)?;
+        }
+        Some(total)
+    }
+
+    /// Character ranges of `text` matched by the terms, sorted and merged:
+    /// every occurrence of a term, or the letters of its fuzzy match.
+    pub fn highlights(&self, text: &str) -> Vec<Range<usize>> {
+        let text: Vec<char> = fold(text);
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for term in &self.terms {
+            let term: Vec<char> = term.chars().collect();
+            let occurrences: Vec<Range<usize>> = text
+                .windows(term.len())
+                .enumerate()
+                .filter(|(_, window)| *window == term.as_slice())
+                .map(|(start, _)| start..start + term.len())
+                .collect();
+            if occurrences.is_empty() {
+                if let Some(positions) = fuzzy_positions(&term, &text) {
+                    ranges.extend(positions.into_iter().map(|index| index..index + 1));
+                }
+            } else {
+                ranges.extend(occurrences);
+            }
+        }
+        ranges.sort_by_key(|range| range.start);
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.terms.join(" "))
+    }
+}
+
+/// Lowercases one character at a time, so that indexes into the result are
+/// character indexes into the original text.
+fn fold(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+fn term_score(term: &[char], text: &[char], fuzzy: bool) -> Option<u32> {
+    let words: Vec<&[char]> = text
+        .split(|c| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    if words.contains(&term) {
+        Some(EXACT_WORD_SCORE)
+    } else if words.iter().any(|word| word.starts_with(term)) {
+        Some(WORD_PREFIX_SCORE)
+    } else if text.windows(term.len()).any(|window| window == term) {
+        Some(SUBSTRING_SCORE)
+    } else if term.len() >= MIN_TYPO_TERM_LENGTH
+        && words.iter().any(|word| within_one_edit(term, word))
+    {
+        Some(TYPO_SCORE)
+    } else if fuzzy {
+        // The tighter the letters sit together, the closer to FUZZY_SCORE.
+        let positions: Vec<usize> = fuzzy_positions(term, text)?;
+        let span = positions[positions.len() - 1] - positions[0] + 1;
+        Some((FUZZY_SCORE * term.len() as u32 / span as u32).max(1))
+    } else {
+        None
+    }
+}
+
+/// Positions of the most compact occurrence of the term's letters, in order,
+/// anywhere in the text.
+fn fuzzy_positions(term: &[char], text: &[char]) -> Option<Vec<usize>> {
+    let mut best: Option<Vec<usize>> = None;
+    for start in (0..text.len()).filter(|&index| text[index] == term[0]) {
+        let mut positions: Vec<usize> = vec![start];
+        for (index, c) in text.iter().enumerate().skip(start + 1) {
+            if positions.len() == term.len() {
+                break;
+            }
+            if *c == term[positions.len()] {
+                positions.push(index);
+            }
+        }
+        if positions.len() < term.len() {
+            break;
+        }
+        let span = |positions: &[usize]| positions[positions.len() - 1] - positions[0];
+        if best
+            .as_deref()
+            .is_none_or(|best| span(&positions) < span(best))
+        {
+            best = Some(positions);
+        }
+    }
+    best
+}
+
+/// One substitution, insertion, deletion or swap of neighbouring letters.
+fn within_one_edit(a: &[char], b: &[char]) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    match long.len() - short.len() {
+        0 => {
+            let diffs: Vec<usize> = (0..a.len()).filter(|&i| a[i] != b[i]).collect();
+            match diffs.as_slice() {
+                [] | [_] => true,
+                [first, second] => {
+                    *second == first + 1 && a[*first] == b[*second] && a[*second] == b[*first]
+                }
+                _ => false,
+            }
+        }
+        1 => {
+            let prefix = short.iter().zip(long).take_while(|(x, y)| x == y).count();
+            short[prefix..] == long[prefix + 1..]
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EXACT_WORD_SCORE, SUBSTRING_SCORE, SearchQuery, TYPO_SCORE, WORD_PREFIX_SCORE};
+    use crate::tasks::domain::task::Task;
+
+    fn task(title: &str) -> Task {
+        Task::new(title.to_string()).expect("task should be created")
+    }
+
+    fn query(text: &str) -> SearchQuery {
+        SearchQuery::parse(text).expect("query should parse")
+    }
+
+    #[test]
+    fn empty_query_is_rejected() {
+        assert!(SearchQuery::parse("   ").is_err());
+    }
+
+    #[test]
+    fn terms_match_case_insensitively_and_rank_by_kind() {
+        let task = task("Buy MILK at the Supermarket");
+
+        assert_eq!(query("milk").score(&task), Some(EXACT_WORD_SCORE));
+        assert_eq!(query("super").score(&task), Some(WORD_PREFIX_SCORE));
+        assert_eq!(query("ermar").score(&task), Some(SUBSTRING_SCORE));
+        assert_eq!(query("mlik").score(&task), Some(TYPO_SCORE));
+    }
+
+    #[test]
+    fn every_term_has_to_match() {
+        let task = task("Buy milk");
+
+        assert_eq!(query("buy milk").score(&task), Some(2 * EXACT_WORD_SCORE));
+        assert_eq!(query("buy bread").score(&task), None);
+    }
+
+    #[test]
+    fn fuzzy_matches_score_higher_when_letters_are_close() {
+        let tight = query("rpt").score(&task("Write report"));
+        let loose = query("wpt").score(&task("Write report"));
+
+        assert!(tight.is_some_and(|score| score > 0));
+        assert!(tight > loose);
+        assert!(tight < Some(TYPO_SCORE));
+        assert_eq!(query("zq").score(&task("Write report")), None);
+    }
+
+    #[test]
+    fn notes_count_half_and_are_not_fuzzy_matched() {
+        let task = task("Groceries")
+            .set_description(Some("remember the oat milk".to_string()))
+            .expect("description should be set");
+
+        assert_eq!(query("milk").score(&task), Some(EXACT_WORD_SCORE / 2));
+        assert_eq!(query("rtk").score(&task), None);
+    }
+
+    #[test]
+    fn highlights_cover_occurrences_and_fuzzy_letters() {
+        let query = query("milk rpt");
+
+        assert_eq!(
+            query.highlights("Milk report"),
+            vec![0..4, 5..6, 7..8, 10..11]
+        );
+    }
+}
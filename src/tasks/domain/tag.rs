@@ -0,0 +1,99 @@
+use crate::tasks::domain::errors::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// Normalised task label: lowercase ASCII letters, digits, `-` and `_`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Tag(String);
+
+impl Tag {
+    /// Trims the input, drops a leading `+` or `#` and lowercases it.
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        let trimmed = value.trim();
+        let name = trimmed
+            .strip_prefix('+')
+            .or_else(|| trimmed.strip_prefix('#'))
+            .unwrap_or(trimmed)
+            .to_lowercase();
+        let valid_chars = name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if name.is_empty() || !valid_chars || name.len() > MAX_TAG_LENGTH {
+            Err(DomainError::InvalidTag {
+                value: value.to_string(),
+            })
+        } else {
+            Ok(Tag(name))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl FromStr for Tag {
+    type Err = DomainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Tag::parse(value)
+    }
+}
+
+impl TryFrom<String> for Tag {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Tag::parse(&value)
+    }
+}
+
+impl From<Tag> for String {
+    fn from(value: Tag) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "+{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tag;
+    use crate::tasks::domain::errors::DomainError;
+
+    #[test]
+    fn parse_normalises_case_and_prefix() {
+        let tag = Tag::parse(" +Backend ").expect("tag should parse");
+
+        assert_eq!(tag.as_str(), "backend");
+        assert_eq!(tag, Tag::parse("#backend").expect("tag should parse"));
+        assert_eq!(tag.to_string(), "+backend");
+    }
+
+    #[test]
+    fn parse_accepts_digits_dashes_and_underscores() {
+        let tag = Tag::parse("sprint-12_b").expect("tag should parse");
+
+        assert_eq!(tag.as_str(), "sprint-12_b");
+    }
+
+    #[test]
+    fn parse_rejects_empty_and_invalid_characters() {
+        for value in ["", "+", "two words", "café", "a/b", &"x".repeat(33)] {
+            let result = Tag::parse(value);
+
+            assert!(
+                matches!(result, Err(DomainError::InvalidTag { .. })),
+                "{value:?} should be rejected"
+            );
+        }
+    }
+}
@@ -1,36 +1,133 @@
+use crate::tasks::domain::due_date::DueDate;
 use crate::tasks::domain::errors::{DomainError, DomainResult};
-use chrono::{DateTime, Utc};
+use crate::tasks::domain::priority::Priority;
+use crate::tasks::domain::recurrence::Recurrence;
+use crate::tasks::domain::tag::Tag;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
 use uuid::Uuid;
 
+/// Maximum title length, counted in grapheme clusters.
+pub const MAX_TITLE_LENGTH: usize = 200;
+/// Maximum description length, counted in grapheme clusters.
+pub const MAX_DESCRIPTION_LENGTH: usize = 10_000;
+/// Maximum length of the reason given when blocking a task.
+pub const MAX_BLOCKED_REASON_LENGTH: usize = 200;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Task {
     id: Uuid,
     title: String,
     status: TaskStatus,
+    /// Only set while the task is `Blocked`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    blocked_reason: Option<String>,
+    #[serde(default)]
+    priority: Priority,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<DueDate>,
+    /// Moves to the next occurrence once this one is completed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    recurrence: Option<Recurrence>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    tags: BTreeSet<Tag>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Tasks that have to be done before this one can start.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    blocked_by: BTreeSet<Uuid>,
+    /// Set for subtasks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent: Option<Uuid>,
+    /// `None` means the task lives in the inbox.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<Uuid>,
     created_at: DateTime<Utc>,
     modified_at: DateTime<Utc>,
+    /// Set while the task is in the trash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deleted_at: Option<DateTime<Utc>>,
+    /// Bumped by the repository on every save; 0 until first saved.
+    #[serde(default)]
+    version: u64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TaskStatus {
     Todo,
+    InProgress,
+    Blocked,
     Done,
+    Cancelled,
+}
+
+impl TaskStatus {
+    pub const ALL: [TaskStatus; 5] = [
+        TaskStatus::Todo,
+        TaskStatus::InProgress,
+        TaskStatus::Blocked,
+        TaskStatus::Done,
+        TaskStatus::Cancelled,
+    ];
+
+    /// The transition table. Finished tasks can only be reopened, and a
+    /// blocked task has to be unblocked before it can be completed.
+    pub fn can_transition_to(self, to: TaskStatus) -> bool {
+        use TaskStatus::*;
+        matches!(
+            (self, to),
+            (Todo, InProgress | Blocked | Done | Cancelled)
+                | (InProgress, Todo | Blocked | Done | Cancelled)
+                | (Blocked, Todo | InProgress | Cancelled)
+                | (Done | Cancelled, Todo)
+        )
+    }
+
+    /// Neither done nor cancelled.
+    pub fn is_open(self) -> bool {
+        !matches!(self, TaskStatus::Done | TaskStatus::Cancelled)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskStatus::Todo => "todo",
+            TaskStatus::InProgress => "in-progress",
+            TaskStatus::Blocked => "blocked",
+            TaskStatus::Done => "done",
+            TaskStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
 }
 
 impl Task {
     pub fn new(title: String) -> DomainResult<Self> {
-        if title.trim().is_empty() {
-            Err(DomainError::EmptyTitle)
-        } else {
-            Ok(Task {
-                id: Uuid::new_v4(),
-                title,
-                status: TaskStatus::Todo,
-                created_at: Utc::now(),
-                modified_at: Utc::now(),
-            })
-        }
+        Ok(Task {
+            id: Uuid::new_v4(),
+            title: normalize_title(&title)?,
+            status: TaskStatus::Todo,
+            blocked_reason: None,
+            priority: Priority::default(),
+            due: None,
+            recurrence: None,
+            tags: BTreeSet::new(),
+            description: None,
+            blocked_by: BTreeSet::new(),
+            parent: None,
+            project: None,
+            created_at: Utc::now(),
+            modified_at: Utc::now(),
+            deleted_at: None,
+            version: 0,
+        })
     }
 
     pub fn mark_done(self) -> DomainResult<Self> {
@@ -39,9 +136,27 @@ impl Task {
     pub fn mark_todo(self) -> DomainResult<Self> {
         self.mark_as(TaskStatus::Todo)
     }
+    pub fn start(self) -> DomainResult<Self> {
+        self.mark_as(TaskStatus::InProgress)
+    }
+    pub fn cancel(self) -> DomainResult<Self> {
+        self.mark_as(TaskStatus::Cancelled)
+    }
 
-    fn mark_as(self, status: TaskStatus) -> DomainResult<Self> {
-        if status == self.status {
+    /// A blank reason is treated as no reason.
+    pub fn block(self, reason: Option<String>) -> DomainResult<Self> {
+        let reason = normalize_blocked_reason(reason)?;
+        let task = self.mark_as(TaskStatus::Blocked)?;
+        Ok(Self {
+            blocked_reason: reason,
+            ..task
+        })
+    }
+
+    /// Moves to `status` if the transition table allows it; leaving `Blocked`
+    /// drops the reason.
+    pub fn mark_as(self, status: TaskStatus) -> DomainResult<Self> {
+        if !self.status.can_transition_to(status) {
             Err(DomainError::InvalidStatusTransition {
                 id: self.id,
                 from: self.status,
@@ -50,23 +165,199 @@ impl Task {
         } else {
             Ok(Self {
                 status,
+                blocked_reason: None,
                 modified_at: Utc::now(),
                 ..self
             })
         }
     }
     pub fn edit_title(self, title: String) -> DomainResult<Self> {
-        if title.trim().is_empty() {
-            Err(DomainError::EmptyTitle)
+        Ok(Self {
+            title: normalize_title(&title)?,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    /// A blank description clears it.
+    pub fn set_description(self, description: Option<String>) -> DomainResult<Self> {
+        Ok(Self {
+            description: normalize_description(description)?,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    pub fn set_priority(self, priority: Priority) -> DomainResult<Self> {
+        Ok(Self {
+            priority,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    pub fn set_due(self, due: Option<DueDate>) -> DomainResult<Self> {
+        Ok(Self {
+            due,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    pub fn set_recurrence(self, recurrence: Option<Recurrence>) -> DomainResult<Self> {
+        Ok(Self {
+            recurrence,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    /// A fresh open copy of a recurring task, due at the next occurrence
+    /// after `today`; a due time of day is kept. Tasks without a due date
+    /// recur from `today`. `None` for one-off tasks and finished series.
+    pub fn next_occurrence(&self, today: NaiveDate) -> Option<Task> {
+        let recurrence = self.recurrence.as_ref()?;
+        let previous = self.due.map_or(today, |due| due.date());
+        let (date, recurrence) = recurrence.next(previous, today)?;
+        let due = match self.due {
+            Some(DueDate::DateTime(date_time)) => {
+                DueDate::DateTime(date.and_time(date_time.time()).and_utc())
+            }
+            _ => DueDate::Date(date),
+        };
+        Some(Task {
+            id: Uuid::new_v4(),
+            status: TaskStatus::Todo,
+            blocked_reason: None,
+            due: Some(due),
+            recurrence: Some(recurrence),
+            blocked_by: BTreeSet::new(),
+            created_at: Utc::now(),
+            modified_at: Utc::now(),
+            deleted_at: None,
+            version: 0,
+            ..self.clone()
+        })
+    }
+
+    pub fn add_tag(self, tag: Tag) -> DomainResult<Self> {
+        let mut tags = self.tags;
+        tags.insert(tag);
+        Ok(Self {
+            tags,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    pub fn remove_tag(self, tag: &Tag) -> DomainResult<Self> {
+        let mut tags = self.tags;
+        tags.remove(tag);
+        Ok(Self {
+            tags,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    /// Moves the task to the trash at `now`; trashing it again keeps the
+    /// original time.
+    pub fn trash(self, now: DateTime<Utc>) -> DomainResult<Self> {
+        Ok(Self {
+            deleted_at: self.deleted_at.or(Some(now)),
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    pub fn restore(self) -> DomainResult<Self> {
+        if self.deleted_at.is_none() {
+            return Err(DomainError::TaskNotInTrash { id: self.id });
+        }
+        Ok(Self {
+            deleted_at: None,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    pub fn move_to_project(self, project: Option<Uuid>) -> DomainResult<Self> {
+        Ok(Self {
+            project,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    /// Only rejects a task being its own parent; deeper cycles need the
+    /// other tasks and are checked by the application layer.
+    pub fn set_parent(self, parent: Option<Uuid>) -> DomainResult<Self> {
+        if parent == Some(self.id) {
+            return Err(DomainError::SubtaskCycle {
+                id: self.id,
+                parent: self.id,
+            });
+        }
+        Ok(Self {
+            parent,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    /// Only rejects a task blocking itself; longer cycles are checked by the
+    /// application layer.
+    pub fn add_blocker(self, blocker: Uuid) -> DomainResult<Self> {
+        if blocker == self.id {
+            return Err(DomainError::DependencyCycle {
+                id: self.id,
+                blocker,
+            });
+        }
+        let mut blocked_by = self.blocked_by;
+        blocked_by.insert(blocker);
+        Ok(Self {
+            blocked_by,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    pub fn remove_blocker(self, blocker: Uuid) -> DomainResult<Self> {
+        let mut blocked_by = self.blocked_by;
+        blocked_by.remove(&blocker);
+        Ok(Self {
+            blocked_by,
+            modified_at: Utc::now(),
+            ..self
+        })
+    }
+
+    /// Refuses to start or finish while any blocker is still open.
+    pub fn ensure_unblocked(&self, open_blockers: Vec<Uuid>) -> DomainResult<()> {
+        if open_blockers.is_empty() {
+            Ok(())
         } else {
-            Ok(Self {
-                title,
-                modified_at: Utc::now(),
-                ..self
+            Err(DomainError::OpenBlockers {
+                id: self.id,
+                blockers: open_blockers,
             })
         }
     }
 
+    pub fn has_tag(&self, tag: &Tag) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// Open tasks whose due date has passed at `now`.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.status.is_open() && self.due.is_some_and(|due| due.is_overdue(now))
+    }
+
     pub fn task_id(&self) -> Uuid {
         self.id
     }
@@ -76,10 +367,268 @@ impl Task {
     pub fn status(&self) -> TaskStatus {
         self.status
     }
+    pub fn blocked_reason(&self) -> Option<&str> {
+        self.blocked_reason.as_deref()
+    }
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+    pub fn due(&self) -> Option<DueDate> {
+        self.due
+    }
+    pub fn recurrence(&self) -> Option<&Recurrence> {
+        self.recurrence.as_ref()
+    }
+    pub fn tags(&self) -> &BTreeSet<Tag> {
+        &self.tags
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    pub fn blocked_by(&self) -> &BTreeSet<Uuid> {
+        &self.blocked_by
+    }
+    pub fn parent(&self) -> Option<Uuid> {
+        self.parent
+    }
+    pub fn project(&self) -> Option<Uuid> {
+        self.project
+    }
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
     pub fn modified_at(&self) -> DateTime<Utc> {
         self.modified_at
     }
+    pub fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// The task as if read at `version`. Repositories stamp every saved task
+    /// with the next version and refuse saves made from an older one.
+    pub fn with_version(self, version: u64) -> Self {
+        Self { version, ..self }
+    }
+}
+
+/// Collapses every whitespace run (including newlines) into a single space.
+fn normalize_title(title: &str) -> DomainResult<String> {
+    let title = title.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if title.is_empty() {
+        Err(DomainError::EmptyTitle)
+    } else if title.graphemes(true).count() > MAX_TITLE_LENGTH {
+        Err(DomainError::TitleTooLong {
+            max: MAX_TITLE_LENGTH,
+        })
+    } else {
+        Ok(title)
+    }
+}
+
+fn normalize_blocked_reason(reason: Option<String>) -> DomainResult<Option<String>> {
+    let reason = reason
+        .map(|reason| reason.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .filter(|reason| !reason.is_empty());
+    match reason {
+        Some(reason) if reason.graphemes(true).count() > MAX_BLOCKED_REASON_LENGTH => {
+            Err(DomainError::BlockedReasonTooLong {
+                max: MAX_BLOCKED_REASON_LENGTH,
+            })
+        }
+        reason => Ok(reason),
+    }
+}
+
+/// Keeps the text as written apart from surrounding blank space and `\r\n`
+/// line endings.
+fn normalize_description(description: Option<String>) -> DomainResult<Option<String>> {
+    let Some(description) = description else {
+        return Ok(None);
+    };
+    let description = description.replace("\r\n", "\n");
+    let description = description.trim();
+    if description.is_empty() {
+        Ok(None)
+    } else if description.graphemes(true).count() > MAX_DESCRIPTION_LENGTH {
+        Err(DomainError::DescriptionTooLong {
+            max: MAX_DESCRIPTION_LENGTH,
+        })
+    } else {
+        Ok(Some(description.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_DESCRIPTION_LENGTH, MAX_TITLE_LENGTH, Task, TaskStatus};
+    use crate::tasks::domain::due_date::DueDate;
+    use crate::tasks::domain::errors::DomainError;
+    use crate::tasks::domain::recurrence::Recurrence;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn new_and_edit_title_normalize_whitespace() {
+        let task = Task::new("  Buy \t milk\n ".to_string()).expect("task should be created");
+        assert_eq!(task.title(), "Buy milk");
+
+        let task = task
+            .edit_title(" Buy   oat milk ".to_string())
+            .expect("title should be edited");
+        assert_eq!(task.title(), "Buy oat milk");
+    }
+
+    #[test]
+    fn blank_titles_are_rejected() {
+        assert!(matches!(
+            Task::new(" \n\t ".to_string()),
+            Err(DomainError::EmptyTitle)
+        ));
+    }
+
+    #[test]
+    fn title_length_is_counted_in_graphemes() {
+        // "e" + combining acute accent: two chars, one grapheme.
+        let accented = "e\u{301}".repeat(MAX_TITLE_LENGTH);
+        assert!(Task::new(accented).is_ok());
+
+        let task = Task::new("short".to_string()).expect("task should be created");
+        let result = task.edit_title("x".repeat(MAX_TITLE_LENGTH + 1));
+        assert!(matches!(
+            result,
+            Err(DomainError::TitleTooLong {
+                max: MAX_TITLE_LENGTH
+            })
+        ));
+    }
+
+    #[test]
+    fn description_keeps_lines_and_has_its_own_limit() {
+        let task = Task::new("Write report".to_string()).expect("task should be created");
+
+        let task = task
+            .set_description(Some("# Outline\r\n\n- intro\n- results\n\n".to_string()))
+            .expect("description should be set");
+        assert_eq!(task.description(), Some("# Outline\n\n- intro\n- results"));
+
+        let cleared = task
+            .clone()
+            .set_description(Some("   ".to_string()))
+            .expect("description should be cleared");
+        assert_eq!(cleared.description(), None);
+
+        let too_long = task.set_description(Some("x".repeat(MAX_DESCRIPTION_LENGTH + 1)));
+        assert!(matches!(
+            too_long,
+            Err(DomainError::DescriptionTooLong {
+                max: MAX_DESCRIPTION_LENGTH
+            })
+        ));
+    }
+
+    #[test]
+    fn transition_table_allows_the_documented_moves_only() {
+        use TaskStatus::*;
+        let allowed = [
+            (Todo, InProgress),
+            (Todo, Blocked),
+            (Todo, Done),
+            (Todo, Cancelled),
+            (InProgress, Todo),
+            (InProgress, Blocked),
+            (InProgress, Done),
+            (InProgress, Cancelled),
+            (Blocked, Todo),
+            (Blocked, InProgress),
+            (Blocked, Cancelled),
+            (Done, Todo),
+            (Cancelled, Todo),
+        ];
+        for from in TaskStatus::ALL {
+            for to in TaskStatus::ALL {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    allowed.contains(&(from, to)),
+                    "{from} -> {to}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn illegal_transition_returns_typed_error() {
+        let task = Task::new("ship".to_string())
+            .and_then(|task| task.block(None))
+            .expect("task should be blocked");
+
+        let result = task.mark_done();
+
+        assert!(matches!(
+            result,
+            Err(DomainError::InvalidStatusTransition {
+                from: TaskStatus::Blocked,
+                to: TaskStatus::Done,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn blocked_reason_is_kept_until_the_task_is_unblocked() {
+        let task = Task::new("deploy".to_string())
+            .and_then(|task| task.block(Some("  waiting on   QA ".to_string())))
+            .expect("task should be blocked");
+        assert_eq!(task.status(), TaskStatus::Blocked);
+        assert_eq!(task.blocked_reason(), Some("waiting on QA"));
+
+        let task = task.start().expect("task should be started");
+        assert_eq!(task.status(), TaskStatus::InProgress);
+        assert_eq!(task.blocked_reason(), None);
+    }
+
+    #[test]
+    fn next_occurrence_is_an_open_copy_due_later() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 21).expect("date should be valid");
+        let task = Task::new("standup".to_string())
+            .and_then(|task| task.set_due(Some(DueDate::parse("2026-03-21T09:30")?)))
+            .and_then(|task| task.set_recurrence(Some(Recurrence::parse("daily")?)))
+            .and_then(Task::mark_done)
+            .expect("task should be created");
+
+        let next = task.next_occurrence(today).expect("task should recur");
+
+        assert_ne!(next.task_id(), task.task_id());
+        assert_eq!(next.title(), "standup");
+        assert_eq!(next.status(), TaskStatus::Todo);
+        assert_eq!(
+            next.due(),
+            Some(DueDate::parse("2026-03-22T09:30").expect("should parse"))
+        );
+        assert_eq!(next.recurrence(), task.recurrence());
+        assert!(
+            Task::new("one-off".to_string())
+                .expect("task should be created")
+                .next_occurrence(today)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn trash_and_restore_round_trip() {
+        let now = chrono::Utc::now();
+        let task = Task::new("old idea".to_string())
+            .and_then(|task| task.trash(now))
+            .expect("task should be trashed");
+        assert!(task.is_trashed());
+        assert_eq!(task.deleted_at(), Some(now));
+
+        let task = task.restore().expect("task should be restored");
+        assert!(!task.is_trashed());
+        assert!(matches!(
+            task.restore(),
+            Err(DomainError::TaskNotInTrash { .. })
+        ));
+    }
 }
@@ -0,0 +1,11 @@
+use crate::tasks::domain::task::Task;
+use crate::tasks::ports::outputs::errors::RepoResult;
+use uuid::Uuid;
+
+/// Closed tasks moved out of the main task store so that it stays small.
+pub trait ArchiveRepository {
+    fn list_archived(&self) -> RepoResult<Vec<Task>>;
+    /// Adds `tasks` to the archive in one write, replacing any with the same id.
+    fn archive(&mut self, tasks: Vec<Task>) -> RepoResult<()>;
+    fn delete_archived(&mut self, id: Uuid) -> RepoResult<bool>;
+}
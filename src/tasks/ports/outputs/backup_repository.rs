@@ -0,0 +1,29 @@
+use crate::tasks::ports::outputs::errors::RepoResult;
+use chrono::{DateTime, Utc};
+
+/// A copy of the task store taken at some point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// How many tasks it holds; `None` when it cannot be read back.
+    pub tasks: Option<usize>,
+}
+
+impl Backup {
+    pub fn is_valid(&self) -> bool {
+        self.tasks.is_some()
+    }
+}
+
+/// Copies of the task store to fall back on, taken on request or, by
+/// stores that support it, automatically as the store changes.
+pub trait BackupRepository {
+    /// Backups in no particular order, unreadable ones included.
+    fn list_backups(&self) -> RepoResult<Vec<Backup>>;
+    /// Copies the task store as it is now.
+    fn create_backup(&mut self) -> RepoResult<Backup>;
+    /// Replaces the task store with the named backup once it has been read
+    /// back successfully.
+    fn restore_backup(&mut self, name: &str) -> RepoResult<Backup>;
+}
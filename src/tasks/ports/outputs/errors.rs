@@ -1,8 +1,32 @@
+use std::time::Duration;
 use thiserror::Error;
+use uuid::Uuid;
 
 pub type RepoResult<T> = Result<T, RepoError>;
 #[derive(Debug, Error)]
 pub enum RepoError {
     #[error("internal error: {error}")]
     InternalError { error: String },
+    #[error("'{path}' is locked by another todo process (gave up after {waited:?})")]
+    Locked { path: String, waited: Duration },
+    #[error(
+        "task {id} was changed elsewhere (expected version {expected}, found {actual}); reload and try again"
+    )]
+    Conflict {
+        id: Uuid,
+        expected: u64,
+        actual: u64,
+    },
+    #[error(
+        "the data file was written by a newer todo (schema version {found}; this build reads up to {supported}); upgrade todo to open it"
+    )]
+    NewerSchema { found: u32, supported: u32 },
+    #[error("the data file is corrupted: {error}")]
+    Corrupted { error: String },
+    #[error("there is no data file to back up yet")]
+    NothingToBackUp,
+    #[error("no backup named '{name}'")]
+    BackupNotFound { name: String },
+    #[error("backup '{name}' cannot be read ({error}); the data file was left unchanged")]
+    InvalidBackup { name: String, error: String },
 }
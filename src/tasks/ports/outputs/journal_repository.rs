@@ -0,0 +1,18 @@
+use crate::tasks::domain::journal::Journal;
+use crate::tasks::ports::outputs::errors::RepoResult;
+
+/// Keeps the undo/redo journal across runs.
+pub trait JournalRepository {
+    fn load_journal(&self) -> RepoResult<Journal>;
+    fn save_journal(&mut self, journal: Journal) -> RepoResult<()>;
+
+    /// Reads the journal, applies `update` and writes it back as one step.
+    /// Stores shared between processes must override this so that nothing
+    /// else writes the journal in between.
+    fn update_journal<T>(&mut self, update: impl FnOnce(&mut Journal) -> T) -> RepoResult<T> {
+        let mut journal: Journal = self.load_journal()?;
+        let result: T = update(&mut journal);
+        self.save_journal(journal)?;
+        Ok(result)
+    }
+}
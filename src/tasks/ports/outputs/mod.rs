@@ -1,2 +1,7 @@
+pub mod archive_repository;
+pub mod backup_repository;
+pub mod clock;
 pub mod errors;
+pub mod journal_repository;
+pub mod project_repository;
 pub mod task_repository;
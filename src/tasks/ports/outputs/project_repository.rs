@@ -0,0 +1,11 @@
+use crate::tasks::domain::project::Project;
+use crate::tasks::ports::outputs::errors::RepoResult;
+use uuid::Uuid;
+
+pub trait ProjectRepository {
+    fn save_project(&mut self, project: Project) -> RepoResult<()>;
+    fn list_projects(&self) -> RepoResult<Vec<Project>>;
+    fn find_project_by_id(&self, id: Uuid) -> RepoResult<Option<Project>>;
+    fn find_project_by_name(&self, name: &str) -> RepoResult<Option<Project>>;
+    fn delete_project(&mut self, id: Uuid) -> RepoResult<bool>;
+}
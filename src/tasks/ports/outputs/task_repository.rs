@@ -1,16 +1,194 @@
+use crate::tasks::domain::filter::{Condition, FilterExpr, Operator, ProjectRef, StatusValue};
+use crate::tasks::domain::priority::Priority;
+use crate::tasks::domain::search::SearchQuery;
+use crate::tasks::domain::tag::Tag;
 use crate::tasks::domain::task::{Task, TaskStatus};
-use crate::tasks::ports::outputs::errors::RepoResult;
+use crate::tasks::ports::outputs::errors::{RepoError, RepoResult};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{HashSet, VecDeque};
 use uuid::Uuid;
 
 pub trait TaskRepository {
-    fn save(&mut self, task: Task) -> RepoResult<()>;
+    /// Stores `task` and returns it as stored, with its new version. A task
+    /// read at an older version than the stored one is refused with
+    /// [`RepoError::Conflict`].
+    fn save(&mut self, task: Task) -> RepoResult<Task>;
+    /// Trashed tasks only match [`TaskQuery::Trashed`].
     fn list(&self, query: TaskQuery) -> RepoResult<Vec<Task>>;
-    fn find_by_id(&self, id: Uuid) -> RepoResult<Option<Task>>;
+    /// Looks a task up whether or not it is in the trash.
+    fn find_any(&self, id: Uuid) -> RepoResult<Option<Task>>;
+    /// Removes the record for good; deleting a task from the user's point of
+    /// view moves it to the trash instead.
     fn delete(&mut self, id: Uuid) -> RepoResult<bool>;
+
+    /// Saves several tasks; stores that can should do it in one write.
+    fn save_all(&mut self, tasks: Vec<Task>) -> RepoResult<Vec<Task>> {
+        tasks.into_iter().map(|task| self.save(task)).collect()
+    }
+
+    /// Tasks matching every query. The repository is asked for the first
+    /// query it can narrow its scan with; the rest are checked in memory.
+    fn list_matching(&self, queries: &[TaskQuery]) -> RepoResult<Vec<Task>> {
+        let pushed: TaskQuery = queries
+            .iter()
+            .find_map(TaskQuery::pushable)
+            .unwrap_or(TaskQuery::All);
+        Ok(self
+            .list(pushed)?
+            .into_iter()
+            .filter(|task| queries.iter().all(|query| query.matches(task)))
+            .collect())
+    }
+
+    /// Looks a task up, treating trashed tasks as missing.
+    fn find_by_id(&self, id: Uuid) -> RepoResult<Option<Task>> {
+        Ok(self.find_any(id)?.filter(|task| !task.is_trashed()))
+    }
+
+    /// Looks a task up like [`TaskRepository::find_by_id`], refusing it when
+    /// the caller expects another version, i.e. it changed since they read it.
+    fn find_at_version(&self, id: Uuid, expected: Option<u64>) -> RepoResult<Option<Task>> {
+        let task: Option<Task> = self.find_by_id(id)?;
+        match (&task, expected) {
+            (Some(task), Some(expected)) if task.version() != expected => {
+                Err(RepoError::Conflict {
+                    id,
+                    expected,
+                    actual: task.version(),
+                })
+            }
+            _ => Ok(task),
+        }
+    }
+
+    fn children(&self, id: Uuid) -> RepoResult<Vec<Task>> {
+        self.list(TaskQuery::ChildrenOf(id))
+    }
+
+    /// Children, grandchildren and so on, parents before their children.
+    fn descendants(&self, id: Uuid) -> RepoResult<Vec<Task>> {
+        let mut descendants: Vec<Task> = Vec::new();
+        let mut visited: HashSet<Uuid> = HashSet::from([id]);
+        let mut pending: VecDeque<Uuid> = VecDeque::from([id]);
+        while let Some(parent) = pending.pop_front() {
+            for child in self.children(parent)? {
+                if visited.insert(child.task_id()) {
+                    pending.push_back(child.task_id());
+                    descendants.push(child);
+                }
+            }
+        }
+        Ok(descendants)
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// `task` as a repository should store it over `stored`: refused when it
+/// was read at another version than the stored one, otherwise stamped with
+/// the next version. A task coming back from elsewhere, e.g. the archive,
+/// carries on from its own version.
+pub fn next_version(stored: Option<&Task>, task: Task) -> RepoResult<Task> {
+    if let Some(stored) = stored
+        && stored.version() != task.version()
+    {
+        return Err(RepoError::Conflict {
+            id: task.task_id(),
+            expected: task.version(),
+            actual: stored.version(),
+        });
+    }
+    let version: u64 = task.version() + 1;
+    Ok(task.with_version(version))
+}
+
+#[derive(Debug, Clone)]
 pub enum TaskQuery {
     All,
     ByStatus(TaskStatus),
+    ByPriority(Priority),
+    Overdue(DateTime<Utc>),
+    DueOn(NaiveDate),
+    DueWithin {
+        now: DateTime<Utc>,
+        days: u32,
+    },
+    HasTag(Tag),
+    HasAnyTag(Vec<Tag>),
+    HasAllTags(Vec<Tag>),
+    /// `None` selects the inbox.
+    ByProject(Option<Uuid>),
+    ChildrenOf(Uuid),
+    /// Open tasks none of whose blockers is in the given set of open task ids.
+    Ready(HashSet<Uuid>),
+    /// Tasks whose title or notes match every search term.
+    Text(SearchQuery),
+    /// Tasks in the trash, which no other query matches.
+    Trashed,
+    /// Tasks matching a filter expression whose projects have been resolved,
+    /// with relative dates taken from `now`.
+    Where {
+        filter: FilterExpr,
+        now: DateTime<Utc>,
+    },
+}
+
+impl TaskQuery {
+    pub fn matches(&self, task: &Task) -> bool {
+        if task.is_trashed() {
+            return matches!(self, TaskQuery::Trashed);
+        }
+        match self {
+            TaskQuery::All => true,
+            TaskQuery::Trashed => false,
+            TaskQuery::ByStatus(status) => task.status() == *status,
+            TaskQuery::ByPriority(priority) => task.priority() == *priority,
+            TaskQuery::Overdue(now) => task.is_overdue(*now),
+            TaskQuery::DueOn(day) => task.due().is_some_and(|due| due.is_due_on(*day)),
+            TaskQuery::DueWithin { now, days } => {
+                task.due().is_some_and(|due| due.is_due_within(*now, *days))
+            }
+            TaskQuery::HasTag(tag) => task.has_tag(tag),
+            TaskQuery::HasAnyTag(tags) => tags.iter().any(|tag| task.has_tag(tag)),
+            TaskQuery::HasAllTags(tags) => tags.iter().all(|tag| task.has_tag(tag)),
+            TaskQuery::ByProject(project) => task.project() == *project,
+            TaskQuery::ChildrenOf(parent) => task.parent() == Some(*parent),
+            TaskQuery::Text(query) => query.score(task).is_some(),
+            TaskQuery::Where { filter, now } => filter.matches(task, *now),
+            TaskQuery::Ready(open_tasks) => {
+                task.status().is_open()
+                    && task.blocked_by().iter().all(|id| !open_tasks.contains(id))
+            }
+        }
+    }
+
+    /// A query selecting a superset of this one's tasks that a repository can
+    /// answer with a plain lookup: most queries are their own, and a filter
+    /// offers one of its `and`-ed equality conditions.
+    pub fn pushable(&self) -> Option<TaskQuery> {
+        match self {
+            TaskQuery::All => None,
+            TaskQuery::Where { filter, .. } => {
+                filter
+                    .conjuncts()
+                    .into_iter()
+                    .find_map(|condition| match condition {
+                        Condition::Status(
+                            Operator::Colon | Operator::Eq,
+                            StatusValue::Is(status),
+                        ) => Some(TaskQuery::ByStatus(*status)),
+                        Condition::Priority(Operator::Colon | Operator::Eq, priority) => {
+                            Some(TaskQuery::ByPriority(*priority))
+                        }
+                        Condition::Tag(Operator::Colon | Operator::Eq, tag) => {
+                            Some(TaskQuery::HasTag(tag.clone()))
+                        }
+                        Condition::Project(
+                            Operator::Colon | Operator::Eq,
+                            ProjectRef::Resolved(project),
+                        ) => Some(TaskQuery::ByProject(*project)),
+                        _ => None,
+                    })
+            }
+            other => Some(other.clone()),
+        }
+    }
 }
@@ -0,0 +1,64 @@
+//! Backing up the data file and recovering from a corrupted one.
+
+mod common;
+
+use common::{data_file, home, todo};
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+fn run(home: &Path, args: &[&str]) -> Output {
+    todo(home).args(args).output().expect("todo should run")
+}
+
+#[test]
+fn a_corrupted_file_names_the_backup_to_restore() {
+    let home = home();
+    assert!(run(home.path(), &["add", "Buy milk"]).status.success());
+    assert!(run(home.path(), &["backup", "create"]).status.success());
+    fs::write(data_file(home.path()), "{\"tasks\": [").expect("file should be written");
+
+    let listed: Output = run(home.path(), &["list"]);
+
+    assert!(!listed.status.success());
+    let stderr = String::from_utf8_lossy(&listed.stderr);
+    assert!(
+        stderr.contains("the data file is corrupted"),
+        "stderr: {stderr}"
+    );
+    let backups: Output = run(home.path(), &["--output", "json", "backup", "list"]);
+    let backups: Vec<serde_json::Value> =
+        serde_json::from_slice(&backups.stdout).expect("backup list should print a JSON array");
+    let name = backups[0]["name"]
+        .as_str()
+        .expect("backup should have a name");
+    assert!(stderr.contains(&format!("todo backup restore {name}")));
+
+    assert!(
+        run(home.path(), &["backup", "restore", name])
+            .status
+            .success()
+    );
+
+    let listed: Output = run(home.path(), &["--output", "json", "list"]);
+    let tasks: Vec<serde_json::Value> =
+        serde_json::from_slice(&listed.stdout).expect("list should print a JSON array");
+    assert_eq!(tasks.len(), 1);
+}
+
+#[test]
+fn backups_can_be_turned_off() {
+    let home = home();
+
+    for title in ["first", "second"] {
+        let added: Output = todo(home.path())
+            .env("TODO_BACKUP_KEEP", "0")
+            .args(["add", title])
+            .output()
+            .expect("todo should run");
+        assert!(added.status.success());
+    }
+
+    let backups_dir = data_file(home.path()).with_file_name("backups");
+    assert!(!backups_dir.exists());
+}
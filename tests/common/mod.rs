@@ -0,0 +1,32 @@
+//! Helpers shared by the integration tests, which run the `todo` binary
+//! against a temporary home directory.
+
+// Each test crate uses its own subset of these.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::{TempDir, tempdir};
+
+/// An empty home directory, removed when dropped.
+pub fn home() -> TempDir {
+    tempdir().expect("temp dir should be created")
+}
+
+/// The `todo` binary, keeping its data under `home`.
+pub fn todo(home: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_todo"));
+    command
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"));
+    command
+}
+
+/// The data file `todo` uses under `home`; its directory is created so that
+/// tests can write the file before the first run.
+pub fn data_file(home: &Path) -> PathBuf {
+    let dir = home.join(".config").join("todo-cli").join("data");
+    fs::create_dir_all(&dir).expect("data dir should be created");
+    dir.join("tasks.json")
+}
@@ -0,0 +1,62 @@
+//! A `todo` process changing a task while another one has it open.
+
+#![cfg(unix)]
+
+mod common;
+
+use common::{home, todo};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::process::Output;
+
+fn json(output: Output) -> serde_json::Value {
+    assert!(
+        output.status.success(),
+        "todo failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("todo should print JSON")
+}
+
+#[test]
+fn a_note_saved_over_a_newer_change_is_refused_and_handed_back() {
+    let home = home();
+    let added = json(
+        todo(home.path())
+            .args(["--output", "json", "add", "deploy"])
+            .output()
+            .expect("todo should run"),
+    );
+    let id = added["id"].as_str().expect("task should have an id");
+
+    // While the note is open, another process renames the task.
+    let editor = home.path().join("editor.sh");
+    fs::write(
+        &editor,
+        format!(
+            "#!/bin/sh\n'{}' edit {id} --title 'deploy v2' > /dev/null\nprintf 'rollback plan' > \"$1\"\n",
+            env!("CARGO_BIN_EXE_todo")
+        ),
+    )
+    .expect("editor should be written");
+    fs::set_permissions(&editor, fs::Permissions::from_mode(0o755))
+        .expect("editor should be executable");
+
+    let noted: Output = todo(home.path())
+        .env("EDITOR", &editor)
+        .args(["note", id])
+        .output()
+        .expect("todo should run");
+
+    assert!(!noted.status.success());
+    let stderr = String::from_utf8_lossy(&noted.stderr);
+    assert!(stderr.contains("rollback plan"), "stderr: {stderr}");
+    let listed = json(
+        todo(home.path())
+            .args(["--output", "json", "list"])
+            .output()
+            .expect("todo should run"),
+    );
+    assert_eq!(listed[0]["title"], "deploy v2");
+    assert!(listed[0]["description"].is_null());
+}
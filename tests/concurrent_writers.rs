@@ -0,0 +1,49 @@
+//! Several `todo` processes writing the same data file at once.
+
+mod common;
+
+use common::{data_file, home, todo};
+use std::fs;
+use std::process::{Child, Output};
+
+const WRITERS: usize = 24;
+
+#[test]
+fn concurrent_adds_are_all_kept() {
+    let home = home();
+
+    let writers: Vec<Child> = (0..WRITERS)
+        .map(|index| {
+            todo(home.path())
+                .args(["add", &format!("task {index}")])
+                .stdout(std::process::Stdio::null())
+                .spawn()
+                .expect("todo should start")
+        })
+        .collect();
+    for writer in writers {
+        let output: Output = writer.wait_with_output().expect("todo should finish");
+        assert!(
+            output.status.success(),
+            "add failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let listed: Output = todo(home.path())
+        .args(["--output", "json", "list"])
+        .output()
+        .expect("todo should run");
+    assert!(listed.status.success());
+    let tasks: Vec<serde_json::Value> =
+        serde_json::from_slice(&listed.stdout).expect("list should print a JSON array");
+    assert_eq!(tasks.len(), WRITERS);
+    let journal_file = data_file(home.path()).with_extension("journal.json");
+    let journal: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(journal_file).expect("journal should be written"))
+            .expect("journal should be JSON");
+    let entries = journal["undo"]
+        .as_array()
+        .expect("journal should list entries");
+    assert_eq!(entries.len(), WRITERS);
+}
@@ -0,0 +1,55 @@
+//! Data files written by other builds of `todo`.
+
+mod common;
+
+use common::{data_file, home, todo};
+use std::fs;
+use std::process::Output;
+
+#[test]
+fn an_unversioned_file_is_read_and_upgraded() {
+    let home = home();
+    let old = include_str!("fixtures/tasks.v1.last.json");
+    fs::write(data_file(home.path()), old).expect("fixture should be written");
+
+    let added: Output = todo(home.path())
+        .args(["add", "Ship it"])
+        .output()
+        .expect("todo should run");
+    let listed: Output = todo(home.path())
+        .args(["--output", "json", "list"])
+        .output()
+        .expect("todo should run");
+
+    assert!(added.status.success());
+    let tasks: Vec<serde_json::Value> =
+        serde_json::from_slice(&listed.stdout).expect("list should print a JSON array");
+    assert_eq!(tasks.len(), 3);
+    let backup = data_file(home.path()).with_extension("v1.backup.json");
+    assert_eq!(
+        fs::read_to_string(backup).expect("backup should exist"),
+        old
+    );
+}
+
+#[test]
+fn a_file_from_a_newer_build_is_refused_with_a_clear_error() {
+    let home = home();
+    fs::write(
+        data_file(home.path()),
+        r#"{"schema_version":99,"tasks":[]}"#,
+    )
+    .expect("file should be written");
+
+    let listed: Output = todo(home.path())
+        .arg("list")
+        .output()
+        .expect("todo should run");
+
+    assert!(!listed.status.success());
+    let stderr = String::from_utf8_lossy(&listed.stderr);
+    assert!(
+        stderr.contains("written by a newer todo (schema version 99"),
+        "stderr: {stderr}"
+    );
+}